@@ -13,7 +13,7 @@ use chrono::{DateTime, Utc};
 use clap::Parser;
 use data_anchor_client::{
     BloberIdentifier, ChainError, DataAnchorClient, DataAnchorClientError, DataAnchorClientResult,
-    FeeStrategy, Priority,
+    FeeStrategy, Priority, UploadOptions,
 };
 use futures::StreamExt;
 use itertools::{Itertools, iproduct};
@@ -321,6 +321,7 @@ async fn measure_performance(
                                 FeeStrategy::BasedOnRecentFees(priority),
                                 namespace,
                                 Some(Duration::from_secs(timeout)),
+                                UploadOptions::default(),
                             )
                             .await
                             .inspect(|_| status.increment_success())
@@ -541,3 +542,56 @@ impl StatusData {
         std::io::stdout().flush().unwrap();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatting::OutputFormat;
+
+    fn measurement() -> BenchMeasurement {
+        BenchMeasurement {
+            timestamp: DateTime::from_timestamp(0, 0).unwrap(),
+            priority: 0.5,
+            elapsed: 2.0,
+            total_size: ByteSize::kb(10),
+            bps: ByteSize::kb(5),
+            total_txs: 4,
+            tps: 2.0,
+            start_balance: 1_000,
+            end_balance: 900,
+            total_cost: 100,
+            cost_per_byte: 0,
+            total_files: 2,
+            cost_per_blob: 50,
+            upload_per_blob: 1.0,
+            declare_failures: 0,
+            insert_failures: 1,
+            finalize_failures: 0,
+        }
+    }
+
+    #[test]
+    fn measurements_csv_has_header_and_rows() {
+        let output: CommandOutput =
+            BenchmarkCommandOutput::Measurements(vec![measurement(), measurement()]).into();
+        let csv = output.serialize_output(OutputFormat::Csv);
+        let mut lines = csv.lines();
+
+        assert_eq!(
+            lines.next(),
+            Some(
+                "timestamp,priority,elapsed,total_size,bps,total_txs,tps,start_balance,\
+                 end_balance,total_cost,cost_per_byte,total_files,cost_per_blob,upload_per_blob,\
+                 declare_failures,insert_failures,finalize_failures"
+            )
+        );
+        let row = format!(
+            "1970-01-01T00:00:00Z,0.5,2.0,{},{},4,2.0,1000,900,100,0,2,50,1.0,0,1,0",
+            ByteSize::kb(10),
+            ByteSize::kb(5)
+        );
+        assert_eq!(lines.next(), Some(row.as_str()));
+        assert_eq!(lines.next(), Some(row.as_str()));
+        assert_eq!(lines.next(), None);
+    }
+}
@@ -1,17 +1,20 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use anchor_lang::{prelude::Pubkey, solana_program::clock::Slot};
 use clap::Parser;
 use data_anchor_api::pubkey_with_str;
 use data_anchor_client::{
     BloberIdentifier, DataAnchorClient, DataAnchorClientResult, FeeStrategy, Priority,
-    TransactionType,
+    TransactionType, UploadBlobResult, UploadOptions,
 };
 use itertools::Itertools;
 use serde::Serialize;
 use solana_signature::Signature;
-use tokio::io::AsyncReadExt;
-use tracing::instrument;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tracing::{instrument, warn};
 
 use crate::{Cli, NAMESPACE_MISSING_MSG, formatting::CommandOutput};
 
@@ -20,13 +23,17 @@ pub enum BlobSubCommand {
     /// Upload a blob of data. If no arguments are provided, the data will be read from stdin.
     #[command(visible_alias = "u")]
     Upload {
-        /// The path to the data to upload.
+        /// The path to the data to upload. Use `-` to read from stdin.
         #[arg(short, long)]
         data_path: Option<PathBuf>,
 
         /// The raw hex encoded data to upload.
         #[arg(long, conflicts_with = "data_path")]
         data: Option<String>,
+
+        /// Read the data to upload from stdin.
+        #[arg(long, conflicts_with_all = ["data_path", "data"])]
+        stdin: bool,
     },
     /// Discard a blob.
     #[command(visible_alias = "d")]
@@ -39,6 +46,9 @@ pub enum BlobSubCommand {
     Fetch {
         /// The signatures of the transactions from which the blob data will be fetched.
         signatures: Vec<Signature>,
+        /// Write the raw blob data to this file instead of printing it.
+        #[arg(long)]
+        output_file: Option<PathBuf>,
     },
     /// Get all blobs finalized in the given slot.
     #[command(visible_alias = "g")]
@@ -61,6 +71,11 @@ pub enum BlobCommandOutput {
         success: bool,
     },
     Fetching(Vec<Vec<u8>>),
+    /// The file the fetched blob was written to and its size in bytes.
+    Saved {
+        path: PathBuf,
+        bytes: usize,
+    },
 }
 
 impl std::fmt::Display for BlobCommandOutput {
@@ -73,6 +88,9 @@ impl std::fmt::Display for BlobCommandOutput {
                     blobs.iter().map(hex::encode).collect_vec().join(", ")
                 )
             }
+            BlobCommandOutput::Saved { path, bytes } => {
+                write!(f, "Wrote {bytes} bytes to {}", path.display())
+            }
             BlobCommandOutput::Posting {
                 slot,
                 address,
@@ -101,41 +119,51 @@ impl BlobSubCommand {
         identifier: BloberIdentifier,
     ) -> DataAnchorClientResult<CommandOutput> {
         match self {
-            BlobSubCommand::Upload { data_path, data } => {
-                let blob_data = if let Some(data_path) = data_path {
-                    tokio::fs::read(data_path)
-                        .await
-                        .unwrap_or_else(|_| panic!("failed to read file at {data_path:?}"))
-                } else if let Some(data) = data {
-                    hex::decode(data).unwrap_or_else(|_| panic!("failed to decode hex data"))
-                } else {
-                    let mut input = tokio::io::stdin();
-                    let mut data = String::new();
-                    input
-                        .read_to_string(&mut data)
+            BlobSubCommand::Upload {
+                data_path,
+                data,
+                stdin,
+            } => {
+                let blob_data = match (data_path, data) {
+                    (Some(data_path), _) if !*stdin && data_path.as_os_str() != "-" => {
+                        tokio::fs::read(data_path)
+                            .await
+                            .unwrap_or_else(|_| panic!("failed to read file at {data_path:?}"))
+                    }
+                    (_, Some(data)) => {
+                        hex::decode(data).unwrap_or_else(|_| panic!("failed to decode hex data"))
+                    }
+                    _ => read_blob_data(tokio::io::stdin())
                         .await
-                        .unwrap_or_else(|_| panic!("failed to read from stdin"));
-                    data.into_bytes()
+                        .unwrap_or_else(|_| panic!("failed to read from stdin")),
                 };
 
                 let Some(namespace) = identifier.namespace() else {
                     Cli::exit_with_missing_arg(NAMESPACE_MISSING_MSG);
                 };
 
-                let (results, address) = client
+                let UploadBlobResult {
+                    transactions,
+                    blob,
+                    finalize_slot,
+                    ..
+                } = client
                     .upload_blob(
                         &blob_data,
                         FeeStrategy::BasedOnRecentFees(Priority::VeryHigh),
                         namespace,
                         None,
+                        UploadOptions::default(),
                     )
                     .await?;
-                let last_tx = results.last().expect("there should be at least one result");
+                let last_tx = transactions
+                    .last()
+                    .expect("there should be at least one result");
                 Ok(BlobCommandOutput::Posting {
-                    slot: last_tx.slot,
-                    address,
-                    signatures: results.iter().map(|tx| tx.signature).collect(),
-                    success: !matches!(last_tx.data, TransactionType::DiscardBlob),
+                    slot: finalize_slot.unwrap_or(last_tx.slot),
+                    address: blob,
+                    signatures: transactions.iter().map(|tx| tx.signature).collect(),
+                    success: finalize_slot.is_some(),
                 }
                 .into())
             }
@@ -161,10 +189,21 @@ impl BlobSubCommand {
                 }
                 .into())
             }
-            BlobSubCommand::Fetch { signatures } => {
+            BlobSubCommand::Fetch {
+                signatures,
+                output_file,
+            } => {
                 let blob = client
                     .get_ledger_blobs_from_signatures::<Vec<u8>>(identifier, signatures.to_owned())
                     .await?;
+                if let Some(output_file) = output_file {
+                    return Ok(write_blob_data(output_file, &blob).await?.into());
+                }
+                if std::str::from_utf8(&blob).is_err() {
+                    warn!(
+                        "The fetched blob is not valid UTF-8, use --output-file to save the raw bytes"
+                    );
+                }
                 Ok(BlobCommandOutput::Fetching(vec![blob]).into())
             }
             BlobSubCommand::Get {
@@ -179,3 +218,91 @@ impl BlobSubCommand {
         }
     }
 }
+
+/// Writes the raw blob data to the given file.
+async fn write_blob_data(path: &Path, data: &[u8]) -> std::io::Result<BlobCommandOutput> {
+    tokio::fs::write(path, data).await?;
+    Ok(BlobCommandOutput::Saved {
+        path: path.to_owned(),
+        bytes: data.len(),
+    })
+}
+
+/// Reads the entire blob from the reader into a buffer.
+async fn read_blob_data<R: AsyncRead + Unpin>(mut reader: R) -> std::io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    reader.read_to_end(&mut data).await?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::error::ErrorKind;
+
+    use super::*;
+
+    #[derive(Debug, Parser)]
+    struct TestCli {
+        #[command(subcommand)]
+        command: BlobSubCommand,
+    }
+
+    fn parse(args: &[&str]) -> Result<BlobSubCommand, clap::Error> {
+        TestCli::try_parse_from(std::iter::once("test").chain(args.iter().copied()))
+            .map(|cli| cli.command)
+    }
+
+    #[test]
+    fn upload_from_stdin_flag() {
+        let command = parse(&["upload", "--stdin"]).unwrap();
+        assert!(matches!(
+            command,
+            BlobSubCommand::Upload {
+                data_path: None,
+                data: None,
+                stdin: true,
+            }
+        ));
+    }
+
+    #[test]
+    fn upload_from_dash_path() {
+        let command = parse(&["upload", "-d", "-"]).unwrap();
+        assert!(matches!(
+            command,
+            BlobSubCommand::Upload { data_path: Some(path), stdin: false, .. } if path.as_os_str() == "-"
+        ));
+    }
+
+    #[test]
+    fn stdin_conflicts_with_other_sources() {
+        let err = parse(&["upload", "--stdin", "-d", "data.bin"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+
+        let err = parse(&["upload", "--stdin", "--data", "00ff"]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ArgumentConflict);
+    }
+
+    #[tokio::test]
+    async fn writes_binary_blob_to_file() {
+        let blob = vec![0, 159, 146, 150, 255];
+        let path = std::env::temp_dir().join(format!("blob-{}.bin", Pubkey::new_unique()));
+
+        let output = write_blob_data(&path, &blob).await.unwrap();
+        let contents = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(contents, blob);
+        assert!(matches!(
+            output,
+            BlobCommandOutput::Saved { path: p, bytes: 5 } if p == path
+        ));
+    }
+
+    #[tokio::test]
+    async fn reads_binary_blob_from_reader() {
+        let blob = vec![0, 159, 146, 150, 255];
+        let data = read_blob_data(blob.as_slice()).await.unwrap();
+        assert_eq!(data, blob);
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use anchor_lang::prelude::Pubkey;
-use clap::Parser;
+use clap::{CommandFactory, Parser, error::ErrorKind};
 use data_anchor_api::{BloberWithNamespace, CustomerElf};
 use data_anchor_blober::checkpoint::Checkpoint;
 use data_anchor_client::{
@@ -168,6 +168,9 @@ impl BloberSubCommand {
                 let Some(namespace) = identifier.namespace() else {
                     Cli::exit_with_missing_arg(NAMESPACE_MISSING_MSG);
                 };
+                if let Err(e) = identifier.validate() {
+                    Cli::command().error(ErrorKind::InvalidValue, e).exit();
+                }
                 info!("Initializing blober account with namespace: {namespace}");
                 client
                     .initialize_blober(
@@ -0,0 +1,183 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use clap::Parser;
+use data_anchor_api::{CompoundInclusionProof, CustomerElf, ProofData};
+use data_anchor_blober::state::blober::Blober;
+use data_anchor_client::{
+    BloberIdentifier, DataAnchorClient, DataAnchorClientError, DataAnchorClientResult, ProofError,
+};
+use data_anchor_proofs::compound::{ProofBlob, VerifyArgs};
+use data_anchor_prover::{DATA_CORRECTNESS_ELF, POB_ELF, ProofSystem};
+use serde::Serialize;
+use tracing::instrument;
+
+use crate::{formatting::CommandOutput, proof::blober_state};
+
+#[derive(Debug, Parser)]
+pub enum CheckpointSubCommand {
+    /// Generate a checkpoint proof for a given slot locally and save it to a file.
+    #[command(visible_alias = "g")]
+    Generate {
+        /// The slot to generate the proof for.
+        slot: u64,
+        /// The file to write the proof data to.
+        #[arg(long)]
+        out: PathBuf,
+        /// The proof type to generate.
+        #[arg(long, value_enum, default_value_t = CustomerElf::DataCorrectness)]
+        proof_type: CustomerElf,
+        /// The proof system to wrap the proof in.
+        #[arg(long, value_enum, default_value_t = ProofSystem::default())]
+        proof_system: ProofSystem,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub enum CheckpointCommandOutput {
+    /// The slot the proof was generated for and the file it was written to.
+    Generated { slot: u64, out: PathBuf },
+}
+
+impl std::fmt::Display for CheckpointCommandOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckpointCommandOutput::Generated { slot, out } => {
+                write!(f, "Proof for slot {slot} written to {}", out.display())
+            }
+        }
+    }
+}
+
+impl CheckpointSubCommand {
+    #[instrument(skip(client), level = "debug")]
+    pub async fn run(
+        &self,
+        client: Arc<DataAnchorClient>,
+        identifier: BloberIdentifier,
+    ) -> DataAnchorClientResult<CommandOutput> {
+        match self {
+            CheckpointSubCommand::Generate {
+                slot,
+                out,
+                proof_type,
+                proof_system,
+            } => {
+                #[allow(deprecated)]
+                let proof = client
+                    .get_proof(*slot, identifier.clone())
+                    .await?
+                    .ok_or_else(|| {
+                        DataAnchorClientError::InvalidData(format!(
+                            "No proof found for slot {slot}"
+                        ))
+                    })?;
+                let blober = client.get_blober(identifier).await?.ok_or_else(|| {
+                    DataAnchorClientError::InvalidData(format!(
+                        "Blober {} not found",
+                        proof.blober_pubkey
+                    ))
+                })?;
+
+                let args = verify_args(&proof, blober)?;
+                let elf = match proof_type {
+                    CustomerElf::DataCorrectness => DATA_CORRECTNESS_ELF,
+                    CustomerElf::PobSla => POB_ELF,
+                };
+
+                // Simulating is cheap compared to proving, so catch inputs that would produce
+                // a proof with the wrong commitments before generating it.
+                let proof_data = data_anchor_prover::simulate_then_prove(
+                    &proof,
+                    &args,
+                    || data_anchor_prover::simulate_proof_generation(&proof, &args, elf),
+                    || data_anchor_prover::generate_proof(&proof, &args, elf, *proof_system, None),
+                )
+                .await
+                .map_err(|e| {
+                    ProofError::LocalProof(
+                        proof.blober_pubkey.to_string(),
+                        *slot,
+                        *proof_type,
+                        e.to_string(),
+                    )
+                })?;
+                write_proof_data(&proof_data, out)?;
+
+                Ok(CheckpointCommandOutput::Generated {
+                    slot: *slot,
+                    out: out.clone(),
+                }
+                .into())
+            }
+        }
+    }
+}
+
+/// Builds the prover arguments for a compound proof, using the blober state the proof ends in.
+///
+/// See [`blober_state`] for how the blober state is built. Blob data is not checked, only the blob
+/// addresses are included.
+fn verify_args(
+    proof: &CompoundInclusionProof,
+    current_blober: Blober,
+) -> DataAnchorClientResult<VerifyArgs> {
+    Ok(VerifyArgs {
+        blober: proof.blober_pubkey,
+        blober_state: blober_state(proof, current_blober)?,
+        blobs: proof
+            .blober_account_state_proof
+            .blob_keys()
+            .map(|(_, blob)| ProofBlob::empty(blob))
+            .collect(),
+    })
+}
+
+/// Serializes the proof data as pretty JSON into the given file.
+fn write_proof_data(proof_data: &ProofData, out: &Path) -> DataAnchorClientResult {
+    let json = serde_json::to_vec_pretty(proof_data).map_err(std::io::Error::from)?;
+    std::fs::write(out, json)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use anchor_lang::prelude::Pubkey;
+    use data_anchor_blober::initial_hash;
+    use data_anchor_proofs::blober_account_state::BloberAccountStateProof;
+    use data_anchor_prover::mock::MockProver;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn generated_proof_file_deserializes() {
+        let proof = CompoundInclusionProof::new(
+            Vec::new(),
+            Pubkey::new_unique(),
+            BloberAccountStateProof::new(initial_hash(), 1, BTreeMap::new()),
+        );
+        let blober = Blober {
+            hash: initial_hash(),
+            slot: 1,
+            caller: Pubkey::new_unique(),
+            namespace: "test".to_owned(),
+        };
+        let args = verify_args(&proof, blober).unwrap();
+        let proof_data =
+            MockProver::generate_proof(&proof, &args, DATA_CORRECTNESS_ELF, ProofSystem::Groth16)
+                .await
+                .unwrap();
+
+        let out = std::env::temp_dir().join(format!("proof-{}.json", Pubkey::new_unique()));
+        write_proof_data(&proof_data, &out).unwrap();
+        let contents = std::fs::read(&out).unwrap();
+        std::fs::remove_file(&out).unwrap();
+
+        let read: ProofData = serde_json::from_slice(&contents).unwrap();
+        assert_eq!(read, proof_data);
+    }
+}
@@ -0,0 +1,82 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+/// The template written by `init-config`. Every option is commented out, so a fresh file doesn't
+/// change any defaults until it is edited.
+const TEMPLATE: &str = r#"# Defaults for the data-anchor CLI.
+# Command line flags and DATA_ANCHOR_* environment variables take precedence over these values.
+
+# The program ID of the Blober program.
+# program_id = "<PROGRAM_ID>"
+
+# The namespace to use to generate the blober PDA.
+# namespace = "<NAMESPACE>"
+
+# The indexer to use, either a URL or one of "mainnet", "devnet" or "staging".
+# indexer_url = "devnet"
+
+# The path to the payer keypair.
+# payer = "<PATH_TO_KEYPAIR>"
+
+# The output format, one of "text", "json", "json-pretty", "ndjson" or "csv".
+# output = "text"
+"#;
+
+/// Default values for the CLI options, read from a TOML file.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CliConfig {
+    pub program_id: Option<String>,
+    pub namespace: Option<String>,
+    pub indexer_url: Option<String>,
+    pub payer: Option<String>,
+    pub output: Option<String>,
+}
+
+impl CliConfig {
+    /// The default location of the config file, `~/.config/data-anchor/config.toml`.
+    pub fn default_path() -> Option<PathBuf> {
+        std::env::var_os("HOME").map(|home| {
+            PathBuf::from(home)
+                .join(".config")
+                .join("data-anchor")
+                .join("config.toml")
+        })
+    }
+
+    /// Reads the config file, returning `None` if it doesn't exist.
+    pub fn load(path: &Path) -> std::io::Result<Option<Self>> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        toml::from_str(&contents)
+            .map(Some)
+            .map_err(|e| std::io::Error::new(ErrorKind::InvalidData, e))
+    }
+
+    /// Writes the commented template to `path`, creating its parent directories. An existing file
+    /// is only overwritten if `force` is set.
+    pub fn write_template(path: &Path, force: bool) -> std::io::Result<()> {
+        if !force && path.exists() {
+            return Err(std::io::Error::new(
+                ErrorKind::AlreadyExists,
+                format!(
+                    "{} already exists, pass --force to overwrite it",
+                    path.display()
+                ),
+            ));
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, TEMPLATE)
+    }
+}
@@ -1,11 +1,15 @@
+use data_anchor_client::DataAnchorClientResult;
 use serde::Serialize;
 use serde_json::json;
 
+#[cfg(feature = "prover")]
+use crate::checkpoint::CheckpointCommandOutput;
 use crate::{
     benchmark::{BenchmarkCommandOutput, write_measurements},
     blob::BlobCommandOutput,
     blober::BloberCommandOutput,
     indexer::IndexerCommandOutput,
+    proof::ProofCommandOutput,
 };
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
@@ -17,6 +21,8 @@ pub enum OutputFormat {
     Json,
     /// Output in pretty JSON format.
     JsonPretty,
+    /// Output in newline-delimited JSON format, one compact JSON object per line for lists.
+    Ndjson,
     /// Output in CSV format.
     Csv,
 }
@@ -27,6 +33,9 @@ pub enum CommandOutput {
     Blob(BlobCommandOutput),
     Indexer(IndexerCommandOutput),
     Benchmark(BenchmarkCommandOutput),
+    Proof(ProofCommandOutput),
+    #[cfg(feature = "prover")]
+    Checkpoint(CheckpointCommandOutput),
 }
 
 impl From<BloberCommandOutput> for CommandOutput {
@@ -53,6 +62,19 @@ impl From<BenchmarkCommandOutput> for CommandOutput {
     }
 }
 
+impl From<ProofCommandOutput> for CommandOutput {
+    fn from(command: ProofCommandOutput) -> Self {
+        CommandOutput::Proof(command)
+    }
+}
+
+#[cfg(feature = "prover")]
+impl From<CheckpointCommandOutput> for CommandOutput {
+    fn from(command: CheckpointCommandOutput) -> Self {
+        CommandOutput::Checkpoint(command)
+    }
+}
+
 impl std::fmt::Display for CommandOutput {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -60,6 +82,9 @@ impl std::fmt::Display for CommandOutput {
             CommandOutput::Blob(output) => write!(f, "{output}"),
             CommandOutput::Indexer(output) => write!(f, "{output}"),
             CommandOutput::Benchmark(output) => write!(f, "{output}"),
+            CommandOutput::Proof(output) => write!(f, "{output}"),
+            #[cfg(feature = "prover")]
+            CommandOutput::Checkpoint(output) => write!(f, "{output}"),
         }
     }
 }
@@ -101,6 +126,12 @@ impl CommandOutput {
                     }
                     Ok(String::from_utf8(writer.into_inner()?)?)
                 }
+                BlobCommandOutput::Saved { path, bytes } => {
+                    let mut writer = csv::WriterBuilder::new().from_writer(Vec::new());
+                    writer.write_record(["path", "bytes"])?;
+                    writer.write_record(&[format!("{}", path.display()), format!("{bytes}")])?;
+                    Ok(String::from_utf8(writer.into_inner()?)?)
+                }
             },
             CommandOutput::Indexer(output) => match output {
                 IndexerCommandOutput::Blobs(vec) => {
@@ -149,6 +180,30 @@ impl CommandOutput {
                     Ok(write_measurements(vec.clone(), true))
                 }
             },
+            CommandOutput::Proof(ProofCommandOutput::Verified {
+                blober,
+                slot,
+                error,
+            }) => {
+                let mut writer = csv::WriterBuilder::new().from_writer(Vec::new());
+                writer.write_record(["blober", "slot", "valid", "error"])?;
+                writer.write_record(&[
+                    blober.to_string(),
+                    format!("{slot}"),
+                    format!("{}", error.is_none()),
+                    error.clone().unwrap_or_default(),
+                ])?;
+                Ok(String::from_utf8(writer.into_inner()?)?)
+            }
+            #[cfg(feature = "prover")]
+            CommandOutput::Checkpoint(output) => match output {
+                CheckpointCommandOutput::Generated { slot, out } => {
+                    let mut writer = csv::WriterBuilder::new().from_writer(Vec::new());
+                    writer.write_record(["slot", "out"])?;
+                    writer.write_record(&[format!("{slot}"), format!("{}", out.display())])?;
+                    Ok(String::from_utf8(writer.into_inner()?)?)
+                }
+            },
         }
     }
 
@@ -176,6 +231,10 @@ impl CommandOutput {
                     }
                     serde_json::to_string(&output)
                 }
+                BlobCommandOutput::Saved { path, bytes } => serde_json::to_string(&json!({
+                    "path": path,
+                    "bytes": bytes,
+                })),
             },
             CommandOutput::Indexer(output) => match output {
                 IndexerCommandOutput::Blobs(vec) => {
@@ -204,6 +263,9 @@ impl CommandOutput {
                 })),
             },
             CommandOutput::Benchmark(output) => serde_json::to_string(output),
+            CommandOutput::Proof(output) => serde_json::to_string(&output.to_json()),
+            #[cfg(feature = "prover")]
+            CommandOutput::Checkpoint(output) => serde_json::to_string(output),
         };
 
         Ok(json_res?)
@@ -233,6 +295,10 @@ impl CommandOutput {
                     }
                     serde_json::to_string_pretty(&output)
                 }
+                BlobCommandOutput::Saved { path, bytes } => serde_json::to_string_pretty(&json!({
+                    "path": path,
+                    "bytes": bytes,
+                })),
             },
             CommandOutput::Indexer(output) => match output {
                 IndexerCommandOutput::Blobs(vec) => {
@@ -261,11 +327,44 @@ impl CommandOutput {
                 })),
             },
             CommandOutput::Benchmark(output) => serde_json::to_string_pretty(output),
+            CommandOutput::Proof(output) => serde_json::to_string_pretty(&output.to_json()),
+            #[cfg(feature = "prover")]
+            CommandOutput::Checkpoint(output) => serde_json::to_string_pretty(output),
         };
 
         Ok(json_res?)
     }
 
+    fn to_ndjson(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let lines = match self {
+            CommandOutput::Blob(BlobCommandOutput::Fetching(vec))
+            | CommandOutput::Indexer(IndexerCommandOutput::Blobs(vec)) => vec
+                .iter()
+                .map(|blob| serde_json::to_string(&json!({"data": hex::encode(blob)})))
+                .collect::<Result<Vec<_>, _>>()?,
+            CommandOutput::Indexer(IndexerCommandOutput::Payers(payers)) => payers
+                .iter()
+                .map(|p| serde_json::to_string(&json!({"payer": p.to_string()})))
+                .collect::<Result<Vec<_>, _>>()?,
+            CommandOutput::Benchmark(BenchmarkCommandOutput::Measurements(vec)) => vec
+                .iter()
+                .map(serde_json::to_string)
+                .collect::<Result<Vec<_>, _>>()?,
+            _ => vec![self.to_json()?],
+        };
+
+        Ok(lines.join("\n"))
+    }
+
+    /// Returns an error if the command ran but its outcome is a failure, such as a proof that
+    /// didn't verify.
+    pub fn check(&self) -> DataAnchorClientResult {
+        match self {
+            CommandOutput::Proof(output) => output.check(),
+            _ => Ok(()),
+        }
+    }
+
     /// Convert the command output to a string.
     pub fn serialize_output(&self, format: OutputFormat) -> String {
         let fallback = self.to_string();
@@ -274,9 +373,77 @@ impl CommandOutput {
             OutputFormat::Text => Ok(fallback.clone()),
             OutputFormat::Json => self.to_json().map_err(|_| ()),
             OutputFormat::JsonPretty => self.to_json_pretty().map_err(|_| ()),
+            OutputFormat::Ndjson => self.to_ndjson().map_err(|_| ()),
             OutputFormat::Csv => self.to_csv().map_err(|_| ()),
         };
 
         output.unwrap_or(fallback)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::prelude::Pubkey;
+    use data_anchor_api::RequestStatus;
+    use serde_json::Value;
+
+    use super::*;
+
+    fn scalar() -> CommandOutput {
+        IndexerCommandOutput::ProofRequestStatus("request".to_owned(), RequestStatus::Created)
+            .into()
+    }
+
+    fn list() -> CommandOutput {
+        IndexerCommandOutput::Blobs(vec![vec![1, 2], vec![3]]).into()
+    }
+
+    #[test]
+    fn json_is_a_single_compact_line() {
+        let output = scalar().serialize_output(OutputFormat::Json);
+        assert_eq!(output.lines().count(), 1);
+        let value: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["request_id"], "request");
+
+        let output = list().serialize_output(OutputFormat::Json);
+        assert_eq!(output, r#"[{"data":"0102"},{"data":"03"}]"#);
+    }
+
+    #[test]
+    fn json_pretty_is_indented() {
+        let output = scalar().serialize_output(OutputFormat::JsonPretty);
+        assert!(output.lines().count() > 1);
+        assert_eq!(
+            serde_json::from_str::<Value>(&output).unwrap(),
+            serde_json::from_str::<Value>(&scalar().serialize_output(OutputFormat::Json)).unwrap()
+        );
+
+        let output = list().serialize_output(OutputFormat::JsonPretty);
+        assert!(output.lines().count() > 1);
+        assert_eq!(
+            serde_json::from_str::<Value>(&output).unwrap(),
+            serde_json::from_str::<Value>(&list().serialize_output(OutputFormat::Json)).unwrap()
+        );
+    }
+
+    #[test]
+    fn ndjson_emits_one_object_per_line() {
+        let output = scalar().serialize_output(OutputFormat::Ndjson);
+        assert_eq!(output, scalar().serialize_output(OutputFormat::Json));
+
+        let output = list().serialize_output(OutputFormat::Ndjson);
+        assert_eq!(output, "{\"data\":\"0102\"}\n{\"data\":\"03\"}");
+
+        let payers = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let output: CommandOutput = IndexerCommandOutput::Payers(payers.to_vec()).into();
+        let lines = output
+            .serialize_output(OutputFormat::Ndjson)
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(lines.len(), payers.len());
+        for (line, payer) in lines.iter().zip(payers) {
+            assert_eq!(line["payer"], payer.to_string());
+        }
+    }
+}
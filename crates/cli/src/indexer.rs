@@ -1,15 +1,16 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
-use anchor_lang::prelude::Pubkey;
+use anchor_lang::{prelude::Pubkey, solana_program::hash::hashv};
 use chrono::{DateTime, Utc};
 use clap::{Args, Parser};
 use data_anchor_api::{CompoundInclusionProof, CustomerElf, RequestStatus, TimeRange};
 use data_anchor_client::{BloberIdentifier, DataAnchorClient, DataAnchorClientResult};
 use itertools::Itertools;
 use serde::Serialize;
+use tokio_util::sync::CancellationToken;
 use tracing::instrument;
 
-use crate::formatting::CommandOutput;
+use crate::formatting::{CommandOutput, OutputFormat};
 
 #[derive(Debug, Parser)]
 pub enum IndexerSubCommand {
@@ -57,6 +58,12 @@ pub enum IndexerSubCommand {
         payer_pubkey: Option<Pubkey>,
         #[clap(flatten)]
         time_args: TimeArgs,
+        /// Keep polling the indexer and print new blobs as they appear, until interrupted.
+        #[arg(long)]
+        watch: bool,
+        /// The number of seconds between polls in watch mode.
+        #[arg(long, default_value_t = 5, requires = "watch")]
+        interval: u64,
     },
     /// Get proof for a given blob.
     #[deprecated(since = "0.4.3", note = "please use `zk-proof` instead")]
@@ -167,6 +174,55 @@ impl std::fmt::Display for IndexerCommandOutput {
 }
 
 impl IndexerSubCommand {
+    /// Returns whether the command should keep polling the indexer instead of running once.
+    pub fn is_watch(&self) -> bool {
+        matches!(
+            self,
+            IndexerSubCommand::BlobsForNamespace { watch: true, .. }
+        )
+    }
+
+    /// Polls the indexer for the command's blobs and prints each blob not seen before in the
+    /// given format, until the cancellation token is cancelled.
+    #[instrument(skip(client, cancellation_token), level = "debug")]
+    pub async fn watch(
+        &self,
+        client: Arc<DataAnchorClient>,
+        output: OutputFormat,
+        cancellation_token: CancellationToken,
+    ) -> DataAnchorClientResult {
+        let IndexerSubCommand::BlobsForNamespace {
+            namespace,
+            payer_pubkey,
+            time_args: TimeArgs { start, end },
+            interval,
+            ..
+        } = self
+        else {
+            return Ok(());
+        };
+
+        watch_blobs(
+            || {
+                client.get_blobs_by_namespace_for_payer(
+                    namespace.to_owned(),
+                    payer_pubkey.to_owned(),
+                    Some(TimeRange {
+                        start: start.to_owned(),
+                        end: end.to_owned(),
+                    }),
+                )
+            },
+            Duration::from_secs(*interval),
+            cancellation_token,
+            |blobs| {
+                let output_value: CommandOutput = IndexerCommandOutput::Blobs(blobs).into();
+                println!("{}", output_value.serialize_output(output));
+            },
+        )
+        .await
+    }
+
     #[instrument(skip(client), level = "debug")]
     pub async fn run(
         &self,
@@ -229,6 +285,7 @@ impl IndexerSubCommand {
                 namespace,
                 payer_pubkey,
                 time_args: TimeArgs { start, end },
+                ..
             } => {
                 let data = client
                     .get_blobs_by_namespace_for_payer(
@@ -273,3 +330,71 @@ impl IndexerSubCommand {
         }
     }
 }
+
+/// Calls `fetch` every `interval` and passes the blobs that weren't returned by an earlier call to
+/// `emit`, until the cancellation token is cancelled.
+async fn watch_blobs<F, Fut>(
+    mut fetch: F,
+    interval: Duration,
+    cancellation_token: CancellationToken,
+    mut emit: impl FnMut(Vec<Vec<u8>>),
+) -> DataAnchorClientResult
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = DataAnchorClientResult<Vec<Vec<u8>>>>,
+{
+    let mut seen = HashSet::new();
+    let mut ticker = tokio::time::interval(interval);
+
+    loop {
+        tokio::select! {
+            _ = cancellation_token.cancelled() => return Ok(()),
+            _ = ticker.tick() => {}
+        }
+
+        let blobs = tokio::select! {
+            _ = cancellation_token.cancelled() => return Ok(()),
+            blobs = fetch() => blobs?,
+        };
+        let new_blobs = blobs
+            .into_iter()
+            .filter(|blob| seen.insert(hashv(&[blob]).to_bytes()))
+            .collect::<Vec<_>>();
+        if !new_blobs.is_empty() {
+            emit(new_blobs);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn watch_emits_new_blobs_until_cancelled() {
+        let cancellation_token = CancellationToken::new();
+        let mut polls = 0;
+        let mut emitted = Vec::new();
+
+        watch_blobs(
+            || {
+                polls += 1;
+                let blobs = (0..polls.min(3)).map(|i| vec![i]).collect();
+                async move { Ok(blobs) }
+            },
+            Duration::from_millis(1),
+            cancellation_token.clone(),
+            |blobs| {
+                emitted.extend(blobs);
+                if emitted.len() == 3 {
+                    cancellation_token.cancel();
+                }
+            },
+        )
+        .await
+        .unwrap();
+
+        // Every blob is emitted exactly once, even though later polls return it again.
+        assert_eq!(emitted, vec![vec![0], vec![1], vec![2]]);
+    }
+}
@@ -6,10 +6,18 @@ use anchor_lang::prelude::Pubkey;
 use benchmark::BenchmarkSubCommand;
 use blob::BlobSubCommand;
 use blober::BloberSubCommand;
-use clap::{CommandFactory, Parser, Subcommand, error::ErrorKind};
+#[cfg(feature = "prover")]
+use checkpoint::CheckpointSubCommand;
+use clap::{
+    ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum, error::ErrorKind,
+    parser::ValueSource,
+};
+use clap_complete::Shell;
+use config::CliConfig;
 use data_anchor_client::{BloberIdentifier, DataAnchorClient, DataAnchorClientResult, IndexerUrl};
 use formatting::OutputFormat;
 use indexer::IndexerSubCommand;
+use proof::ProofSubCommand;
 use solana_cli_config::Config;
 use solana_keypair::Keypair;
 use solana_signer::{EncodableKey, Signer};
@@ -19,8 +27,12 @@ use tracing::trace;
 mod benchmark;
 mod blob;
 mod blober;
+#[cfg(feature = "prover")]
+mod checkpoint;
+mod config;
 mod formatting;
 mod indexer;
+mod proof;
 
 const NAMESPACE_MISSING_MSG: &str = "Namespace is not set. Please provide a namespace using the --namespace flag or set the DATA_ANCHOR_NAMESPACE environment variable.";
 
@@ -75,7 +87,7 @@ struct Cli {
         env = "DATA_ANCHOR_INDEXER_API_TOKEN",
         hide_env_values = true
     )]
-    pub indexer_api_token: Option<String>,
+    pub indexer_api_token: Option<ApiToken>,
 
     /// The path to the Solana [`Config`] file.
     #[arg(
@@ -86,6 +98,29 @@ struct Cli {
         default_value_t = solana_cli_config::CONFIG_FILE.as_ref().unwrap().clone()
     )]
     pub config_file: String,
+
+    /// The path to the CLI config file with default option values, see `init-config`. Defaults to
+    /// `~/.config/data-anchor/config.toml`.
+    #[arg(long, global = true, env = "DATA_ANCHOR_CLI_CONFIG")]
+    pub cli_config: Option<PathBuf>,
+}
+
+/// An API token that is redacted from [`Debug`] output, so it doesn't leak into logs.
+#[derive(Clone)]
+struct ApiToken(String);
+
+impl FromStr for ApiToken {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_owned()))
+    }
+}
+
+impl std::fmt::Debug for ApiToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "***")
+    }
 }
 
 impl Cli {
@@ -95,6 +130,69 @@ impl Cli {
             .exit()
     }
 
+    /// Fills in options that weren't set by a flag or environment variable from the CLI config.
+    fn apply_config(&mut self, config: CliConfig, matches: &ArgMatches) -> Result<(), String> {
+        if self.program_id.is_none()
+            && let Some(program_id) = config.program_id
+        {
+            self.program_id = Some(
+                Pubkey::from_str(&program_id)
+                    .map_err(|e| format!("Invalid program_id in config: {e}"))?,
+            );
+        }
+        if self.namespace.is_none() {
+            self.namespace = config.namespace;
+        }
+        if self.indexer_url.is_none()
+            && let Some(indexer_url) = config.indexer_url
+        {
+            self.indexer_url = Some(
+                IndexerUrl::from_str(&indexer_url)
+                    .map_err(|e| format!("Invalid indexer_url in config: {e}"))?,
+            );
+        }
+        if self.payer.is_none() {
+            self.payer = config.payer;
+        }
+        if matches.value_source("output") == Some(ValueSource::DefaultValue)
+            && let Some(output) = config.output
+        {
+            self.output = <OutputFormat as ValueEnum>::from_str(&output, true)
+                .map_err(|e| format!("Invalid output in config: {e}"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Loads the CLI config, if there is one, and applies it to the unset options.
+    fn load_config(&mut self, matches: &ArgMatches) {
+        let explicit = self.cli_config.is_some();
+        let Some(path) = self.cli_config.clone().or_else(CliConfig::default_path) else {
+            return;
+        };
+
+        let config = match CliConfig::load(&path) {
+            Ok(Some(config)) => config,
+            Ok(None) if !explicit => return,
+            Ok(None) => Self::command()
+                .error(
+                    ErrorKind::Io,
+                    format!("CLI config {} not found", path.display()),
+                )
+                .exit(),
+            Err(e) => Self::command()
+                .error(
+                    ErrorKind::Io,
+                    format!("Failed to read CLI config {}: {e}", path.display()),
+                )
+                .exit(),
+        };
+
+        if let Err(msg) = self.apply_config(config, matches) {
+            Self::command().error(ErrorKind::InvalidValue, msg).exit()
+        }
+    }
+
     fn payer_keypair(&self, config: &Config) -> String {
         if let Some(payer) = &self.payer {
             return payer.to_owned();
@@ -130,6 +228,28 @@ enum Command {
     /// Subcommands for benchmarking the blober.
     #[command(subcommand, visible_alias = "m")]
     Benchmark(BenchmarkSubCommand),
+    /// Subcommands for verifying proofs locally.
+    #[command(subcommand, visible_alias = "p")]
+    Proof(ProofSubCommand),
+    /// Subcommands for generating checkpoint proofs locally.
+    #[cfg(feature = "prover")]
+    #[command(subcommand, visible_alias = "c")]
+    Checkpoint(CheckpointSubCommand),
+    /// Write a commented config file template with default values for the global options.
+    InitConfig {
+        /// The file to write the template to. Defaults to `~/.config/data-anchor/config.toml`.
+        path: Option<PathBuf>,
+        /// Overwrite the file if it already exists.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Generate a shell completion script and print it to stdout.
+    #[command(hide = true)]
+    Completions {
+        /// The shell to generate the completion script for.
+        #[arg(value_enum)]
+        shell: Shell,
+    },
 }
 
 pub struct Options {
@@ -148,7 +268,17 @@ impl Options {
     /// [`Keypair`].
     pub fn parse() -> Self {
         trace!("Parsing options");
-        let args = Cli::parse();
+        let matches = Cli::command().get_matches();
+        let mut args = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+        if let Command::Completions { shell } = args.command {
+            // Completions don't need a config or payer, so they are printed before loading them.
+            write_completions(shell, &mut std::io::stdout());
+            std::process::exit(0);
+        }
+        if let Command::InitConfig { path, force } = &args.command {
+            init_config(path.clone(), *force);
+        }
+        args.load_config(&matches);
         let config = Config::load(&args.config_file).unwrap();
         let payer_path = args.payer_keypair(&config);
         let payer = Arc::new(Keypair::read_from_file(payer_path).unwrap());
@@ -168,7 +298,7 @@ impl Options {
 
         Self {
             indexer: args.indexer_url,
-            indexer_api_token: args.indexer_api_token,
+            indexer_api_token: args.indexer_api_token.map(|token| token.0),
             command: args.command,
             program_id,
             output: args.output,
@@ -195,9 +325,26 @@ impl Options {
         );
 
         let output = match self.command {
+            Command::Indexer(subcommand) if subcommand.is_watch() => {
+                let ctrl_c_token = cancellation_token.clone();
+                tokio::spawn(async move {
+                    if tokio::signal::ctrl_c().await.is_ok() {
+                        ctrl_c_token.cancel();
+                    }
+                });
+                subcommand
+                    .watch(client, self.output, cancellation_token.clone())
+                    .await?;
+
+                cancellation_token.cancel();
+                return Ok(());
+            }
             Command::Indexer(subcommand) => subcommand.run(client, self.blober_pda).await,
             Command::Blob(subcommand) => subcommand.run(client, self.blober_pda).await,
             Command::Benchmark(subcommand) => subcommand.run(client, self.blober_pda).await,
+            Command::Proof(subcommand) => subcommand.run(client, self.blober_pda).await,
+            #[cfg(feature = "prover")]
+            Command::Checkpoint(subcommand) => subcommand.run(client, self.blober_pda).await,
             Command::Blober(subcommand) => {
                 subcommand
                     .run(
@@ -208,6 +355,9 @@ impl Options {
                     )
                     .await
             }
+            Command::Completions { .. } | Command::InitConfig { .. } => {
+                unreachable!("handled while parsing")
+            }
         }?;
 
         println!("{}", output.serialize_output(self.output));
@@ -215,6 +365,109 @@ impl Options {
         // Ensure all background tasks are stopped before exiting.
         cancellation_token.cancel();
 
-        Ok(())
+        output.check()
+    }
+}
+
+/// Writes the config template and exits.
+fn init_config(path: Option<PathBuf>, force: bool) -> ! {
+    let Some(path) = path.or_else(CliConfig::default_path) else {
+        Cli::exit_with_missing_arg("Could not determine the home directory, please pass a path")
+    };
+
+    match CliConfig::write_template(&path, force) {
+        Ok(()) => {
+            println!("Config template written to {}", path.display());
+            std::process::exit(0);
+        }
+        Err(e) => Cli::command().error(ErrorKind::Io, e).exit(),
+    }
+}
+
+/// Writes the completion script for the given shell.
+fn write_completions(shell: Shell, out: &mut impl std::io::Write) {
+    clap_complete::generate(shell, &mut Cli::command(), env!("CARGO_PKG_NAME"), out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_token_is_redacted_from_debug_output() {
+        let token = "super-secret-token";
+        let cli = Cli::try_parse_from([
+            "data-anchor",
+            "--indexer-api-token",
+            token,
+            "blob",
+            "get",
+            "1",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.indexer_api_token.as_ref().unwrap().0, token);
+        assert!(!format!("{cli:?}").contains(token));
+    }
+
+    #[test]
+    fn bash_completions_include_subcommands() {
+        let mut out = Vec::new();
+        write_completions(Shell::Bash, &mut out);
+        let script = String::from_utf8(out).unwrap();
+
+        assert!(!script.is_empty());
+        for subcommand in ["blober", "blob", "indexer", "benchmark"] {
+            assert!(
+                script.contains(subcommand),
+                "missing {subcommand} in completions"
+            );
+        }
+    }
+
+    fn parse(args: &[&str]) -> (Cli, ArgMatches) {
+        let matches = Cli::command().try_get_matches_from(args).unwrap();
+        (Cli::from_arg_matches(&matches).unwrap(), matches)
+    }
+
+    #[test]
+    fn init_config_template_round_trips() {
+        let dir = std::env::temp_dir().join(format!("cli-config-{}", Pubkey::new_unique()));
+        let path = dir.join("data-anchor").join("config.toml");
+
+        CliConfig::write_template(&path, false).unwrap();
+        assert_eq!(CliConfig::load(&path).unwrap(), Some(CliConfig::default()));
+        let err = CliConfig::write_template(&path, false).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+
+        let program_id = Pubkey::new_unique();
+        std::fs::write(
+            &path,
+            format!(
+                "program_id = \"{program_id}\"\n\
+                 namespace = \"from-config\"\n\
+                 indexer_url = \"mainnet\"\n\
+                 output = \"json\"\n"
+            ),
+        )
+        .unwrap();
+        let config = CliConfig::load(&path).unwrap().unwrap();
+
+        // Values from the file fill in unset options, flags take precedence.
+        let (mut cli, matches) = parse(&["data-anchor", "-n", "from-flag", "blob", "get", "1"]);
+        cli.apply_config(config.clone(), &matches).unwrap();
+        assert_eq!(cli.program_id, Some(program_id));
+        assert_eq!(cli.namespace.as_deref(), Some("from-flag"));
+        assert_eq!(cli.indexer_url, Some(IndexerUrl::Mainnet));
+        assert_eq!(cli.output, OutputFormat::Json);
+
+        let (mut cli, matches) = parse(&["data-anchor", "-o", "text", "blob", "get", "1"]);
+        cli.apply_config(config, &matches).unwrap();
+        assert_eq!(cli.namespace.as_deref(), Some("from-config"));
+        assert_eq!(cli.output, OutputFormat::Text);
+
+        CliConfig::write_template(&path, true).unwrap();
+        assert_eq!(CliConfig::load(&path).unwrap(), Some(CliConfig::default()));
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
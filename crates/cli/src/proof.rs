@@ -0,0 +1,297 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use anchor_lang::{AnchorSerialize, Discriminator, prelude::Pubkey, solana_program::clock::Slot};
+use clap::Parser;
+use data_anchor_api::CompoundInclusionProof;
+use data_anchor_blober::state::blober::Blober;
+use data_anchor_client::{
+    BloberIdentifier, DataAnchorClient, DataAnchorClientError, DataAnchorClientResult, ProofError,
+};
+use data_anchor_proofs::compound::ProofBlob;
+use itertools::Itertools;
+use serde::Serialize;
+use serde_json::json;
+use tracing::instrument;
+
+use crate::formatting::CommandOutput;
+
+#[derive(Debug, Parser)]
+pub enum ProofSubCommand {
+    /// Fetch the compound proof for a slot or blob from the indexer and verify it locally.
+    #[command(visible_alias = "v")]
+    Verify {
+        /// The slot to verify the proof for.
+        #[arg(required_unless_present = "blob")]
+        slot: Option<u64>,
+        /// The blob PDA to verify the proof for, instead of a slot.
+        #[arg(long, conflicts_with = "slot")]
+        blob: Option<Pubkey>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub enum ProofCommandOutput {
+    /// The blober and slot the proof was verified for, and the reason verification failed.
+    Verified {
+        blober: Pubkey,
+        slot: Slot,
+        error: Option<String>,
+    },
+}
+
+impl std::fmt::Display for ProofCommandOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProofCommandOutput::Verified {
+                blober,
+                slot,
+                error: None,
+            } => write!(f, "Proof for blober {blober} at slot {slot} is valid"),
+            ProofCommandOutput::Verified {
+                blober,
+                slot,
+                error: Some(error),
+            } => write!(
+                f,
+                "Proof for blober {blober} at slot {slot} is invalid: {error}"
+            ),
+        }
+    }
+}
+
+impl ProofCommandOutput {
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        match self {
+            ProofCommandOutput::Verified {
+                blober,
+                slot,
+                error,
+            } => json!({
+                "blober": blober.to_string(),
+                "slot": slot,
+                "valid": error.is_none(),
+                "error": error,
+            }),
+        }
+    }
+
+    /// Returns an error if verification failed, so the CLI exits with a non-zero code.
+    pub fn check(&self) -> DataAnchorClientResult {
+        match self {
+            ProofCommandOutput::Verified { error: None, .. } => Ok(()),
+            ProofCommandOutput::Verified {
+                blober,
+                slot,
+                error: Some(error),
+            } => Err(ProofError::Verification(blober.to_string(), *slot, error.clone()).into()),
+        }
+    }
+}
+
+impl ProofSubCommand {
+    #[instrument(skip(client), level = "debug")]
+    pub async fn run(
+        &self,
+        client: Arc<DataAnchorClient>,
+        identifier: BloberIdentifier,
+    ) -> DataAnchorClientResult<CommandOutput> {
+        match self {
+            ProofSubCommand::Verify { slot, blob } => {
+                #[allow(deprecated)]
+                let proof = match (slot, blob) {
+                    (_, Some(blob)) => {
+                        client.get_proof_for_blob(*blob).await?.ok_or_else(|| {
+                            DataAnchorClientError::InvalidData(format!(
+                                "No proof found for blob {blob}"
+                            ))
+                        })?
+                    }
+                    (Some(slot), None) => {
+                        client.get_proof(*slot, identifier).await?.ok_or_else(|| {
+                            DataAnchorClientError::InvalidData(format!(
+                                "No proof found for slot {slot}"
+                            ))
+                        })?
+                    }
+                    (None, None) => unreachable!("clap requires a slot or a blob"),
+                };
+
+                // The proof names its blober, which is the one to check against even if the CLI
+                // was pointed at a different one.
+                let identifier = BloberIdentifier::from(proof.blober_pubkey);
+                let blober = client
+                    .get_blober(identifier.clone())
+                    .await?
+                    .ok_or_else(|| {
+                        DataAnchorClientError::InvalidData(format!(
+                            "Blober {} not found",
+                            proof.blober_pubkey
+                        ))
+                    })?;
+
+                let mut uploads = BTreeMap::new();
+                for slot in proof
+                    .blober_account_state_proof
+                    .blob_keys()
+                    .map(|(slot, _)| slot)
+                    .dedup()
+                {
+                    let blobs = client
+                        .get_raw_blobs(slot, identifier.clone())
+                        .await?
+                        .unwrap_or_default();
+                    uploads.insert(slot, blobs);
+                }
+
+                let output = verify(&proof, blober, uploads);
+                Ok(output.into())
+            }
+        }
+    }
+}
+
+/// Builds the blober account data at the slot the proof targets.
+///
+/// Historical blober state isn't available on-chain, so the hash and slot are taken from the proof.
+/// The caller and namespace don't change over the lifetime of a blober, so they are taken from its
+/// current on-chain state.
+pub(crate) fn blober_state(
+    proof: &CompoundInclusionProof,
+    current_blober: Blober,
+) -> DataAnchorClientResult<Vec<u8>> {
+    let blober_state = Blober {
+        hash: proof.blober_account_state_proof.calculate_hash(),
+        slot: proof.target_slot(),
+        ..current_blober
+    };
+
+    Ok([Blober::DISCRIMINATOR, blober_state.try_to_vec()?.as_ref()].concat())
+}
+
+/// Pairs every blob in the proof with the uploaded data its blob proof accepts, taken from the
+/// blobs the indexer returned for the slot the blob was finalized in.
+fn proof_blobs(
+    proof: &CompoundInclusionProof,
+    mut uploads: BTreeMap<Slot, Vec<Vec<u8>>>,
+) -> Result<Vec<ProofBlob>, String> {
+    proof
+        .blober_account_state_proof
+        .blob_keys()
+        .zip(&proof.blob_proofs)
+        .map(|((slot, blob), blob_proof)| {
+            let candidates = uploads.entry(slot).or_default();
+            let position = candidates
+                .iter()
+                .position(|data| blob_proof.verify(data).is_ok())
+                .ok_or_else(|| format!("No blob data from the indexer matches blob {blob}"))?;
+
+            Ok(ProofBlob {
+                blob,
+                data: Some(candidates.swap_remove(position)),
+            })
+        })
+        .collect()
+}
+
+/// Verifies the proof against the blober state and the blob data uploaded in the slots it covers.
+fn verify(
+    proof: &CompoundInclusionProof,
+    current_blober: Blober,
+    uploads: BTreeMap<Slot, Vec<Vec<u8>>>,
+) -> ProofCommandOutput {
+    let result = blober_state(proof, current_blober)
+        .map_err(|e| e.to_string())
+        .and_then(|state| {
+            let blobs = proof_blobs(proof, uploads)?;
+            proof
+                .verify(proof.blober_pubkey, &state, &blobs)
+                .map_err(|e| e.to_string())
+        });
+
+    ProofCommandOutput::Verified {
+        blober: proof.blober_pubkey,
+        slot: proof.target_slot(),
+        error: result.err(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_anchor_blober::initial_hash;
+    use data_anchor_proofs::{
+        blob::BlobProof,
+        blober_account_state::{BlobAccount, BloberAccountStateProof},
+    };
+
+    use super::*;
+
+    fn fixture() -> (CompoundInclusionProof, Blober, BTreeMap<Slot, Vec<Vec<u8>>>) {
+        let data = b"hello world".to_vec();
+        let blob_proof = BlobProof::new(&[(0, &data)]);
+        let blob_account = BlobAccount::new(
+            Pubkey::new_unique(),
+            [
+                blob_proof.digest.as_slice(),
+                &(data.len() as u32).to_le_bytes(),
+            ]
+            .concat(),
+        );
+        let proof = CompoundInclusionProof::new(
+            vec![blob_proof],
+            Pubkey::new_unique(),
+            BloberAccountStateProof::new(
+                initial_hash(),
+                1,
+                BTreeMap::from([(2, vec![blob_account])]),
+            ),
+        );
+        let blober = Blober {
+            hash: initial_hash(),
+            slot: 1,
+            caller: Pubkey::new_unique(),
+            namespace: "test".to_owned(),
+        };
+
+        (proof, blober, BTreeMap::from([(2, vec![data])]))
+    }
+
+    #[test]
+    fn known_good_proof_passes() {
+        let (proof, blober, uploads) = fixture();
+
+        let output = verify(&proof, blober, uploads);
+        output.check().unwrap();
+        assert!(
+            matches!(
+                output,
+                ProofCommandOutput::Verified {
+                    slot: 2,
+                    error: None,
+                    ..
+                }
+            ),
+            "Expected a valid proof, got {output:?}"
+        );
+    }
+
+    #[test]
+    fn tampered_proof_fails() {
+        let (mut proof, blober, uploads) = fixture();
+        proof.blob_proofs[0].digest[0] ^= 1;
+
+        let output = verify(&proof, blober, uploads);
+        assert!(
+            matches!(output, ProofCommandOutput::Verified { error: Some(_), .. }),
+            "Expected an invalid proof, got {output:?}"
+        );
+        output.check().unwrap_err();
+    }
+
+    #[test]
+    fn tampered_blob_data_fails() {
+        let (proof, blober, mut uploads) = fixture();
+        uploads.get_mut(&2).unwrap()[0][0] ^= 1;
+
+        verify(&proof, blober, uploads).check().unwrap_err();
+    }
+}
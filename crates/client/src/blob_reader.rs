@@ -0,0 +1,136 @@
+use std::io::Read;
+
+use anchor_lang::prelude::Pubkey;
+use data_anchor_api::{RelevantInstructionWithAccounts, get_blob_chunks_from_instructions};
+use data_anchor_utils::{DataAnchorUtilsError, compression::CompressionType};
+
+use crate::DataAnchorClientResult;
+
+/// Reads the data of a blob straight from the chunks in its insert instructions, decompressing it
+/// as it is read.
+///
+/// The bytes read are the decompressed blob, still encoded, so they can be streamed into a parser
+/// without first reconstructing the whole blob in memory. See
+/// [`CompressionType::decompress_reader`] for which compression types are decompressed
+/// incrementally.
+pub struct BlobReader<'a> {
+    inner: Box<dyn Read + 'a>,
+}
+
+impl<'a> BlobReader<'a> {
+    /// Creates a reader for the given blob, checking its instructions the same way as
+    /// [`data_anchor_api::get_blob_data_from_instructions`].
+    pub fn new(
+        relevant_instructions: &'a [RelevantInstructionWithAccounts],
+        blober: Pubkey,
+        blob: Pubkey,
+    ) -> DataAnchorClientResult<Self> {
+        let chunks = ChunksReader {
+            chunks: get_blob_chunks_from_instructions(relevant_instructions, blober, blob)?,
+            next: 0,
+        };
+
+        Ok(Self {
+            inner: CompressionType::decompress_reader(chunks)
+                .map_err(DataAnchorUtilsError::from)?,
+        })
+    }
+}
+
+impl Read for BlobReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+/// Reads a list of chunks one after the other, as if they were a single buffer.
+struct ChunksReader<'a> {
+    chunks: Vec<&'a [u8]>,
+    next: usize,
+}
+
+impl Read for ChunksReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while let Some(chunk) = self.chunks.get_mut(self.next) {
+            if chunk.is_empty() {
+                self.next += 1;
+                continue;
+            }
+            return chunk.read(buf);
+        }
+
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_anchor_api::{RelevantInstruction, get_blob_data_from_instructions};
+    use data_anchor_blober::{
+        CHUNK_SIZE,
+        instruction::{DeclareBlob, FinalizeBlob, InsertChunk},
+    };
+    use data_anchor_utils::{
+        compression::{DataAnchorCompression, ZstdCompression},
+        encode_and_compress,
+        encoding::EncodingType,
+    };
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::no_compression(CompressionType::NoCompression)]
+    #[case::lz4_compression(CompressionType::Lz4Compression)]
+    #[case::flate2_compression(CompressionType::Flate2Compression)]
+    #[case::zstd_compression(CompressionType::ZstdCompression(ZstdCompression::default().0))]
+    #[case::snappy_compression(CompressionType::SnappyCompression)]
+    fn reader_matches_buffered_reconstruction(#[case] compression: CompressionType) {
+        let blober = Pubkey::new_unique();
+        let blob = Pubkey::new_unique();
+        let data = (0..5 * CHUNK_SIZE as u32).collect::<Vec<_>>();
+        let blob_data = encode_and_compress(&EncodingType::default(), &compression, &data).unwrap();
+
+        let instruction = |instruction| RelevantInstructionWithAccounts {
+            blob,
+            blober,
+            instruction,
+        };
+        // Chunks can land in any order, so insert them back to front.
+        let relevant_instructions =
+            std::iter::once(instruction(RelevantInstruction::DeclareBlob(DeclareBlob {
+                timestamp: 0,
+                blob_size: blob_data.len() as u32,
+            })))
+            .chain(
+                blob_data
+                    .chunks(CHUNK_SIZE as usize)
+                    .enumerate()
+                    .rev()
+                    .map(|(idx, chunk)| {
+                        instruction(RelevantInstruction::InsertChunk(InsertChunk {
+                            idx: idx as u16,
+                            data: chunk.to_vec(),
+                        }))
+                    }),
+            )
+            .chain(std::iter::once(instruction(
+                RelevantInstruction::FinalizeBlob(FinalizeBlob {}),
+            )))
+            .collect::<Vec<_>>();
+
+        let buffered = compression
+            .decompress(
+                &get_blob_data_from_instructions(&relevant_instructions, blober, blob).unwrap(),
+            )
+            .unwrap();
+
+        let mut streamed = Vec::new();
+        BlobReader::new(&relevant_instructions, blober, blob)
+            .unwrap()
+            .read_to_end(&mut streamed)
+            .unwrap();
+
+        assert_eq!(streamed, buffered);
+    }
+}
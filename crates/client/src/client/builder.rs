@@ -1,6 +1,9 @@
 use std::{str::FromStr, sync::Arc};
 
-use jsonrpsee::{http_client::HttpClientBuilder, ws_client::HeaderMap};
+use jsonrpsee::{
+    http_client::HttpClientBuilder,
+    ws_client::{HeaderMap, HeaderValue},
+};
 use nitro_sender::NitroSender;
 use solana_cli_config::Config;
 use solana_client::nonblocking::rpc_client::RpcClient;
@@ -40,27 +43,7 @@ where
         State::IndexerClient: IsUnset,
         State::ProofClient: IsUnset,
     {
-        let mut headers = HeaderMap::new();
-        if let Some(token) = indexer_api_token {
-            headers.insert(
-                "x-api-key",
-                token.parse().map_err(|_| {
-                    DataAnchorClientError::InvalidIndexerApiToken(
-                        "Failed to parse API token".to_owned(),
-                    )
-                })?,
-            );
-        }
-        headers.insert(
-            "user-agent",
-            format!("data-anchor-client/{}", env!("CARGO_PKG_VERSION"))
-                .parse()
-                .map_err(|_| {
-                    DataAnchorClientError::InvalidIndexerApiToken(
-                        "Failed to set user-agent".to_owned(),
-                    )
-                })?,
-        );
+        let headers = indexer_headers(indexer_api_token)?;
         let indexer_client = HttpClientBuilder::new()
             .set_headers(headers.clone())
             .build(indexer_url)?;
@@ -75,6 +58,9 @@ where
     /// Builds a new `DataAnchorClient` with an RPC client and a batch client built from the given
     /// Solana cli [`Config`].
     ///
+    /// Fails with [`crate::ChainError::ProgramNotDeployed`] if the program ID doesn't point at a
+    /// deployed program.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -113,6 +99,10 @@ where
             CommitmentConfig::from_str(&solana_config.commitment)?,
         ));
         let payer = self.get_payer().clone();
+        // Both the payer and the fee payer sign every transaction.
+        let signers = std::iter::once(payer)
+            .chain(self.get_fee_payer().cloned())
+            .collect::<Vec<_>>();
 
         let indexer_url = if let Some(indexer) = self.get_indexer() {
             indexer.url()
@@ -121,14 +111,57 @@ where
             IndexerUrl::from_genesis_hash(&genesis_hash.to_string())?.url()
         };
 
-        Ok(self
+        let client = self
             .rpc_client(rpc_client.clone())
-            .nitro_sender(
-                NitroSender::new(rpc_client.clone(), cancellation_token, vec![payer.clone()])
-                    .await?,
-            )
+            .nitro_sender(NitroSender::new(rpc_client.clone(), cancellation_token, signers).await?)
             .indexer_from_url(&indexer_url, indexer_api_token)
             .await?
-            .build())
+            .build();
+
+        client.check_program_deployed().await?;
+
+        Ok(client)
+    }
+}
+
+/// Builds the headers sent with every indexer request. The API token is marked as sensitive, so
+/// it is redacted from the [`Debug`] output of the headers and the clients using them.
+fn indexer_headers(indexer_api_token: Option<String>) -> DataAnchorClientResult<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    if let Some(token) = indexer_api_token {
+        let mut token: HeaderValue = token.parse().map_err(|_| {
+            DataAnchorClientError::InvalidIndexerApiToken("Failed to parse API token".to_owned())
+        })?;
+        token.set_sensitive(true);
+        headers.insert("x-api-key", token);
+    }
+    headers.insert(
+        "user-agent",
+        format!("data-anchor-client/{}", env!("CARGO_PKG_VERSION"))
+            .parse()
+            .map_err(|_| {
+                DataAnchorClientError::InvalidIndexerApiToken("Failed to set user-agent".to_owned())
+            })?,
+    );
+    Ok(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn api_token_is_redacted_from_debug_output() {
+        let token = "super-secret-token";
+        let headers = indexer_headers(Some(token.to_owned())).unwrap();
+
+        assert_eq!(headers["x-api-key"], token);
+        assert!(!format!("{headers:?}").contains(token));
+
+        let client = HttpClientBuilder::new()
+            .set_headers(headers)
+            .build("http://localhost:8080")
+            .unwrap();
+        assert!(!format!("{client:?}").contains(token));
     }
 }
@@ -1,43 +1,137 @@
-use anchor_lang::{prelude::Pubkey, solana_program::clock::Slot};
-use data_anchor_api::{CompoundInclusionProof, IndexerRpcClient, PubkeyFromStr, TimeRange};
+use std::{
+    collections::{BTreeMap, HashSet, VecDeque},
+    time::Duration,
+};
+
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{
+        clock::Slot,
+        hash::{HASH_BYTES, hashv},
+    },
+};
+use data_anchor_api::{
+    BlobsBySlotRange, CompoundInclusionProof, IndexerHealth, IndexerRpcClient,
+    MAX_PROOF_SLOTS_PER_CALL, MAX_SLOT_RANGE_SPAN, Page, PageRequest, PubkeyFromStr,
+    SlotRangeError, TimeRange,
+};
 use data_anchor_utils::encoding::Decodable;
+use futures::Stream;
+use jsonrpsee::{
+    core::client::Error as ClientError, http_client::transport::Error as HttpTransportError,
+};
 use solana_signer::Signer;
+use tokio::time::Instant;
 
 use super::BloberIdentifier;
-use crate::{DataAnchorClient, DataAnchorClientResult};
+use crate::{DataAnchorClient, DataAnchorClientError, DataAnchorClientResult};
 
 #[derive(thiserror::Error, Debug)]
 pub enum IndexerError {
     /// Failed to read blobs for slot {0} via indexer client: {1}
     #[error("Failed to read blobs for slot {0} via indexer client: {1}")]
-    Blobs(Slot, String),
+    Blobs(Slot, ClientError),
     /// Failed to read proof for slot {0} via indexer client: {1}
     #[error("Failed to read proof for slot {0} via indexer client: {1}")]
-    Proof(Slot, String),
+    Proof(Slot, ClientError),
+    /// Failed to check the indexer health: {0}
+    #[error("Failed to check the indexer health: {0}")]
+    Health(ClientError),
+    /// Failed to read the latest indexed slot for blober {0} via indexer client: {1}
+    #[error("Failed to read the latest indexed slot for blober {0} via indexer client: {1}")]
+    LatestIndexedSlot(String, ClientError),
+    /// Failed to read blobs for slots {0} to {1} via indexer client: {2}
+    #[error("Failed to read blobs for slots {0} to {1} via indexer client: {2}")]
+    BlobsForSlotRange(Slot, Slot, ClientError),
+    /// Invalid slot range: {0}
+    #[error("Invalid slot range: {0}")]
+    InvalidSlotRange(#[from] SlotRangeError),
     /// Failed to read blobs for blober {0} via indexer client: {1}
     #[error("Failed to read blobs for blober {0} via indexer client: {1}")]
-    BlobsForBlober(String, String),
+    BlobsForBlober(String, ClientError),
     /// Failed to read blobs for payer {0} via indexer client: {1}
     #[error("Failed to read blobs for payer {0} via indexer client: {1}")]
-    BlobsForPayer(String, String),
+    BlobsForPayer(String, ClientError),
     /// Failed to read blobs for network {0} via indexer client: {1}
     #[error("Failed to read blobs for network {0} via indexer client: {1}")]
-    BlobsForNetwork(String, String),
+    BlobsForNetwork(String, ClientError),
     /// Failed to read blobs for namespace {0} via indexer client: {1}
     #[error("Failed to read blobs for namespace {0} via indexer client: {1}")]
-    BlobsForNamespace(String, String),
+    BlobsForNamespace(String, ClientError),
+    /// Failed to read proofs for {0} slots via indexer client: {1}
+    #[error("Failed to read proofs for {0} slots via indexer client: {1}")]
+    ProofsForSlots(usize, ClientError),
+    /// Too many slots requested at once: {0}
+    #[error("Requested proofs for {0} slots, the maximum is {MAX_PROOF_SLOTS_PER_CALL}")]
+    TooManySlots(usize),
     /// Failed to read proof for blob {0} via indexer client: {1}
     #[error("Failed to read proof for blob {0} via indexer client: {1}")]
-    ProofForBlob(String, String),
+    ProofForBlob(String, ClientError),
     /// Failed to read compound proof for slot {0} via indexer client: {1}
     #[error("Failed to read checkpoint proof for blober {0} and slot {1} via indexer client: {2}")]
-    ZKProof(String, u64, String),
+    ZKProof(String, u64, ClientError),
     /// Failed to read payers for network {0} via indexer client: {1}
     #[error("Failed to read payers for network {0} via indexer client: {1}")]
-    PayersForNamespace(String, String),
+    PayersForNamespace(String, ClientError),
+    /// Failed to look up a blob by content hash for blober {0} via indexer client: {1}
+    #[error("Failed to look up a blob by content hash for blober {0} via indexer client: {1}")]
+    BlobByContentHash(String, ClientError),
+    /// The indexer query budget of {0:?} was exhausted
+    #[error("The indexer query budget of {0:?} was exhausted")]
+    BudgetExhausted(Duration),
+}
+
+impl IndexerError {
+    /// Whether retrying the request that caused this error may succeed. Timeouts, dropped
+    /// connections and server-side failures are transient, while rejected requests (such as
+    /// unauthorized ones) and malformed responses are permanent.
+    pub fn is_transient(&self) -> bool {
+        let e = match self {
+            // Retrying won't give the budget back.
+            IndexerError::BudgetExhausted(_)
+            | IndexerError::InvalidSlotRange(_)
+            | IndexerError::TooManySlots(_) => return false,
+            IndexerError::Health(e)
+            | IndexerError::Blobs(_, e)
+            | IndexerError::Proof(_, e)
+            | IndexerError::LatestIndexedSlot(_, e)
+            | IndexerError::BlobsForSlotRange(_, _, e)
+            | IndexerError::BlobsForBlober(_, e)
+            | IndexerError::BlobsForPayer(_, e)
+            | IndexerError::BlobsForNetwork(_, e)
+            | IndexerError::BlobsForNamespace(_, e)
+            | IndexerError::ProofsForSlots(_, e)
+            | IndexerError::ProofForBlob(_, e)
+            | IndexerError::ZKProof(_, _, e)
+            | IndexerError::PayersForNamespace(_, e)
+            | IndexerError::BlobByContentHash(_, e) => e,
+        };
+
+        match e {
+            ClientError::RequestTimeout | ClientError::RestartNeeded(_) => true,
+            ClientError::Transport(e) => match e.downcast_ref::<HttpTransportError>() {
+                Some(HttpTransportError::Rejected { status_code }) => {
+                    *status_code == 429 || *status_code >= 500
+                }
+                Some(HttpTransportError::Http(_)) => true,
+                Some(_) => false,
+                // Websocket transport errors are connection failures.
+                None => true,
+            },
+            _ => false,
+        }
+    }
 }
 
 impl DataAnchorClient {
+    /// Checks the health of the [`IndexerRpcClient`], returning its version, indexed tip and uptime.
+    pub async fn indexer_health(&self) -> DataAnchorClientResult<IndexerHealth> {
+        self.indexer()
+            .health()
+            .await
+            .map_err(|e| IndexerError::Health(e).into())
+    }
+
     /// Fetches all blobs for a given slot from the [`IndexerRpcClient`].
     pub async fn get_blobs<T>(
         &self,
@@ -47,20 +141,77 @@ impl DataAnchorClient {
     where
         T: Decodable,
     {
+        let Some(blobs) = self.get_raw_blobs(slot, identifier).await? else {
+            return Ok(None);
+        };
+
+        self.decompress_and_decode_vec(blobs.iter().map(|b| b.as_slice()))
+            .await
+            .map(Some)
+    }
+
+    /// Fetches all blobs for a given slot from the [`IndexerRpcClient`] as they were uploaded,
+    /// without decompressing or decoding them. This is the data blob proofs are computed over.
+    pub async fn get_raw_blobs(
+        &self,
+        slot: u64,
+        identifier: BloberIdentifier,
+    ) -> DataAnchorClientResult<Option<Vec<Vec<u8>>>> {
         let blober = identifier.to_blober_address(self.program_id, self.payer.pubkey());
 
-        let Some(blobs) = self
-            .indexer()
+        self.indexer()
             .get_blobs(blober.into(), slot)
             .await
-            .map_err(|e| IndexerError::Blobs(slot, e.to_string()))?
-        else {
-            return Ok(None);
+            .map_err(|e| IndexerError::Blobs(slot, e).into())
+    }
+
+    /// Fetches the highest slot the [`IndexerRpcClient`] has processed for a given blober, or `None`
+    /// if the indexer doesn't know the blober.
+    pub async fn get_latest_indexed_slot(
+        &self,
+        identifier: BloberIdentifier,
+    ) -> DataAnchorClientResult<Option<Slot>> {
+        let blober = identifier.to_blober_address(self.program_id, self.payer.pubkey());
+
+        self.indexer()
+            .get_latest_indexed_slot(blober.into())
+            .await
+            .map_err(|e| IndexerError::LatestIndexedSlot(blober.to_string(), e).into())
+    }
+
+    /// Fetches the blobs for a given blober finalized between `start_slot` and `end_slot`,
+    /// inclusive, from the [`IndexerRpcClient`], grouped by slot. The range is validated before
+    /// querying the indexer.
+    pub async fn get_blobs_by_slot_range<T>(
+        &self,
+        identifier: BloberIdentifier,
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> DataAnchorClientResult<BTreeMap<Slot, Vec<T>>>
+    where
+        T: Decodable,
+    {
+        let request = BlobsBySlotRange {
+            blober: identifier.to_blober_address(self.program_id, self.payer.pubkey()),
+            start_slot,
+            end_slot,
         };
+        request.validate().map_err(IndexerError::from)?;
 
-        self.decompress_and_decode_vec(blobs.iter().map(|b| b.as_slice()))
+        let blobs = self
+            .indexer()
+            .get_blobs_by_slot_range(request)
             .await
-            .map(Some)
+            .map_err(|e| IndexerError::BlobsForSlotRange(start_slot, end_slot, e))?;
+
+        let mut decoded = BTreeMap::new();
+        for (slot, blobs) in blobs {
+            let blobs = self
+                .decompress_and_decode_vec(blobs.iter().map(|b| b.as_slice()))
+                .await?;
+            decoded.insert(slot, blobs);
+        }
+        Ok(decoded)
     }
 
     /// Fetches blobs for a given blober and time range from the [`IndexerRpcClient`].
@@ -78,7 +229,7 @@ impl DataAnchorClient {
             .indexer()
             .get_blobs_by_blober(blober.into(), time_range)
             .await
-            .map_err(|e| IndexerError::BlobsForBlober(blober.to_string(), e.to_string()))?;
+            .map_err(|e| IndexerError::BlobsForBlober(blober.to_string(), e))?;
 
         self.decompress_and_decode_vec(blobs.iter().map(|b| b.as_slice()))
             .await
@@ -98,12 +249,38 @@ impl DataAnchorClient {
             .indexer()
             .get_blobs_by_payer(payer.into(), network_name, time_range)
             .await
-            .map_err(|e| IndexerError::BlobsForPayer(payer.to_string(), e.to_string()))?;
+            .map_err(|e| IndexerError::BlobsForPayer(payer.to_string(), e))?;
 
         self.decompress_and_decode_vec(blobs.iter().map(|b| b.as_slice()))
             .await
     }
 
+    /// Fetches a page of blobs for a given payer, network name and time range from the
+    /// [`IndexerRpcClient`]. Pass [`PageRequest::next`] of the returned page to fetch the next one.
+    pub async fn get_blobs_by_payer_paginated<T>(
+        &self,
+        payer: Pubkey,
+        network_name: String,
+        time_range: Option<TimeRange>,
+        page: PageRequest,
+    ) -> DataAnchorClientResult<Page<T>>
+    where
+        T: Decodable,
+    {
+        let page = self
+            .indexer()
+            .get_blobs_by_payer_paginated(payer.into(), network_name, time_range, page)
+            .await
+            .map_err(|e| IndexerError::BlobsForPayer(payer.to_string(), e))?;
+
+        Ok(Page {
+            items: self
+                .decompress_and_decode_vec(page.items.iter().map(|b| b.as_slice()))
+                .await?,
+            next_cursor: page.next_cursor,
+        })
+    }
+
     /// Fetches blobs for a given network and time range from the [`IndexerRpcClient`].
     pub async fn get_blobs_by_network<T>(
         &self,
@@ -117,7 +294,7 @@ impl DataAnchorClient {
             .indexer()
             .get_blobs_by_network(network_name.clone(), time_range)
             .await
-            .map_err(|e| IndexerError::BlobsForNetwork(network_name, e.to_string()))?;
+            .map_err(|e| IndexerError::BlobsForNetwork(network_name, e))?;
 
         self.decompress_and_decode_vec(blobs.iter().map(|b| b.as_slice()))
             .await
@@ -141,7 +318,7 @@ impl DataAnchorClient {
                 time_range,
             )
             .await
-            .map_err(|e| IndexerError::BlobsForNamespace(namespace, e.to_string()))?;
+            .map_err(|e| IndexerError::BlobsForNamespace(namespace, e))?;
 
         self.decompress_and_decode_vec(blobs.iter().map(|b| b.as_slice()))
             .await
@@ -155,7 +332,7 @@ impl DataAnchorClient {
         self.indexer()
             .get_payers_by_network(network.clone())
             .await
-            .map_err(|e| IndexerError::PayersForNamespace(network, e.to_string()).into())
+            .map_err(|e| IndexerError::PayersForNamespace(network, e).into())
     }
 
     /// Fetches compound proof for a given slot from the [`IndexerRpcClient`].
@@ -171,7 +348,35 @@ impl DataAnchorClient {
         self.indexer()
             .get_proof(blober.into(), slot)
             .await
-            .map_err(|e| IndexerError::Proof(slot, e.to_string()).into())
+            .map_err(|e| IndexerError::Proof(slot, e).into())
+    }
+
+    /// Fetches compound proofs for several slots from the [`IndexerRpcClient`] in a single call.
+    /// The result is in the same order as `slots`, with `None` for slots without a proof. At most
+    /// [`MAX_PROOF_SLOTS_PER_CALL`] slots can be requested at once.
+    pub async fn get_proofs_for_slots(
+        &self,
+        identifier: BloberIdentifier,
+        slots: Vec<Slot>,
+    ) -> DataAnchorClientResult<Vec<(Slot, Option<CompoundInclusionProof>)>> {
+        if slots.len() > MAX_PROOF_SLOTS_PER_CALL {
+            return Err(IndexerError::TooManySlots(slots.len()).into());
+        }
+        let blober = identifier.to_blober_address(self.program_id, self.payer.pubkey());
+
+        let proofs = self
+            .indexer()
+            .get_proofs_for_slots(blober.into(), slots.clone())
+            .await
+            .map_err(|e| IndexerError::ProofsForSlots(slots.len(), e))?;
+
+        if !proofs.iter().map(|(slot, _)| slot).eq(slots.iter()) {
+            return Err(DataAnchorClientError::InvalidData(format!(
+                "Indexer returned proofs for slots {:?}, requested {slots:?}",
+                proofs.iter().map(|(slot, _)| slot).collect::<Vec<_>>()
+            )));
+        }
+        Ok(proofs)
     }
 
     /// Fetches compound proof for a given blob PDA [`Pubkey`] from the [`IndexerRpcClient`].
@@ -184,6 +389,394 @@ impl DataAnchorClient {
         self.indexer()
             .get_proof_for_blob(blob.into())
             .await
-            .map_err(|e| IndexerError::ProofForBlob(blob.to_string(), e.to_string()).into())
+            .map_err(|e| IndexerError::ProofForBlob(blob.to_string(), e).into())
+    }
+
+    /// Fetches the address of a finalized blob for a given blober whose content hash matches
+    /// `content_hash` from the [`IndexerRpcClient`], or `None` if the indexer has no such blob.
+    /// The content hash is the SHA-256 hash of the blob as uploaded, after encoding and
+    /// compression.
+    pub async fn find_blob_by_content_hash(
+        &self,
+        identifier: BloberIdentifier,
+        content_hash: [u8; 32],
+    ) -> DataAnchorClientResult<Option<Pubkey>> {
+        let blober = identifier.to_blober_address(self.program_id, self.payer.pubkey());
+
+        self.indexer()
+            .find_blob_by_content_hash(blober.into(), content_hash)
+            .await
+            .map(|blob| blob.map(Into::into))
+            .map_err(|e| IndexerError::BlobByContentHash(blober.to_string(), e).into())
+    }
+}
+
+/// The state of a [`DataAnchorClient::subscribe_blobs`] stream between polls.
+struct BlobSubscription<'a> {
+    client: &'a DataAnchorClient,
+    blober: Pubkey,
+    poll_interval: Duration,
+    /// The first slot the next poll queries.
+    next_slot: Slot,
+    /// Whether the last poll reached the latest indexed slot, so the next one should wait first.
+    caught_up: bool,
+    /// Blobs found by the last poll that haven't been yielded yet.
+    pending: VecDeque<(Slot, Vec<u8>)>,
+    /// The slot and digest of every blob yielded from `next_slot` onwards.
+    seen: HashSet<(Slot, [u8; HASH_BYTES])>,
+}
+
+impl BlobSubscription<'_> {
+    /// Queries the indexer for the blobs finalized since the last poll.
+    async fn poll(&mut self) -> DataAnchorClientResult {
+        let latest = self
+            .client
+            .get_latest_indexed_slot(self.blober.into())
+            .await?;
+        let Some(latest) = latest.filter(|latest| *latest >= self.next_slot) else {
+            self.caught_up = true;
+            return Ok(());
+        };
+
+        let start_slot = self.next_slot;
+        let end_slot = latest.min(start_slot.saturating_add(MAX_SLOT_RANGE_SPAN - 1));
+        let blobs = self
+            .client
+            .indexer()
+            .get_blobs_by_slot_range(BlobsBySlotRange {
+                blober: self.blober,
+                start_slot,
+                end_slot,
+            })
+            .await
+            .map_err(|e| IndexerError::BlobsForSlotRange(start_slot, end_slot, e))?;
+
+        self.seen.retain(|(slot, _)| *slot >= end_slot);
+        for (slot, blobs) in blobs {
+            for blob in blobs {
+                if self.seen.insert((slot, hashv(&[&blob]).to_bytes())) {
+                    self.pending.push_back((slot, blob));
+                }
+            }
+        }
+
+        // The last slot is queried again by the next poll, in case more of its blobs are indexed
+        // in the meantime.
+        self.next_slot = end_slot;
+        self.caught_up = end_slot == latest;
+        Ok(())
+    }
+}
+
+impl DataAnchorClient {
+    /// Streams the blobs finalized from `from_slot` onwards, as they were uploaded, along with the
+    /// slot they were finalized in, by polling the [`IndexerRpcClient`] every `poll_interval`.
+    ///
+    /// Blobs are yielded in slot order, and a blob is never yielded twice for the same slot even
+    /// if later polls return it again. A failed poll is yielded as an error and retried after the
+    /// next interval. The stream never ends on its own, polling stops when it is dropped.
+    pub fn subscribe_blobs(
+        &self,
+        identifier: BloberIdentifier,
+        from_slot: Slot,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = DataAnchorClientResult<(Slot, Vec<u8>)>> + '_ {
+        let subscription = BlobSubscription {
+            client: self,
+            blober: identifier.to_blober_address(self.program_id, self.payer.pubkey()),
+            poll_interval,
+            next_slot: from_slot,
+            caught_up: false,
+            pending: VecDeque::new(),
+            seen: HashSet::new(),
+        };
+
+        futures::stream::unfold(subscription, |mut subscription| async move {
+            loop {
+                if let Some(blob) = subscription.pending.pop_front() {
+                    return Some((Ok(blob), subscription));
+                }
+                if subscription.caught_up {
+                    tokio::time::sleep(subscription.poll_interval).await;
+                }
+                if let Err(e) = subscription.poll().await {
+                    subscription.caught_up = true;
+                    return Some((Err(e), subscription));
+                }
+            }
+        })
+    }
+}
+
+/// Bounds the total time spent across several indexer calls made through a [`DataAnchorClient`].
+///
+/// The budget starts when it is created. Every call made through it is cut short once the budget
+/// runs out, and calls made afterwards fail immediately with [`IndexerError::BudgetExhausted`].
+pub struct IndexerBudget<'a> {
+    client: &'a DataAnchorClient,
+    budget: Duration,
+    deadline: Instant,
+}
+
+impl<'a> IndexerBudget<'a> {
+    /// Creates a budget of `budget` for calls made through `client`, starting now.
+    pub fn new(client: &'a DataAnchorClient, budget: Duration) -> Self {
+        Self {
+            client,
+            budget,
+            deadline: Instant::now() + budget,
+        }
+    }
+
+    /// The time left before the budget is exhausted.
+    pub fn remaining(&self) -> Duration {
+        self.deadline.saturating_duration_since(Instant::now())
+    }
+
+    /// Runs an indexer call against the remaining budget.
+    pub async fn run<T>(
+        &self,
+        call: impl Future<Output = DataAnchorClientResult<T>>,
+    ) -> DataAnchorClientResult<T> {
+        let remaining = self.remaining();
+        if remaining.is_zero() {
+            return Err(IndexerError::BudgetExhausted(self.budget).into());
+        }
+
+        tokio::time::timeout(remaining, call)
+            .await
+            .map_err(|_| IndexerError::BudgetExhausted(self.budget))?
+    }
+
+    /// See [`DataAnchorClient::indexer_health`].
+    pub async fn indexer_health(&self) -> DataAnchorClientResult<IndexerHealth> {
+        self.run(self.client.indexer_health()).await
+    }
+
+    /// See [`DataAnchorClient::get_blobs`].
+    pub async fn get_blobs<T>(
+        &self,
+        slot: u64,
+        identifier: BloberIdentifier,
+    ) -> DataAnchorClientResult<Option<Vec<T>>>
+    where
+        T: Decodable,
+    {
+        self.run(self.client.get_blobs(slot, identifier)).await
+    }
+
+    /// See [`DataAnchorClient::get_raw_blobs`].
+    pub async fn get_raw_blobs(
+        &self,
+        slot: u64,
+        identifier: BloberIdentifier,
+    ) -> DataAnchorClientResult<Option<Vec<Vec<u8>>>> {
+        self.run(self.client.get_raw_blobs(slot, identifier)).await
+    }
+
+    /// See [`DataAnchorClient::get_latest_indexed_slot`].
+    pub async fn get_latest_indexed_slot(
+        &self,
+        identifier: BloberIdentifier,
+    ) -> DataAnchorClientResult<Option<Slot>> {
+        self.run(self.client.get_latest_indexed_slot(identifier))
+            .await
+    }
+
+    /// See [`DataAnchorClient::get_blobs_by_slot_range`].
+    pub async fn get_blobs_by_slot_range<T>(
+        &self,
+        identifier: BloberIdentifier,
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> DataAnchorClientResult<BTreeMap<Slot, Vec<T>>>
+    where
+        T: Decodable,
+    {
+        self.run(
+            self.client
+                .get_blobs_by_slot_range(identifier, start_slot, end_slot),
+        )
+        .await
+    }
+
+    /// See [`DataAnchorClient::get_blobs_by_blober`].
+    pub async fn get_blobs_by_blober<T>(
+        &self,
+        identifier: BloberIdentifier,
+        time_range: Option<TimeRange>,
+    ) -> DataAnchorClientResult<Vec<T>>
+    where
+        T: Decodable,
+    {
+        self.run(self.client.get_blobs_by_blober(identifier, time_range))
+            .await
+    }
+
+    /// See [`DataAnchorClient::get_blobs_by_payer`].
+    pub async fn get_blobs_by_payer<T>(
+        &self,
+        payer: Pubkey,
+        network_name: String,
+        time_range: Option<TimeRange>,
+    ) -> DataAnchorClientResult<Vec<T>>
+    where
+        T: Decodable,
+    {
+        self.run(
+            self.client
+                .get_blobs_by_payer(payer, network_name, time_range),
+        )
+        .await
+    }
+
+    /// See [`DataAnchorClient::get_blobs_by_payer_paginated`].
+    pub async fn get_blobs_by_payer_paginated<T>(
+        &self,
+        payer: Pubkey,
+        network_name: String,
+        time_range: Option<TimeRange>,
+        page: PageRequest,
+    ) -> DataAnchorClientResult<Page<T>>
+    where
+        T: Decodable,
+    {
+        self.run(
+            self.client
+                .get_blobs_by_payer_paginated(payer, network_name, time_range, page),
+        )
+        .await
+    }
+
+    /// See [`DataAnchorClient::get_blobs_by_network`].
+    pub async fn get_blobs_by_network<T>(
+        &self,
+        network_name: String,
+        time_range: Option<TimeRange>,
+    ) -> DataAnchorClientResult<Vec<T>>
+    where
+        T: Decodable,
+    {
+        self.run(self.client.get_blobs_by_network(network_name, time_range))
+            .await
+    }
+
+    /// See [`DataAnchorClient::get_blobs_by_namespace_for_payer`].
+    pub async fn get_blobs_by_namespace_for_payer<T>(
+        &self,
+        namespace: String,
+        payer_pubkey: Option<Pubkey>,
+        time_range: Option<TimeRange>,
+    ) -> DataAnchorClientResult<Vec<T>>
+    where
+        T: Decodable,
+    {
+        self.run(
+            self.client
+                .get_blobs_by_namespace_for_payer(namespace, payer_pubkey, time_range),
+        )
+        .await
+    }
+
+    /// See [`DataAnchorClient::get_payers_by_network`].
+    pub async fn get_payers_by_network(
+        &self,
+        network: String,
+    ) -> DataAnchorClientResult<Vec<PubkeyFromStr>> {
+        self.run(self.client.get_payers_by_network(network)).await
+    }
+
+    /// See [`DataAnchorClient::get_proof`].
+    #[deprecated(since = "0.4.3", note = "please use `checkpoint_proof` instead")]
+    pub async fn get_proof(
+        &self,
+        slot: u64,
+        identifier: BloberIdentifier,
+    ) -> DataAnchorClientResult<Option<CompoundInclusionProof>> {
+        #[allow(deprecated)]
+        self.run(self.client.get_proof(slot, identifier)).await
+    }
+
+    /// See [`DataAnchorClient::get_proofs_for_slots`].
+    pub async fn get_proofs_for_slots(
+        &self,
+        identifier: BloberIdentifier,
+        slots: Vec<Slot>,
+    ) -> DataAnchorClientResult<Vec<(Slot, Option<CompoundInclusionProof>)>> {
+        self.run(self.client.get_proofs_for_slots(identifier, slots))
+            .await
+    }
+
+    /// See [`DataAnchorClient::get_proof_for_blob`].
+    #[deprecated(since = "0.4.3", note = "please use `checkpoint_proof` instead")]
+    pub async fn get_proof_for_blob(
+        &self,
+        blob: Pubkey,
+    ) -> DataAnchorClientResult<Option<CompoundInclusionProof>> {
+        #[allow(deprecated)]
+        self.run(self.client.get_proof_for_blob(blob)).await
+    }
+
+    /// See [`DataAnchorClient::find_blob_by_content_hash`].
+    pub async fn find_blob_by_content_hash(
+        &self,
+        identifier: BloberIdentifier,
+        content_hash: [u8; 32],
+    ) -> DataAnchorClientResult<Option<Pubkey>> {
+        self.run(
+            self.client
+                .find_blob_by_content_hash(identifier, content_hash),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpsee::types::{ErrorCode, ErrorObjectOwned};
+
+    use super::*;
+
+    fn transport(error: HttpTransportError) -> ClientError {
+        ClientError::Transport(Box::new(error))
+    }
+
+    #[test]
+    fn timeouts_and_server_failures_are_transient() {
+        assert!(IndexerError::Blobs(1, ClientError::RequestTimeout).is_transient());
+        assert!(
+            IndexerError::Proof(
+                1,
+                transport(HttpTransportError::Rejected { status_code: 503 })
+            )
+            .is_transient()
+        );
+        assert!(
+            IndexerError::Proof(
+                1,
+                transport(HttpTransportError::Rejected { status_code: 429 })
+            )
+            .is_transient()
+        );
+    }
+
+    #[test]
+    fn unauthorized_and_malformed_responses_are_permanent() {
+        assert!(
+            !IndexerError::Proof(
+                1,
+                transport(HttpTransportError::Rejected { status_code: 401 })
+            )
+            .is_transient()
+        );
+        let malformed = serde_json::from_str::<u64>("not json").unwrap_err();
+        assert!(!IndexerError::Blobs(1, ClientError::ParseError(malformed)).is_transient());
+        assert!(
+            !IndexerError::PayersForNamespace(
+                "network".to_owned(),
+                ClientError::Call(ErrorObjectOwned::from(ErrorCode::InvalidParams)),
+            )
+            .is_transient()
+        );
     }
 }
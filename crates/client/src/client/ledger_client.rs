@@ -2,27 +2,33 @@ use std::collections::{HashMap, HashSet};
 
 use anchor_lang::{AccountDeserialize, prelude::Pubkey, solana_program::message::VersionedMessage};
 use data_anchor_api::{
-    BloberWithNamespace, LedgerDataBlobError, RelevantInstruction, RelevantInstructionWithAccounts,
-    extract_relevant_instructions, get_account_at_index, get_blob_data_from_instructions,
+    BloberWithNamespace, LedgerDataBlobError, RelevantInstruction,
+    deserialize_relevant_instructions, extract_relevant_instructions,
+    get_blob_data_from_instructions,
 };
 use data_anchor_blober::{
-    BLOB_ACCOUNT_INSTRUCTION_IDX, BLOB_BLOBER_INSTRUCTION_IDX, checkpoint::Checkpoint,
-    find_checkpoint_address, state::blober::Blober,
+    BLOB_ACCOUNT_INSTRUCTION_IDX, BLOB_BLOBER_INSTRUCTION_IDX,
+    blob::Blob,
+    checkpoint::{Checkpoint, CheckpointConfig},
+    find_checkpoint_address, find_checkpoint_config_address,
+    state::blober::Blober,
 };
+use data_anchor_pob_sla_verifier::deserialize_sla_score;
 use data_anchor_utils::encoding::Decodable;
 use futures::{StreamExt, TryStreamExt};
 use solana_account_decoder_client_types::UiAccountEncoding;
 use solana_client::rpc_config::{
     RpcAccountInfoConfig, RpcBlockConfig, RpcProgramAccountsConfig, RpcTransactionConfig,
 };
-use solana_rpc_client_api::client_error::Error;
+use solana_rpc_client_api::client_error::{Error, ErrorKind};
 use solana_signature::Signature;
 use solana_signer::Signer;
 use solana_transaction_status::{EncodedConfirmedBlock, UiTransactionEncoding};
 
 use super::BloberIdentifier;
 use crate::{
-    DataAnchorClient, DataAnchorClientResult, OutcomeError,
+    BlobInfo, BlobStatus, DataAnchorClient, DataAnchorClientError, DataAnchorClientResult,
+    OutcomeError,
     constants::{DEFAULT_CONCURRENCY, DEFAULT_LOOKBACK_SLOTS},
     helpers::filter_relevant_instructions,
 };
@@ -80,11 +86,91 @@ pub enum ChainError {
     /// Failed to configure checkpoint: {0}
     #[error("Failed to configure checkpoint: {0}")]
     ConfigureCheckpoint(OutcomeError),
+    /// Failed to create checkpoint: {0}
+    #[error("Failed to create checkpoint: {0}")]
+    CreateCheckpoint(OutcomeError),
+    /// Checkpoint of blober {blober} can only be reconfigured by its authority {authority}
+    #[error("Checkpoint of blober {blober} can only be reconfigured by its authority {authority}")]
+    NotCheckpointAuthority { blober: Pubkey, authority: Pubkey },
+    /// Checkpoint authority {0} is not a known verifier program
+    #[error("Checkpoint authority {0} is not a known verifier program")]
+    UnknownCheckpointAuthority(Pubkey),
+    /// Invalid checkpoint proof: {0}
+    #[error("Invalid checkpoint proof: {0}")]
+    InvalidCheckpointProof(String),
     /// Provided proof commitment does not match the blober's address
     #[error("Provided proof commitment does not match the blober's address expected {0}, got {1}")]
     ProofBloberMismatch(Pubkey, Pubkey),
     #[error("Checkpoint account is not up to date with current blober state")]
     CheckpointNotUpToDate,
+    /// Blober {blober} is owned by {owner}, not by the payer
+    #[error("Blober {blober} is owned by {owner}, not by the payer")]
+    NotBloberOwner { blober: Pubkey, owner: Pubkey },
+    /// Program {0} is not deployed: the account is missing, not executable or not owned by a loader
+    #[error(
+        "Program {0} is not deployed: the account is missing, not executable or not owned by a loader"
+    )]
+    ProgramNotDeployed(Pubkey),
+    /// Pre-derived blob address {0} does not match the uploaded blob, expected {1}
+    #[error("Pre-derived blob address {0} does not match the uploaded blob, expected {1}")]
+    BlobAddressMismatch(Pubkey, Pubkey),
+    /// Digest of blob {0} does not match the uploaded data
+    #[error("Digest of blob {0} does not match the uploaded data")]
+    BlobDigestMismatch(Pubkey),
+    /// Blob upload was cancelled
+    #[error("Blob upload was cancelled")]
+    UploadCancelled,
+    /// Transaction simulation failed: {0}
+    #[error("Transaction simulation failed: {0}")]
+    SimulationFailed(String),
+    /// A deduplicated upload can't be given a blob address, since it may resolve to an existing blob
+    #[error(
+        "A deduplicated upload can't be given a blob address, since it may resolve to an existing blob"
+    )]
+    DedupWithAddress,
+}
+
+impl ChainError {
+    /// Whether retrying the operation that caused this error may succeed. Transactions that didn't
+    /// land in time (such as ones whose blockhash expired) and RPC transport failures are
+    /// retryable, while errors about the state of accounts (such as [`ChainError::AccountExists`])
+    /// are permanent.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ChainError::SolanaRpc(e) => matches!(
+                e.kind(),
+                ErrorKind::Io(_) | ErrorKind::Reqwest(_) | ErrorKind::Middleware(_)
+            ),
+            ChainError::TransactionFailure(e)
+            | ChainError::DeclareBlob(e)
+            | ChainError::InsertChunks(e)
+            | ChainError::FinalizeBlob(e)
+            | ChainError::DiscardBlob(e)
+            | ChainError::CompoundUpload(e)
+            | ChainError::InitializeBlober(e)
+            | ChainError::CloseBlober(e)
+            | ChainError::ConfigureCheckpoint(e)
+            | ChainError::CreateCheckpoint(e) => e.is_retryable(),
+            ChainError::ConversionError(_)
+            | ChainError::MissingBloberNamespace
+            | ChainError::AccountExists(_)
+            | ChainError::AccountDoesNotExist(_)
+            | ChainError::InsufficientBalance(_, _)
+            | ChainError::CouldNotCalculateCost
+            | ChainError::NotCheckpointAuthority { .. }
+            | ChainError::UnknownCheckpointAuthority(_)
+            | ChainError::InvalidCheckpointProof(_)
+            | ChainError::ProofBloberMismatch(_, _)
+            | ChainError::CheckpointNotUpToDate
+            | ChainError::NotBloberOwner { .. }
+            | ChainError::ProgramNotDeployed(_)
+            | ChainError::BlobAddressMismatch(_, _)
+            | ChainError::BlobDigestMismatch(_)
+            | ChainError::UploadCancelled
+            | ChainError::SimulationFailed(_)
+            | ChainError::DedupWithAddress => false,
+        }
+    }
 }
 
 impl DataAnchorClient {
@@ -105,7 +191,7 @@ impl DataAnchorClient {
                     .get_transaction_with_config(
                         &signature,
                         RpcTransactionConfig {
-                            commitment: Some(self.rpc_client.commitment()),
+                            commitment: Some(self.commitment),
                             encoding: Some(UiTransactionEncoding::Base58),
                             max_supported_transaction_version: Some(0),
                         },
@@ -146,13 +232,15 @@ impl DataAnchorClient {
             return Err(LedgerDataBlobError::MultipleDeclares.into());
         }
 
+        // A batched finalize can finalize other blobs in the same transaction.
         if relevant_instructions
             .iter()
             .filter(|instruction| {
-                matches!(
-                    instruction.instruction,
-                    RelevantInstruction::FinalizeBlob(_)
-                )
+                instruction.blob == *blob
+                    && matches!(
+                        instruction.instruction,
+                        RelevantInstruction::FinalizeBlob(_)
+                    )
             })
             .count()
             > 1
@@ -178,7 +266,7 @@ impl DataAnchorClient {
         let blober = identifier.to_blober_address(self.program_id, self.payer.pubkey());
 
         let block_config = RpcBlockConfig {
-            commitment: Some(self.rpc_client.commitment()),
+            commitment: Some(self.commitment),
             encoding: Some(UiTransactionEncoding::Base58),
             max_supported_transaction_version: Some(0),
             ..Default::default()
@@ -248,11 +336,7 @@ impl DataAnchorClient {
 
         let block_slots = self
             .rpc_client
-            .get_blocks_with_commitment(
-                slot - lookback_slots,
-                Some(slot - 1),
-                self.rpc_client.commitment(),
-            )
+            .get_blocks_with_commitment(slot - lookback_slots, Some(slot - 1), self.commitment)
             .await?;
 
         for slot in block_slots.into_iter().rev() {
@@ -322,7 +406,7 @@ impl DataAnchorClient {
             .get_block_with_config(
                 slot,
                 RpcBlockConfig {
-                    commitment: Some(self.rpc_client.commitment()),
+                    commitment: Some(self.commitment),
                     encoding: Some(UiTransactionEncoding::Base58),
                     max_supported_transaction_version: Some(0),
                     ..Default::default()
@@ -339,35 +423,24 @@ impl DataAnchorClient {
                 _ => tx.transaction.decode(),
             })
             .filter_map(|tx| {
-                let instructions = tx
-                    .message
-                    .instructions()
-                    .iter()
-                    .filter_map(|compiled_instruction| {
-                        Some(RelevantInstructionWithAccounts {
-                            blob: get_account_at_index(
-                                tx.message.static_account_keys(),
-                                compiled_instruction,
-                                BLOB_ACCOUNT_INSTRUCTION_IDX,
-                            )?,
-                            blober: get_account_at_index(
-                                tx.message.static_account_keys(),
-                                compiled_instruction,
-                                BLOB_BLOBER_INSTRUCTION_IDX,
-                            )?,
-                            instruction: RelevantInstruction::try_from_slice(compiled_instruction)?,
-                        })
-                    })
-                    .filter(|instruction| {
-                        instruction.blober == blober
-                            && matches!(
-                                instruction.instruction,
-                                RelevantInstruction::FinalizeBlob(_)
-                            )
-                    })
-                    .collect::<Vec<_>>();
+                let instructions = deserialize_relevant_instructions(
+                    &self.program_id,
+                    tx.message.static_account_keys(),
+                    tx.message.instructions().iter(),
+                    BLOB_ACCOUNT_INSTRUCTION_IDX,
+                    BLOB_BLOBER_INSTRUCTION_IDX,
+                )
+                .into_iter()
+                .filter(|instruction| {
+                    instruction.blober == blober
+                        && matches!(
+                            instruction.instruction,
+                            RelevantInstruction::FinalizeBlob(_)
+                        )
+                })
+                .collect::<Vec<_>>();
 
-                instructions.is_empty().then_some(
+                (!instructions.is_empty()).then_some(
                     instructions
                         .iter()
                         .map(|instruction| (instruction.blob, tx.message.clone()))
@@ -417,7 +490,7 @@ impl DataAnchorClient {
         let blober = identifier.to_blober_address(self.program_id, self.payer.pubkey());
         let account = self
             .rpc_client
-            .get_account_with_commitment(&blober, self.rpc_client.commitment())
+            .get_account_with_commitment(&blober, self.commitment)
             .await?
             .value;
 
@@ -432,6 +505,52 @@ impl DataAnchorClient {
         Ok(Some(blober))
     }
 
+    /// Retrieves and deserializes a [`Blob`] PDA account, returning `None` if it doesn't exist.
+    pub async fn get_blob_account(&self, blob: Pubkey) -> DataAnchorClientResult<Option<Blob>> {
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(&blob, self.commitment)
+            .await?
+            .value;
+
+        let Some(account) = account else {
+            return Ok(None);
+        };
+
+        let mut data = account.data.as_slice();
+
+        let blob = Blob::try_deserialize(&mut data).map_err(LedgerDataBlobError::from)?;
+
+        Ok(Some(blob))
+    }
+
+    /// Retrieves the state of a [`Blob`] PDA account, returning `None` if it doesn't exist.
+    pub async fn get_blob_info(&self, blob: Pubkey) -> DataAnchorClientResult<Option<BlobInfo>> {
+        Ok(self
+            .get_blob_account(blob)
+            .await?
+            .map(|blob| BlobInfo::from(&blob)))
+    }
+
+    /// Returns whether a blob is complete and whether it has been finalized.
+    ///
+    /// Finalizing a blob stores its hash in the blober and closes its PDA account, so a blob whose
+    /// account still exists is never finalized, even if all of its chunks have been inserted. A
+    /// blob without an account is reported as finalized. Discarded blobs are closed as well and
+    /// can only be told apart from finalized ones through the ledger.
+    pub async fn blob_status(&self, blob: Pubkey) -> DataAnchorClientResult<BlobStatus> {
+        Ok(match self.get_blob_account(blob).await? {
+            Some(blob) => BlobStatus {
+                complete: blob.is_complete(),
+                finalized: false,
+            },
+            None => BlobStatus {
+                complete: true,
+                finalized: true,
+            },
+        })
+    }
+
     /// Retrieves the checkpoint containing the Groth16 proof for a given blober account.
     pub async fn get_checkpoint(
         &self,
@@ -441,7 +560,7 @@ impl DataAnchorClient {
         let checkpoint_address = find_checkpoint_address(self.program_id, blober);
         let account = self
             .rpc_client
-            .get_account_with_commitment(&checkpoint_address, self.rpc_client.commitment())
+            .get_account_with_commitment(&checkpoint_address, self.commitment)
             .await?
             .value;
 
@@ -460,4 +579,95 @@ impl DataAnchorClient {
 
         Ok(Some(checkpoint))
     }
+
+    /// Retrieves the SLA score committed to by the checkpoint of a given blober account, as
+    /// verified by the pob-sla verifier. Returns `None` if there is no checkpoint or it doesn't
+    /// carry an SLA commitment, such as checkpoints created by the data-correctness verifier.
+    pub async fn get_checkpoint_sla_score(
+        &self,
+        blober: BloberIdentifier,
+    ) -> DataAnchorClientResult<Option<f64>> {
+        let Some(checkpoint) = self.get_checkpoint(blober).await? else {
+            return Ok(None);
+        };
+
+        match checkpoint.non_base_commitments() {
+            None => Ok(None),
+            Some(sla_bytes) => deserialize_sla_score(sla_bytes)
+                .map(Some)
+                .map_err(|e| DataAnchorClientError::InvalidData(e.to_string())),
+        }
+    }
+
+    /// Retrieves the checkpoint configuration of a given blober account, which holds the authority
+    /// allowed to create checkpoints for it. Returns `None` if the checkpoint was never configured.
+    pub async fn get_checkpoint_config(
+        &self,
+        blober: BloberIdentifier,
+    ) -> DataAnchorClientResult<Option<CheckpointConfig>> {
+        let blober = blober.to_blober_address(self.program_id, self.payer.pubkey());
+        let checkpoint_config_address = find_checkpoint_config_address(self.program_id, blober);
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(&checkpoint_config_address, self.commitment)
+            .await?
+            .value;
+
+        let Some(account) = account else {
+            return Ok(None);
+        };
+
+        if account.owner != self.program_id {
+            return Err(LedgerDataBlobError::AccountNotOwnedByProgram.into());
+        }
+
+        let mut data = account.data.as_slice();
+
+        let checkpoint_config =
+            CheckpointConfig::try_deserialize(&mut data).map_err(LedgerDataBlobError::from)?;
+
+        Ok(Some(checkpoint_config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpc(kind: ErrorKind) -> ChainError {
+        ChainError::SolanaRpc(Error {
+            request: None,
+            kind,
+        })
+    }
+
+    #[test]
+    fn transport_failures_and_unlanded_transactions_are_retryable() {
+        assert!(
+            rpc(ErrorKind::Io(std::io::Error::from(
+                std::io::ErrorKind::ConnectionReset
+            )))
+            .is_retryable()
+        );
+        // None of the outcomes carry an execution error, so the transactions just didn't land.
+        assert!(ChainError::DeclareBlob(OutcomeError::Unsuccesful(Vec::new())).is_retryable());
+        assert!(ChainError::InsertChunks(OutcomeError::Unsuccesful(Vec::new())).is_retryable());
+    }
+
+    #[test]
+    fn account_state_errors_are_permanent() {
+        let blob = Pubkey::new_unique();
+        assert!(!ChainError::AccountExists(blob.to_string()).is_retryable());
+        assert!(!ChainError::AccountDoesNotExist(blob.to_string()).is_retryable());
+        assert!(!ChainError::InsufficientBalance(10, 5).is_retryable());
+        assert!(
+            !ChainError::NotBloberOwner {
+                blober: Pubkey::new_unique(),
+                owner: Pubkey::new_unique(),
+            }
+            .is_retryable()
+        );
+        assert!(!ChainError::UploadCancelled.is_retryable());
+        assert!(!rpc(ErrorKind::Custom("invalid params".to_owned())).is_retryable());
+    }
 }
@@ -1,13 +1,25 @@
 use std::{sync::Arc, time::Duration};
 
-use anchor_lang::{Discriminator, Space, prelude::Pubkey};
+use anchor_lang::{
+    Discriminator, Space,
+    prelude::Pubkey,
+    solana_program::{
+        bpf_loader, bpf_loader_deprecated, bpf_loader_upgradeable, hash::hashv, message::Message,
+    },
+};
 use bon::Builder;
+use data_anchor_api::{CustomerElf, ProofData};
 use data_anchor_blober::{
-    CHUNK_SIZE, COMPOUND_DECLARE_TX_SIZE, COMPOUND_TX_SIZE, find_blob_address, find_blober_address,
-    find_checkpoint_address, find_checkpoint_config_address,
+    CHECKPOINT_PDA_SIGNER_SEED, CHECKPOINT_SEED, CHUNK_SIZE, COMPOUND_DECLARE_TX_SIZE,
+    COMPOUND_TX_SIZE, MAX_NAMESPACE_LENGTH, SEED,
+    checkpoint::Checkpoint,
+    find_blob_address, find_blober_address, find_checkpoint_address,
+    find_checkpoint_config_address, find_checkpoint_signer_address,
     instruction::{
-        Close, ConfigureCheckpoint, DeclareBlob, DiscardBlob, FinalizeBlob, Initialize, InsertChunk,
+        Close, ConfigureCheckpoint, CreateCheckpoint, DeclareBlob, DiscardBlob, FinalizeBlob,
+        Initialize, InsertChunk,
     },
+    is_valid_namespace,
     state::blober::Blober,
 };
 use data_anchor_utils::{
@@ -19,18 +31,28 @@ use futures::{StreamExt, TryStreamExt};
 use jsonrpsee::http_client::HttpClient;
 use nitro_sender::{NitroSender, SuccessfulTransaction};
 use solana_commitment_config::CommitmentConfig;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
 use solana_keypair::Keypair;
 use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_rpc_client_api::config::RpcSimulateTransactionConfig;
 use solana_signer::Signer;
-use tracing::{Instrument, Span, info, info_span, trace};
+use solana_transaction::Transaction;
+use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, Span, info, info_span, trace, warn};
 
 use crate::{
     DataAnchorClientError, DataAnchorClientResult, IndexerUrl,
-    constants::DEFAULT_CONCURRENCY,
+    constants::{DEFAULT_CONCURRENCY, MAX_UPLOAD_ATTEMPTS, SIMULATION_COMPUTE_UNIT_LIMIT},
     fees::{Fee, FeeStrategy, Lamports},
-    helpers::{check_outcomes, get_unique_timestamp},
-    tx::{Compound, CompoundDeclare, CompoundFinalize, MessageArguments, MessageBuilder},
-    types::TransactionType,
+    helpers::{UploadMessages, check_outcomes, get_unique_timestamp},
+    tx::{
+        Compound, CompoundDeclare, CompoundFinalize, DiscardBlobs, FinalizeBlobs, MessageArguments,
+        MessageBuilder,
+    },
+    types::{
+        CheckpointArgs, DiscardBlobOutcome, RecordedUpload, TransactionType, UploadBlobResult,
+        UploadOptions,
+    },
 };
 
 mod builder;
@@ -38,7 +60,7 @@ mod indexer_client;
 mod ledger_client;
 mod proof_client;
 
-pub use indexer_client::IndexerError;
+pub use indexer_client::{IndexerBudget, IndexerError};
 pub use ledger_client::ChainError;
 pub use proof_client::ProofError;
 
@@ -57,6 +79,11 @@ pub enum BloberIdentifierError {
         "Missing blober identifier: either namespace, namespace and payer or blober PDA must be provided."
     )]
     MissingBloberIdentifier,
+    /// Error indicating that the namespace can't be used for a blober.
+    #[error(
+        "Invalid namespace {0:?}: must be 1 to {MAX_NAMESPACE_LENGTH} bytes of printable ASCII without whitespace"
+    )]
+    InvalidNamespace(String),
 }
 
 impl TryFrom<(Option<String>, Option<Pubkey>)> for BloberIdentifier {
@@ -114,15 +141,35 @@ impl BloberIdentifier {
             BloberIdentifier::Pubkey(_) => None,
         }
     }
+
+    /// Checks that the namespace, if there is one, would be accepted by the blober program when
+    /// initializing a new blober. Existing blobers are addressed without this check, since their
+    /// namespaces may predate it.
+    pub fn validate(&self) -> Result<(), BloberIdentifierError> {
+        match self.namespace() {
+            Some(namespace) if !is_valid_namespace(namespace) => Err(
+                BloberIdentifierError::InvalidNamespace(namespace.to_owned()),
+            ),
+            _ => Ok(()),
+        }
+    }
 }
 
 #[derive(Builder, Clone)]
 pub struct DataAnchorClient {
     #[builder(getter(name = get_payer, vis = ""))]
     pub(crate) payer: Arc<Keypair>,
+    /// Pays the transaction fees instead of the `payer`, which still owns the blober and signs for
+    /// it.
+    #[builder(getter(name = get_fee_payer, vis = ""))]
+    pub(crate) fee_payer: Option<Arc<Keypair>>,
     #[builder(default = data_anchor_blober::id())]
     pub(crate) program_id: Pubkey,
     pub(crate) rpc_client: Arc<RpcClient>,
+    /// The commitment level used for account and balance lookups, ledger queries and confirming
+    /// sent transactions.
+    #[builder(default = CommitmentConfig::confirmed())]
+    pub(crate) commitment: CommitmentConfig,
     pub(crate) nitro_sender: NitroSender,
     #[builder(getter(name = get_indexer, vis = ""))]
     #[allow(dead_code, reason = "Used in builder")]
@@ -133,6 +180,12 @@ pub struct DataAnchorClient {
     pub(crate) encoding: EncodingType,
     #[builder(default)]
     pub(crate) compression: CompressionType,
+    /// Whether chunks inserted in their own transaction are sent with a CRC, so the program
+    /// rejects corrupted chunks before hashing them. Chunks inserted along with the declare or
+    /// finalize instruction have no room for one. Requires a program that supports
+    /// `insert_chunk_with_crc`.
+    #[builder(default)]
+    pub(crate) chunk_crc: bool,
 }
 
 impl DataAnchorClient {
@@ -141,11 +194,65 @@ impl DataAnchorClient {
         self.rpc_client.clone()
     }
 
+    /// Returns the [`CommitmentConfig`] used for RPC queries and transaction confirmations.
+    pub fn commitment(&self) -> CommitmentConfig {
+        self.commitment
+    }
+
     /// Returns the transaction payer [`Keypair`].
     pub fn payer(&self) -> Arc<Keypair> {
         self.payer.clone()
     }
 
+    /// Returns the [`Keypair`] paying the transaction fees, which is the payer unless a separate
+    /// fee payer was set.
+    pub fn fee_payer(&self) -> Arc<Keypair> {
+        self.fee_payer.clone().unwrap_or_else(|| self.payer.clone())
+    }
+
+    /// Returns the address and bump of the checkpoint PDA signer for the given blober, derived the
+    /// same way the blober program expects it when signing checkpoint instructions.
+    pub fn checkpoint_signer(&self, blober: Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                SEED,
+                CHECKPOINT_SEED,
+                CHECKPOINT_PDA_SIGNER_SEED,
+                blober.as_ref(),
+            ],
+            &self.program_id,
+        )
+    }
+
+    /// Returns the number of signatures each transaction needs: the payer's, plus the fee payer's
+    /// when a separate one is set.
+    pub(crate) fn num_signatures(&self) -> u16 {
+        let separate_fee_payer = self
+            .fee_payer
+            .as_ref()
+            .is_some_and(|fee_payer| fee_payer.pubkey() != self.payer.pubkey());
+        1 + u16::from(separate_fee_payer)
+    }
+
+    /// Builds the [`MessageArguments`] for a transaction on the given blober, owned by the payer
+    /// and paid for by the fee payer.
+    pub(crate) fn message_arguments<Input: Send>(
+        &self,
+        blober: Pubkey,
+        fee: Fee,
+        input: Input,
+    ) -> MessageArguments<Input> {
+        MessageArguments::new(
+            self.program_id,
+            blober,
+            &self.payer,
+            self.rpc_client.clone(),
+            fee,
+            input,
+        )
+        .with_fee_payer(self.fee_payer().pubkey())
+    }
+
     fn in_mock_env(&self) -> bool {
         self.rpc_client.url().starts_with("MockSender")
     }
@@ -153,15 +260,89 @@ impl DataAnchorClient {
     async fn check_account_exists(&self, account: Pubkey) -> DataAnchorClientResult<bool> {
         Ok(self
             .rpc_client
-            .get_account_with_commitment(&account, CommitmentConfig::confirmed())
+            .get_account_with_commitment(&account, self.commitment)
             .await
             .map(|res| res.value.is_some())?)
     }
 
-    async fn require_balance(&self, cost: Lamports) -> DataAnchorClientResult {
+    /// Checks that the configured program ID belongs to a deployed program, i.e. an executable
+    /// account owned by one of the BPF loaders. Without this, every PDA derivation and
+    /// instruction would silently target the wrong program.
+    pub async fn check_program_deployed(&self) -> DataAnchorClientResult {
+        let account = self
+            .rpc_client
+            .get_account_with_commitment(&self.program_id, self.commitment)
+            .await?
+            .value;
+
+        match account {
+            Some(account)
+                if account.executable
+                    && [
+                        bpf_loader_upgradeable::ID,
+                        bpf_loader::ID,
+                        bpf_loader_deprecated::ID,
+                    ]
+                    .contains(&account.owner) =>
+            {
+                Ok(())
+            }
+            _ => Err(ChainError::ProgramNotDeployed(self.program_id).into()),
+        }
+    }
+
+    /// Converts `fee_strategy` to a fixed [`Fee`] for a transaction that writes to
+    /// `mutable_accounts`. The fee payer is written to by every transaction, so priority fees are
+    /// always estimated for it as well.
+    pub(crate) async fn fixed_fee(
+        &self,
+        fee_strategy: FeeStrategy,
+        mutable_accounts: &[Pubkey],
+        tx_type: TransactionType,
+    ) -> DataAnchorClientResult<Fee> {
+        let mut mutable_accounts = mutable_accounts.to_vec();
+        let fee_payer = self.fee_payer().pubkey();
+        if !mutable_accounts.contains(&fee_payer) {
+            mutable_accounts.push(fee_payer);
+        }
+
+        fee_strategy
+            .convert_fee_strategy_to_fixed(
+                &self.rpc_client,
+                &mutable_accounts,
+                tx_type,
+                self.num_signatures(),
+            )
+            .await
+    }
+
+    /// Checks that the fee payer can pay the transaction `fees` and the payer can pay the `rent`
+    /// of the accounts it creates. When they are the same account, it must be able to pay both.
+    async fn require_balance(&self, fees: Lamports, rent: Lamports) -> DataAnchorClientResult {
+        let payer = self.payer.pubkey();
+        let fee_payer = self.fee_payer().pubkey();
+        if fee_payer == payer {
+            let cost = fees
+                .checked_add(rent)
+                .ok_or_else(|| ChainError::CouldNotCalculateCost)?;
+            return self.require_account_balance(payer, cost).await;
+        }
+
+        self.require_account_balance(fee_payer, fees).await?;
+        if rent != Lamports::ZERO {
+            self.require_account_balance(payer, rent).await?;
+        }
+        Ok(())
+    }
+
+    async fn require_account_balance(
+        &self,
+        account: Pubkey,
+        cost: Lamports,
+    ) -> DataAnchorClientResult {
         let balance = self
             .rpc_client
-            .get_balance_with_commitment(&self.payer.pubkey(), CommitmentConfig::confirmed())
+            .get_balance_with_commitment(&account, self.commitment)
             .await
             .map(|r| r.value)?;
         let cost_u64 = cost.into_inner() as u64;
@@ -218,6 +399,7 @@ impl DataAnchorClient {
         identifier: BloberIdentifier,
         timeout: Option<Duration>,
     ) -> DataAnchorClientResult<Vec<SuccessfulTransaction<TransactionType>>> {
+        identifier.validate()?;
         let blober = identifier.to_blober_address(self.program_id, self.payer.pubkey());
 
         let in_mock_env = self.in_mock_env();
@@ -227,9 +409,9 @@ impl DataAnchorClient {
             );
         }
 
-        let fee = fee_strategy
-            .convert_fee_strategy_to_fixed(
-                &self.rpc_client,
+        let fee = self
+            .fixed_fee(
+                fee_strategy,
                 &[blober, self.payer.pubkey()],
                 TransactionType::InitializeBlober,
             )
@@ -237,27 +419,22 @@ impl DataAnchorClient {
             .await?;
 
         if !in_mock_env {
-            let cost = fee
-                .total_fee()
-                .checked_add(fee.rent())
-                .ok_or_else(|| ChainError::CouldNotCalculateCost)?;
-            self.require_balance(cost).await?;
+            self.require_balance(fee.total_fee(), fee.rent()).await?;
         }
 
-        let msg = Initialize::build_message(MessageArguments::new(
-            self.program_id,
-            blober,
-            &self.payer,
-            self.rpc_client.clone(),
-            fee,
-            (
-                identifier
-                    .namespace()
-                    .ok_or(ChainError::MissingBloberNamespace)?
-                    .to_owned(),
+        let msg = Initialize::build_message(
+            self.message_arguments(
                 blober,
+                fee,
+                (
+                    identifier
+                        .namespace()
+                        .ok_or(ChainError::MissingBloberNamespace)?
+                        .to_owned(),
+                    blober,
+                ),
             ),
-        ))
+        )
         .await;
 
         let span = info_span!(parent: Span::current(), "initialize_blober");
@@ -266,12 +443,36 @@ impl DataAnchorClient {
                 .send(vec![(TransactionType::InitializeBlober, msg)], timeout)
                 .instrument(span)
                 .await,
-            self.rpc_client.commitment(),
+            self.commitment,
         )
         .map_err(ChainError::InitializeBlober)?)
     }
 
+    /// Initializes a new [`Blober`] PDA account, unless it already exists.
+    ///
+    /// Returns `true` if the blober was created, and `false` without sending any transaction if it
+    /// already existed. Use [`Self::initialize_blober`] to treat an existing blober as an error.
+    pub async fn initialize_blober_if_absent(
+        &self,
+        fee_strategy: FeeStrategy,
+        identifier: BloberIdentifier,
+        timeout: Option<Duration>,
+    ) -> DataAnchorClientResult<bool> {
+        let blober = identifier.to_blober_address(self.program_id, self.payer.pubkey());
+
+        if self.check_account_exists(blober).await? {
+            return Ok(false);
+        }
+
+        self.initialize_blober(fee_strategy, identifier, timeout)
+            .await?;
+        Ok(true)
+    }
+
     /// Closes a [`Blober`] PDA account.
+    ///
+    /// Fails early with [`ChainError::NotBloberOwner`] if the blober was not created by the
+    /// payer, since the program would reject the transaction anyway.
     pub async fn close_blober(
         &self,
         fee_strategy: FeeStrategy,
@@ -289,10 +490,22 @@ impl DataAnchorClient {
             .into());
         }
 
-        let checkpoint = self.get_checkpoint(identifier.clone()).await?;
+        let blober_state = self.get_blober(identifier.clone()).await?;
+
+        if let Some(blober_state) = &blober_state
+            && blober_state.caller != self.payer.pubkey()
+        {
+            return Err(ChainError::NotBloberOwner {
+                blober,
+                owner: blober_state.caller,
+            }
+            .into());
+        }
+
+        let checkpoint = self.get_checkpoint(identifier).await?;
 
         let checkpoint_accounts = if let Some(checkpoint) = checkpoint {
-            let Some(blober_state) = self.get_blober(identifier).await? else {
+            let Some(blober_state) = blober_state else {
                 return Err(ChainError::AccountDoesNotExist(format!(
                     "Blober PDA with address {blober}"
                 ))
@@ -315,9 +528,9 @@ impl DataAnchorClient {
             None
         };
 
-        let fee = fee_strategy
-            .convert_fee_strategy_to_fixed(
-                &self.rpc_client,
+        let fee = self
+            .fixed_fee(
+                fee_strategy,
                 &[blober, self.payer.pubkey()],
                 TransactionType::CloseBlober,
             )
@@ -325,18 +538,12 @@ impl DataAnchorClient {
             .await?;
 
         if !in_mock_env {
-            self.require_balance(fee.total_fee()).await?;
+            self.require_balance(fee.total_fee(), Lamports::ZERO)
+                .await?;
         }
 
-        let msg = Close::build_message(MessageArguments::new(
-            self.program_id,
-            blober,
-            &self.payer,
-            self.rpc_client.clone(),
-            fee,
-            checkpoint_accounts,
-        ))
-        .await;
+        let msg =
+            Close::build_message(self.message_arguments(blober, fee, checkpoint_accounts)).await;
 
         let span = info_span!(parent: Span::current(), "close_blober");
         Ok(check_outcomes(
@@ -344,7 +551,7 @@ impl DataAnchorClient {
                 .send(vec![(TransactionType::CloseBlober, msg)], timeout)
                 .instrument(span)
                 .await,
-            self.rpc_client.commitment(),
+            self.commitment,
         )
         .map_err(ChainError::CloseBlober)?)
     }
@@ -354,14 +561,188 @@ impl DataAnchorClient {
     /// incremental hash of the chunks from the blob data. On completion of the blob upload, the
     /// blob PDA gets closed sending it's funds back to the [`DataAnchorClient::payer`].
     /// If the blob upload fails, the blob PDA gets discarded and the funds also get sent to the
-    /// [`DataAnchorClient::payer`].
+    /// [`DataAnchorClient::payer`]. Failures that [`ChainError::is_retryable`] are retried from the
+    /// start up to [`MAX_UPLOAD_ATTEMPTS`] times before giving up, each attempt getting the full
+    /// `timeout`.
+    ///
+    /// See [`UploadOptions`] for the optional settings of the upload. When
+    /// [`UploadOptions::dedup`] finds an existing blob with the same content, nothing is uploaded
+    /// and the result holds the existing blob PDA, no transactions and no
+    /// [`UploadBlobResult::finalize_slot`].
+    ///
+    /// The returned [`UploadBlobResult::finalize_slot`] is the slot of the finalize transaction,
+    /// which is the slot to query for the blob afterwards.
     pub async fn upload_blob<T>(
         &self,
         blob_data: &T,
         fee_strategy: FeeStrategy,
         namespace: &str,
         timeout: Option<Duration>,
-    ) -> DataAnchorClientResult<(Vec<SuccessfulTransaction<TransactionType>>, Pubkey)>
+        options: UploadOptions,
+    ) -> DataAnchorClientResult<UploadBlobResult>
+    where
+        T: Encodable,
+    {
+        let UploadOptions {
+            address,
+            verify_before_finalize,
+            dedup,
+            encoding,
+            compression,
+            cancel,
+            defer_finalize,
+            record,
+        } = options;
+        if dedup && address.is_some() {
+            return Err(ChainError::DedupWithAddress.into());
+        }
+        let encoded_and_compressed = self
+            .encode_for_upload(
+                blob_data,
+                encoding.as_ref().unwrap_or(&self.encoding),
+                compression.as_ref().unwrap_or(&self.compression),
+                namespace,
+            )
+            .await?;
+        if dedup {
+            let content_hash = hashv(&[&encoded_and_compressed]).to_bytes();
+            if let Some(blob) = self
+                .find_blob_by_content_hash(namespace.to_owned().into(), content_hash)
+                .await?
+            {
+                info!(
+                    "Blob already uploaded, skipping upload: namespace='{namespace}', blob={blob}"
+                );
+                return Ok(UploadBlobResult::new(Vec::new(), blob));
+            }
+        }
+
+        let (blob, upload_messages) = self
+            .prepare_upload(
+                &encoded_and_compressed,
+                fee_strategy,
+                namespace,
+                !defer_finalize,
+                address,
+                verify_before_finalize,
+            )
+            .await?;
+        if record {
+            return Ok(UploadBlobResult::recorded(RecordedUpload {
+                blob,
+                messages: upload_messages.into_messages(),
+            }));
+        }
+        self.upload(
+            blob,
+            upload_messages,
+            fee_strategy,
+            namespace,
+            timeout,
+            &cancel.unwrap_or_default(),
+        )
+        .await
+    }
+
+    /// Sends the messages of an upload recorded with [`UploadOptions::record`], in the
+    /// same stages as [`DataAnchorClient::upload_blob`]. Unlike a regular upload, a blob whose
+    /// declare fails is not discarded.
+    pub async fn replay_upload(
+        &self,
+        upload: &RecordedUpload,
+        timeout: Option<Duration>,
+    ) -> DataAnchorClientResult<UploadBlobResult> {
+        let upload_messages = UploadMessages::from_messages(upload.messages.clone())?;
+
+        self.do_upload(upload_messages, timeout, &CancellationToken::new())
+            .in_current_span()
+            .await
+            .map(|transactions| UploadBlobResult::new(transactions, upload.blob))
+    }
+
+    /// Sends the messages built by [`DataAnchorClient::prepare_upload`], discarding the blob PDA if
+    /// the declare fails or the upload is cancelled.
+    async fn upload(
+        &self,
+        blob: Pubkey,
+        upload_messages: UploadMessages,
+        fee_strategy: FeeStrategy,
+        namespace: &str,
+        timeout: Option<Duration>,
+        cancel: &CancellationToken,
+    ) -> DataAnchorClientResult<UploadBlobResult> {
+        if cancel.is_cancelled() {
+            return Err(ChainError::UploadCancelled.into());
+        }
+
+        let mut attempt = 1;
+        let res = loop {
+            let res = self
+                .do_upload(upload_messages.clone(), timeout, cancel)
+                .in_current_span()
+                .await;
+            match res {
+                Err(DataAnchorClientError::ChainErrors(e))
+                    if e.is_retryable() && attempt < MAX_UPLOAD_ATTEMPTS =>
+                {
+                    warn!("Upload attempt {attempt} of blob {blob} failed, retrying: {e}");
+                    // Start over from a clean slate, once discarded the same blob PDA can be
+                    // declared again.
+                    match self
+                        .discard_blob(fee_strategy, blob, namespace, timeout)
+                        .await
+                    {
+                        Ok(_)
+                        | Err(DataAnchorClientError::ChainErrors(
+                            ChainError::AccountDoesNotExist(_),
+                        )) => {}
+                        Err(e) => break Err(e),
+                    }
+                    attempt += 1;
+                }
+                res => break res,
+            }
+        };
+
+        match res {
+            Err(DataAnchorClientError::ChainErrors(ChainError::DeclareBlob(_))) => self
+                .discard_blob(fee_strategy, blob, namespace, timeout)
+                .await
+                .map(|(transactions, blob)| UploadBlobResult::new(transactions, blob)),
+            Err(e @ DataAnchorClientError::ChainErrors(ChainError::BlobDigestMismatch(_))) => {
+                warn!("Blob {blob} doesn't match the uploaded data, discarding it");
+                self.discard_blob(fee_strategy, blob, namespace, timeout)
+                    .await?;
+                Err(e)
+            }
+            Err(DataAnchorClientError::ChainErrors(ChainError::UploadCancelled)) => {
+                info!("Upload of blob {blob} was cancelled, discarding it");
+                // The declare might not have landed, in which case there is nothing to discard.
+                match self
+                    .discard_blob(fee_strategy, blob, namespace, timeout)
+                    .await
+                {
+                    Err(DataAnchorClientError::ChainErrors(ChainError::AccountDoesNotExist(_))) => {
+                        Err(ChainError::UploadCancelled.into())
+                    }
+                    res => {
+                        res.map(|(transactions, blob)| UploadBlobResult::new(transactions, blob))
+                    }
+                }
+            }
+            res => res.map(|transactions| UploadBlobResult::new(transactions, blob)),
+        }
+    }
+
+    /// Encodes and compresses the blob with the given [`EncodingType`] and [`CompressionType`],
+    /// producing the bytes that get uploaded.
+    async fn encode_for_upload<T>(
+        &self,
+        blob_data: &T,
+        encoding: &EncodingType,
+        compression: &CompressionType,
+        namespace: &str,
+    ) -> DataAnchorClientResult<Vec<u8>>
     where
         T: Encodable,
     {
@@ -371,10 +752,8 @@ impl DataAnchorClient {
             std::mem::size_of_val(blob_data)
         );
 
-        let blober = find_blober_address(self.program_id, self.payer.pubkey(), namespace);
-        let timestamp = get_unique_timestamp();
-
-        let encoded_and_compressed = self.encode_and_compress(blob_data).await?;
+        let encoded_and_compressed =
+            encode_and_compress_async(encoding, compression, blob_data).await?;
 
         info!(
             "Blob encoding/compression completed: compressed_size={} bytes, ratio={:.2}%",
@@ -382,6 +761,28 @@ impl DataAnchorClient {
             (encoded_and_compressed.len() as f64 / std::mem::size_of_val(blob_data) as f64) * 100.0
         );
 
+        Ok(encoded_and_compressed)
+    }
+
+    /// Builds the messages for uploading a blob encoded with
+    /// [`DataAnchorClient::encode_for_upload`], returning the blob PDA along with them. A blob PDA
+    /// and timestamp from [`DataAnchorClient::derive_blob_address`] are used instead of deriving
+    /// new ones, as long as they match the encoded blob.
+    async fn prepare_upload(
+        &self,
+        encoded_and_compressed: &[u8],
+        fee_strategy: FeeStrategy,
+        namespace: &str,
+        finalize: bool,
+        address: Option<(Pubkey, u64)>,
+        verify_before_finalize: bool,
+    ) -> DataAnchorClientResult<(Pubkey, UploadMessages)> {
+        let blober = find_blober_address(self.program_id, self.payer.pubkey(), namespace);
+        let timestamp = match address {
+            Some((_, timestamp)) => timestamp,
+            None => get_unique_timestamp(),
+        };
+
         let blob = find_blob_address(
             self.program_id,
             self.payer.pubkey(),
@@ -389,6 +790,11 @@ impl DataAnchorClient {
             timestamp,
             encoded_and_compressed.len(),
         );
+        if let Some((expected, _)) = address
+            && expected != blob
+        {
+            return Err(ChainError::BlobAddressMismatch(expected, blob).into());
+        }
 
         info!(
             "Created blob PDA: blob={}, blober={}, timestamp={}",
@@ -405,34 +811,111 @@ impl DataAnchorClient {
             .await?;
 
         if !in_mock_env {
-            let cost = fee
-                .total_fee()
-                .checked_add(fee.rent())
-                .ok_or_else(|| ChainError::CouldNotCalculateCost)?;
-            self.require_balance(cost).await?;
+            self.require_balance(fee.total_fee(), fee.rent()).await?;
         }
 
-        let upload_messages = self
+        let mut upload_messages = self
             .generate_messages(
                 blob,
                 timestamp,
-                &encoded_and_compressed,
+                encoded_and_compressed,
                 fee_strategy,
                 blober,
+                finalize,
             )
             .await?;
+        if verify_before_finalize {
+            upload_messages.verify_before_finalize(blob, encoded_and_compressed);
+        }
 
-        let res = self
-            .do_upload(upload_messages, timeout)
-            .in_current_span()
-            .await;
+        Ok((blob, upload_messages))
+    }
 
-        if let Err(DataAnchorClientError::ChainErrors(ChainError::DeclareBlob(_))) = res {
-            self.discard_blob(fee_strategy, blob, namespace, timeout)
-                .await
-        } else {
-            res.map(|r| (r, blob))
+    /// Derives the blob PDA and the unique timestamp an upload of `blob_data_len` bytes would use,
+    /// so the address can be recorded before the upload completes. The length is that of the data
+    /// after [`DataAnchorClient::encode_and_compress`], which is what gets uploaded.
+    ///
+    /// Pass the result to [`DataAnchorClient::upload_blob`] to upload the blob at this address.
+    pub fn derive_blob_address(&self, blob_data_len: usize, namespace: &str) -> (Pubkey, u64) {
+        let blober = find_blober_address(self.program_id, self.payer.pubkey(), namespace);
+        let timestamp = get_unique_timestamp();
+        let blob = find_blob_address(
+            self.program_id,
+            self.payer.pubkey(),
+            blober,
+            timestamp,
+            blob_data_len,
+        );
+        (blob, timestamp)
+    }
+
+    /// Finalizes [`data_anchor_blober::state::blob::Blob`] PDA accounts uploaded with
+    /// [`UploadOptions::defer_finalize`], packing up to
+    /// [`FinalizeBlobs::MAX_BLOBS_PER_TX`] blobs into each
+    /// [`data_anchor_blober::instruction::FinalizeBlobs`] transaction. The blobs of a transaction
+    /// are finalized in the given order.
+    ///
+    /// Returns one transaction per batch of blobs, in the same order as the input.
+    pub async fn finalize_blobs(
+        &self,
+        blobs: &[Pubkey],
+        namespace: &str,
+        fee_strategy: FeeStrategy,
+        timeout: Option<Duration>,
+    ) -> DataAnchorClientResult<Vec<SuccessfulTransaction<TransactionType>>> {
+        let blober = find_blober_address(self.program_id, self.payer.pubkey(), namespace);
+
+        let in_mock_env = self.in_mock_env();
+        if !in_mock_env {
+            for blob in blobs {
+                if !self.check_account_exists(*blob).await? {
+                    return Err(ChainError::AccountDoesNotExist(format!(
+                        "Blob PDA with address {blob}"
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        let mut messages =
+            Vec::with_capacity(blobs.len().div_ceil(FinalizeBlobs::MAX_BLOBS_PER_TX));
+        let mut total_fee = Lamports::ZERO;
+        for batch in blobs.chunks(FinalizeBlobs::MAX_BLOBS_PER_TX) {
+            let batch = FinalizeBlobs::new(batch.to_vec());
+
+            let mut mutable_accounts = batch.blobs().to_vec();
+            mutable_accounts.extend([blober, self.payer.pubkey()]);
+            let fee = self
+                .fixed_fee(
+                    fee_strategy,
+                    &mutable_accounts,
+                    TransactionType::FinalizeBlobs,
+                )
+                .in_current_span()
+                .await?;
+            total_fee = total_fee
+                .checked_add(fee.total_fee())
+                .ok_or_else(|| ChainError::CouldNotCalculateCost)?;
+
+            let msg = FinalizeBlobs::build_message(self.message_arguments(blober, fee, batch))
+                .in_current_span()
+                .await;
+            messages.push((TransactionType::FinalizeBlobs, msg));
+        }
+
+        if !in_mock_env {
+            self.require_balance(total_fee, Lamports::ZERO).await?;
         }
+
+        let span = info_span!(parent: Span::current(), "finalize_blobs");
+        Ok(check_outcomes(
+            self.nitro_sender
+                .send(messages, timeout)
+                .instrument(span)
+                .await,
+            self.commitment,
+        )
+        .map_err(ChainError::FinalizeBlob)?)
     }
 
     /// Discards a [`data_anchor_blober::state::blob::Blob`] PDA account registered with the provided
@@ -453,9 +936,9 @@ impl DataAnchorClient {
             );
         }
 
-        let fee = fee_strategy
-            .convert_fee_strategy_to_fixed(
-                &self.rpc_client,
+        let fee = self
+            .fixed_fee(
+                fee_strategy,
                 &[blob, self.payer.pubkey()],
                 TransactionType::DiscardBlob,
             )
@@ -463,19 +946,13 @@ impl DataAnchorClient {
             .await?;
 
         if !in_mock_env {
-            self.require_balance(fee.total_fee()).await?;
+            self.require_balance(fee.total_fee(), Lamports::ZERO)
+                .await?;
         }
 
-        let msg = DiscardBlob::build_message(MessageArguments::new(
-            self.program_id,
-            blober,
-            &self.payer,
-            self.rpc_client.clone(),
-            fee,
-            blob,
-        ))
-        .in_current_span()
-        .await;
+        let msg = DiscardBlob::build_message(self.message_arguments(blober, fee, blob))
+            .in_current_span()
+            .await;
 
         let span = info_span!(parent: Span::current(), "discard_blob");
 
@@ -485,15 +962,109 @@ impl DataAnchorClient {
                     .send(vec![(TransactionType::DiscardBlob, msg)], timeout)
                     .instrument(span)
                     .await,
-                self.rpc_client.commitment(),
+                self.commitment,
             )
             .map_err(ChainError::DiscardBlob)?,
             blob,
         ))
     }
 
+    /// Discards multiple [`data_anchor_blober::state::blob::Blob`] PDA accounts registered with
+    /// the provided [`Blober`] PDA account, packing up to [`DiscardBlobs::MAX_BLOBS_PER_TX`]
+    /// discard instructions into each transaction.
+    ///
+    /// Returns the outcome for each blob, in the same order as the input.
+    pub async fn discard_blobs(
+        &self,
+        blobs: &[Pubkey],
+        namespace: &str,
+        fee_strategy: FeeStrategy,
+        timeout: Option<Duration>,
+    ) -> DataAnchorClientResult<Vec<(Pubkey, DiscardBlobOutcome)>> {
+        let blober = find_blober_address(self.program_id, self.payer.pubkey(), namespace);
+
+        let in_mock_env = self.in_mock_env();
+        let mut outcomes: Vec<_> = blobs
+            .iter()
+            .map(|blob| (*blob, DiscardBlobOutcome::NotFound))
+            .collect();
+        let mut existing = Vec::with_capacity(blobs.len());
+        for (idx, blob) in blobs.iter().enumerate() {
+            if in_mock_env || self.check_account_exists(*blob).await? {
+                existing.push((idx, *blob));
+            }
+        }
+
+        if existing.is_empty() {
+            return Ok(outcomes);
+        }
+
+        let batches = existing
+            .chunks(DiscardBlobs::MAX_BLOBS_PER_TX)
+            .map(|batch| batch.to_vec())
+            .collect::<Vec<_>>();
+
+        let mut messages = Vec::with_capacity(batches.len());
+        let mut total_fee = Lamports::ZERO;
+        for batch in &batches {
+            let batch = DiscardBlobs::new(batch.iter().map(|(_, blob)| *blob).collect());
+
+            let mut mutable_accounts = batch.blobs().to_vec();
+            mutable_accounts.push(self.payer.pubkey());
+            let fee = self
+                .fixed_fee(
+                    fee_strategy,
+                    &mutable_accounts,
+                    TransactionType::DiscardBlobs,
+                )
+                .in_current_span()
+                .await?;
+            total_fee = total_fee
+                .checked_add(fee.total_fee())
+                .ok_or_else(|| ChainError::CouldNotCalculateCost)?;
+
+            let msg = DiscardBlobs::build_message(self.message_arguments(blober, fee, batch))
+                .in_current_span()
+                .await;
+            messages.push((TransactionType::DiscardBlobs, msg));
+        }
+
+        if !in_mock_env {
+            self.require_balance(total_fee, Lamports::ZERO).await?;
+        }
+
+        let span = info_span!(parent: Span::current(), "discard_blobs");
+        let results = self
+            .nitro_sender
+            .send(messages, timeout)
+            .instrument(span)
+            .await;
+
+        let commitment = self.commitment;
+        for (batch, result) in batches.iter().zip(results) {
+            let outcome = if let Some(error) = result.error() {
+                DiscardBlobOutcome::Failed(error.error.to_string())
+            } else if let Some(tx) = result.into_successful(commitment) {
+                DiscardBlobOutcome::Discarded {
+                    signature: tx.signature,
+                    slot: tx.slot,
+                }
+            } else {
+                DiscardBlobOutcome::Failed("transaction was not confirmed".to_owned())
+            };
+            for (idx, _) in batch {
+                outcomes[*idx].1 = outcome.clone();
+            }
+        }
+
+        Ok(outcomes)
+    }
+
     /// Configures a checkpoint for a given blober with the given authority.
     /// This allows the authority to create checkpoints for the blober.
+    ///
+    /// Once configured, only the current authority can change it, see
+    /// [`Self::get_checkpoint_config`].
     pub async fn configure_checkpoint(
         &self,
         fee_strategy: FeeStrategy,
@@ -514,9 +1085,23 @@ impl DataAnchorClient {
             .into());
         }
 
-        let fee = fee_strategy
-            .convert_fee_strategy_to_fixed(
-                &self.rpc_client,
+        // The program rejects reconfiguring the checkpoint by anyone but the current authority.
+        if let Some(config) = self
+            .get_checkpoint_config(BloberIdentifier::Pubkey(blober))
+            .await?
+            && config.authority != Pubkey::default()
+            && config.authority != self.payer.pubkey()
+        {
+            return Err(ChainError::NotCheckpointAuthority {
+                blober,
+                authority: config.authority,
+            }
+            .into());
+        }
+
+        let fee = self
+            .fixed_fee(
+                fee_strategy,
                 &[checkpoint, checkpoint_config, self.payer.pubkey()],
                 TransactionType::ConfigureCheckpoint,
             )
@@ -524,23 +1109,18 @@ impl DataAnchorClient {
             .await?;
 
         if !in_mock_env {
-            self.require_balance(fee.total_fee()).await?;
+            self.require_balance(fee.total_fee(), Lamports::ZERO)
+                .await?;
         }
 
         info!(
             "Configuring checkpoint for blober: {}, authority: {}",
             blober, authority
         );
-        let msg = ConfigureCheckpoint::build_message(MessageArguments::new(
-            self.program_id,
-            blober,
-            &self.payer,
-            self.rpc_client.clone(),
-            fee,
-            authority,
-        ))
-        .in_current_span()
-        .await;
+        let msg =
+            ConfigureCheckpoint::build_message(self.message_arguments(blober, fee, authority))
+                .in_current_span()
+                .await;
 
         let span = info_span!(parent: Span::current(), "configure_checkpoint");
 
@@ -550,13 +1130,173 @@ impl DataAnchorClient {
                     .send(vec![(TransactionType::ConfigureCheckpoint, msg)], timeout)
                     .instrument(span)
                     .await,
-                self.rpc_client.commitment(),
+                self.commitment,
             )
             .map_err(ChainError::ConfigureCheckpoint)?,
             checkpoint_config,
         ))
     }
 
+    /// Verifies a generated proof with the verifier program configured as the blober's
+    /// checkpoint authority, which stores it as the blober's checkpoint for the given slot.
+    ///
+    /// The checkpoint has to be configured with [`Self::configure_checkpoint`] first, and the slot
+    /// has to be newer than the one of the current checkpoint.
+    pub async fn create_checkpoint(
+        &self,
+        fee_strategy: FeeStrategy,
+        identifier: BloberIdentifier,
+        proof: ProofData,
+        slot: u64,
+        timeout: Option<Duration>,
+    ) -> DataAnchorClientResult<(Vec<SuccessfulTransaction<TransactionType>>, Pubkey)> {
+        let blober = identifier.to_blober_address(self.program_id, self.payer.pubkey());
+        let checkpoint = find_checkpoint_address(self.program_id, blober);
+        let verifier = self.checkpoint_verifier(blober).await?;
+
+        // Only Groth16 proofs can be verified on-chain.
+        let proof_bytes = proof
+            .groth16_proof()
+            .map_err(|e| ChainError::InvalidCheckpointProof(e.to_string()))?;
+        let input = CreateCheckpoint {
+            blober,
+            proof: proof_bytes,
+            public_values: proof.public_values,
+            verification_key: proof.verification_key,
+            slot,
+        };
+        let proof_blober = Checkpoint::new(
+            input.proof,
+            input.public_values.clone(),
+            input.verification_key.clone(),
+            slot,
+        )
+        .and_then(|checkpoint| Ok(checkpoint.parsed_public_values()?.blober()))
+        .map_err(|e| ChainError::InvalidCheckpointProof(e.to_string()))?;
+        if proof_blober != blober {
+            return Err(ChainError::ProofBloberMismatch(blober, proof_blober).into());
+        }
+
+        let fee = self
+            .fixed_fee(
+                fee_strategy,
+                &[
+                    checkpoint,
+                    find_checkpoint_signer_address(verifier.authority(), blober),
+                    self.payer.pubkey(),
+                ],
+                TransactionType::CreateCheckpoint,
+            )
+            .in_current_span()
+            .await?;
+
+        if !self.in_mock_env() {
+            self.require_balance(fee.total_fee(), Lamports::ZERO)
+                .await?;
+        }
+
+        info!("Creating checkpoint for blober: {blober}, slot: {slot}, verifier: {verifier}");
+        let msg =
+            CreateCheckpoint::build_message(self.message_arguments(blober, fee, (input, verifier)))
+                .in_current_span()
+                .await;
+
+        let span = info_span!(parent: Span::current(), "create_checkpoint");
+
+        Ok((
+            check_outcomes(
+                self.nitro_sender
+                    .send(vec![(TransactionType::CreateCheckpoint, msg)], timeout)
+                    .instrument(span)
+                    .await,
+                self.commitment,
+            )
+            .map_err(ChainError::CreateCheckpoint)?,
+            checkpoint,
+        ))
+    }
+
+    /// Returns the verifier program configured as the checkpoint authority of the given blober.
+    async fn checkpoint_verifier(&self, blober: Pubkey) -> DataAnchorClientResult<CustomerElf> {
+        let config = self
+            .get_checkpoint_config(BloberIdentifier::Pubkey(blober))
+            .await?
+            .ok_or_else(|| {
+                ChainError::AccountDoesNotExist(format!(
+                    "Checkpoint config PDA with address {}",
+                    find_checkpoint_config_address(self.program_id, blober)
+                ))
+            })?;
+
+        CustomerElf::from_authority(config.authority)
+            .ok_or_else(|| ChainError::UnknownCheckpointAuthority(config.authority).into())
+    }
+
+    /// Simulates the verifier program's `verify` instruction for the given checkpoint, without
+    /// sending it, and returns the compute units it consumed. This can be used to set the compute
+    /// unit limit of the transaction that creates the checkpoint.
+    ///
+    /// The simulated transaction requests [`SIMULATION_COMPUTE_UNIT_LIMIT`] compute units, so the
+    /// result includes the cost of the compute budget instruction.
+    pub async fn simulate_verify(
+        &self,
+        checkpoint_args: CheckpointArgs,
+    ) -> DataAnchorClientResult<u64> {
+        let CheckpointArgs {
+            blober,
+            verifier,
+            proof,
+            public_values,
+            verification_key,
+            slot,
+        } = checkpoint_args;
+        let blober = blober.to_blober_address(self.program_id, self.payer.pubkey());
+        let input = CreateCheckpoint {
+            blober,
+            proof,
+            public_values,
+            verification_key,
+            slot,
+        };
+
+        let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+            SIMULATION_COMPUTE_UNIT_LIMIT,
+        )];
+        instructions.extend(CreateCheckpoint::generate_instructions(
+            &self.message_arguments(blober, Fee::ZERO, (input, verifier)),
+        ));
+        let transaction = Transaction::new_unsigned(Message::new(
+            &instructions,
+            Some(&self.fee_payer().pubkey()),
+        ));
+
+        let result = self
+            .rpc_client
+            .simulate_transaction_with_config(
+                &transaction,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    commitment: Some(self.commitment),
+                    ..Default::default()
+                },
+            )
+            .await?
+            .value;
+
+        if let Some(err) = result.err {
+            return Err(ChainError::SimulationFailed(format!(
+                "{err}: {}",
+                result.logs.unwrap_or_default().join("\n")
+            ))
+            .into());
+        }
+
+        result.units_consumed.ok_or_else(|| {
+            ChainError::SimulationFailed("No compute units were reported".to_owned()).into()
+        })
+    }
+
     /// Estimates fees for uploading a blob of the size `blob_size` with the given `priority`.
     /// This whole functions is basically a simulation that doesn't run anything. Instead of executing transactions,
     /// it just sums the expected fees and number of signatures.
@@ -569,9 +1309,9 @@ impl DataAnchorClient {
         blober: Pubkey,
         fee_strategy: FeeStrategy,
     ) -> DataAnchorClientResult<Fee> {
-        let prioritization_fee_rate = fee_strategy
-            .convert_fee_strategy_to_fixed(
-                &self.rpc_client,
+        let prioritization_fee_rate = self
+            .fixed_fee(
+                fee_strategy,
                 &[Pubkey::new_unique(), blober, self.payer.pubkey()],
                 TransactionType::Compound,
             )
@@ -580,23 +1320,22 @@ impl DataAnchorClient {
 
         let num_chunks = blob_size.div_ceil(CHUNK_SIZE as usize) as u16;
 
-        let (compute_unit_limit, num_signatures) = if blob_size < COMPOUND_TX_SIZE as usize {
-            (Compound::COMPUTE_UNIT_LIMIT, Compound::NUM_SIGNATURES)
+        let (compute_unit_limit, num_transactions) = if blob_size < COMPOUND_TX_SIZE as usize {
+            (Compound::COMPUTE_UNIT_LIMIT, 1)
         } else if blob_size < COMPOUND_DECLARE_TX_SIZE as usize {
             (
                 CompoundDeclare::COMPUTE_UNIT_LIMIT + FinalizeBlob::COMPUTE_UNIT_LIMIT,
-                CompoundDeclare::NUM_SIGNATURES + FinalizeBlob::NUM_SIGNATURES,
+                2,
             )
         } else {
             (
                 DeclareBlob::COMPUTE_UNIT_LIMIT
                     + (num_chunks - 1) as u32 * InsertChunk::COMPUTE_UNIT_LIMIT
                     + CompoundFinalize::COMPUTE_UNIT_LIMIT,
-                DeclareBlob::NUM_SIGNATURES
-                    + (num_chunks - 1) * InsertChunk::NUM_SIGNATURES
-                    + CompoundFinalize::NUM_SIGNATURES,
+                num_chunks + 1,
             )
         };
+        let num_signatures = num_transactions * self.num_signatures();
 
         // The base Solana transaction fee = 5000.
         // Reference link: https://solana.com/docs/core/fees#:~:text=While%20transaction%20fees%20are%20paid,of%205k%20lamports%20per%20signature.
@@ -15,6 +15,12 @@ pub enum ProofError {
     /// Failed to get proof request status: {0}
     #[error("Failed to get proof request status for request ID {0}: {1}")]
     ProofRequestStatus(String, String),
+    /// Failed to generate {2} proof locally for blober {0} and slot {1}: {3}
+    #[error("Failed to generate {2} proof locally for blober {0} and slot {1}: {3}")]
+    LocalProof(String, u64, CustomerElf, String),
+    /// Proof verification failed for blober {0} and slot {1}: {2}
+    #[error("Proof verification failed for blober {0} and slot {1}: {2}")]
+    Verification(String, u64, String),
 }
 
 impl DataAnchorClient {
@@ -1,6 +1,14 @@
 /// Default number of concurrent requests to send to the RPC.
 pub const DEFAULT_CONCURRENCY: usize = 100;
 
+/// Maximum number of times [`crate::client::DataAnchorClient::upload_blob`] attempts an upload
+/// that fails with a retryable error.
+pub const MAX_UPLOAD_ATTEMPTS: u32 = 3;
+
+/// Compute unit limit set when simulating transactions to measure their compute cost, the
+/// maximum a transaction can request.
+pub const SIMULATION_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
 /// Default number of slots to look back for the
 /// [`crate::client::DataAnchorClient::get_ledger_blobs`] method.
 pub const DEFAULT_LOOKBACK_SLOTS: u64 = 100;
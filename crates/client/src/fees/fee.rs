@@ -1,5 +1,8 @@
 use anchor_lang::{prelude::Rent, solana_program::instruction::Instruction};
+use serde::Serialize;
 use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_native_token::LAMPORTS_PER_SOL;
+use thiserror::Error;
 
 use super::{Lamports, MicroLamports};
 
@@ -18,6 +21,32 @@ pub struct Fee {
     pub blob_account_size: usize,
 }
 
+/// The components of a [`Fee`] converted to SOL, for display and billing. The lamport values
+/// returned by [`Fee`] remain the source of truth.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
+pub struct FeeBreakdown {
+    /// The static fee for the signatures, see [`Fee::static_fee`].
+    pub base_fee_sol: f64,
+    /// The prioritization fee, see [`Fee::prioritization_fee`].
+    pub priority_fee_sol: f64,
+    /// The rent deposit for the blob account, see [`Fee::rent`].
+    pub rent_sol: f64,
+    /// The total cost of the upload, including rent.
+    pub total_sol: f64,
+}
+
+/// Failures that can occur when creating a [`Fee`] from a SOL amount.
+#[derive(Error, Debug, PartialEq)]
+pub enum FeeFromSolError {
+    #[error(
+        "{0} SOL is not a valid fee, it must be between 0 and {max} SOL",
+        max = Lamports(u32::MAX).to_sol()
+    )]
+    InvalidAmount(f64),
+    #[error("The prioritization fee exceeds the total fee of {0}")]
+    PriorityFeeExceedsTotal(Lamports),
+}
+
 impl Fee {
     pub const ZERO: Fee = Fee {
         num_signatures: 0,
@@ -27,6 +56,39 @@ impl Fee {
         blob_account_size: 0,
     };
 
+    /// Creates a fee whose [`Fee::total_fee`] is `total_sol` SOL, paying `priority_microlamports`
+    /// per compute unit for up to `compute_unit_limit` compute units.
+    ///
+    /// `total_sol` is rounded to the nearest lamport. The prioritization fee is rounded up to the
+    /// nearest lamport, like in [`Fee::prioritization_fee`], and whatever remains of the total is
+    /// charged as the price of a single signature. The fee doesn't include any rent.
+    pub fn from_sol(
+        total_sol: f64,
+        priority_microlamports: MicroLamports,
+        compute_unit_limit: u32,
+    ) -> Result<Self, FeeFromSolError> {
+        let lamports = (total_sol * LAMPORTS_PER_SOL as f64).round();
+        // Also rejects NaN, which isn't contained in any range.
+        if !(0.0..=u32::MAX as f64).contains(&lamports) {
+            return Err(FeeFromSolError::InvalidAmount(total_sol));
+        }
+        let total = Lamports::new(lamports as u32);
+
+        let price_per_signature = priority_microlamports
+            .checked_mul(compute_unit_limit as u64)
+            .and_then(|fee| Lamports::try_from(fee).ok())
+            .and_then(|prioritization_fee| total.checked_sub(prioritization_fee))
+            .ok_or(FeeFromSolError::PriorityFeeExceedsTotal(total))?;
+
+        Ok(Fee {
+            num_signatures: 1,
+            price_per_signature,
+            compute_unit_limit,
+            prioritization_fee_rate: priority_microlamports,
+            blob_account_size: 0,
+        })
+    }
+
     /// Calculate the static part of the fee for a blob upload.
     /// It is proportional to the number of signatures.
     pub fn static_fee(&self) -> Lamports {
@@ -63,6 +125,22 @@ impl Fee {
         Lamports::new(minimum_balance)
     }
 
+    /// Breaks the fee down into its components in SOL. Unlike [`Fee::total_fee`], the total
+    /// includes rent.
+    pub fn breakdown(&self) -> FeeBreakdown {
+        let total = self
+            .total_fee()
+            .checked_add(self.rent())
+            .expect("addition overflow");
+
+        FeeBreakdown {
+            base_fee_sol: self.static_fee().to_sol(),
+            priority_fee_sol: self.prioritization_fee().to_sol(),
+            rent_sol: self.rent().to_sol(),
+            total_sol: total.to_sol(),
+        }
+    }
+
     /// Creates a transaction for setting the compute unit price for a transaction.
     pub fn set_compute_unit_price(&self) -> Instruction {
         ComputeBudgetInstruction::set_compute_unit_price(self.prioritization_fee_rate.0)
@@ -84,4 +162,66 @@ mod tests {
         };
         assert_eq!(fee.prioritization_fee(), Lamports::new(1));
     }
+
+    #[test]
+    fn from_sol_round_trips_through_total_fee() {
+        for (total_sol, rate, compute_unit_limit, expected) in [
+            (0.0, 0, 0, 0),
+            (0.00001, 10_000, 200_000, 10_000),
+            (0.001, 999_999, 1, 1_000_000),
+            (1.0, 0, 1_400_000, 1_000_000_000),
+            // Rounded to the nearest lamport.
+            (0.0000000014, 0, 0, 1),
+            (0.0000000016, 0, 0, 2),
+            (0.0000000004, 0, 0, 0),
+        ] {
+            let fee =
+                Fee::from_sol(total_sol, MicroLamports::new(rate), compute_unit_limit).unwrap();
+            assert_eq!(fee.total_fee(), Lamports::new(expected), "{total_sol} SOL");
+            assert_eq!(fee.compute_unit_limit, compute_unit_limit);
+            assert_eq!(fee.prioritization_fee_rate, MicroLamports::new(rate));
+            assert_eq!(fee.rent(), Fee::ZERO.rent());
+        }
+    }
+
+    #[test]
+    fn from_sol_rejects_invalid_amounts() {
+        for total_sol in [-0.001, f64::NAN, f64::INFINITY, 5.0] {
+            assert!(matches!(
+                Fee::from_sol(total_sol, MicroLamports::ZERO, 0),
+                Err(FeeFromSolError::InvalidAmount(_))
+            ));
+        }
+        assert_eq!(
+            Fee::from_sol(0.000001, MicroLamports::new(10_000), 200_000).unwrap_err(),
+            FeeFromSolError::PriorityFeeExceedsTotal(Lamports::new(1000))
+        );
+        assert_eq!(
+            Fee::from_sol(0.000001, MicroLamports::new(u64::MAX), 2).unwrap_err(),
+            FeeFromSolError::PriorityFeeExceedsTotal(Lamports::new(1000))
+        );
+    }
+
+    #[test]
+    fn breakdown_converts_lamports_to_sol() {
+        let fee = Fee {
+            num_signatures: 3,
+            price_per_signature: Lamports::new(5000),
+            compute_unit_limit: 200_000,
+            prioritization_fee_rate: MicroLamports::new(10_000),
+            blob_account_size: 0,
+        };
+        // Rent for an empty account is the account storage overhead, 128 bytes * 2 years.
+        assert_eq!(fee.rent(), Lamports::new(890_880));
+
+        assert_eq!(
+            fee.breakdown(),
+            FeeBreakdown {
+                base_fee_sol: 0.000015,
+                priority_fee_sol: 0.000002,
+                rent_sol: 0.00089088,
+                total_sol: 0.00090788,
+            }
+        );
+    }
 }
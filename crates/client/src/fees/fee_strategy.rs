@@ -2,7 +2,7 @@ use anchor_lang::prelude::Pubkey;
 use solana_client::nonblocking::rpc_client::RpcClient;
 use tracing::Instrument;
 
-use super::Lamports;
+use super::{FeeFromSolError, Lamports, MicroLamports};
 use crate::{ChainError, DataAnchorClientResult, Fee, Priority, TransactionType};
 
 /// The strategy to use for calculating the fees for transactions.
@@ -33,12 +33,23 @@ impl From<Priority> for FeeStrategy {
 }
 
 impl FeeStrategy {
-    /// Converts a [`FeeStrategy`] into a [`Fee`] with the current compute unit price.
+    /// Creates a fixed fee strategy from a total fee in SOL, see [`Fee::from_sol`].
+    pub fn fixed_sol(
+        total_sol: f64,
+        priority_microlamports: MicroLamports,
+        compute_unit_limit: u32,
+    ) -> Result<Self, FeeFromSolError> {
+        Fee::from_sol(total_sol, priority_microlamports, compute_unit_limit).map(Self::Fixed)
+    }
+
+    /// Converts a [`FeeStrategy`] into a [`Fee`] with the current compute unit price, for a
+    /// transaction signed by `num_signatures` signers.
     pub(crate) async fn convert_fee_strategy_to_fixed(
         &self,
         rpc_client: &RpcClient,
         mutating_accounts: &[Pubkey],
         tx_type: TransactionType,
+        num_signatures: u16,
     ) -> DataAnchorClientResult<Fee> {
         let priority = match self {
             FeeStrategy::Fixed(fee) => {
@@ -60,7 +71,7 @@ impl FeeStrategy {
                 Ok(fee) => {
                     return Ok(Fee {
                         prioritization_fee_rate: fee,
-                        num_signatures: tx_type.num_signatures(),
+                        num_signatures,
                         compute_unit_limit: tx_type.compute_unit_limit(),
                         price_per_signature: Lamports(5000),
                         blob_account_size: 0,
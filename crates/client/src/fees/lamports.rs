@@ -1,5 +1,6 @@
 use std::{fmt::Display, num::TryFromIntError};
 
+use solana_native_token::LAMPORTS_PER_SOL;
 use thiserror::Error;
 
 use super::MicroLamports;
@@ -22,6 +23,11 @@ impl Lamports {
         self.0
     }
 
+    /// Converts the value to SOL, for display purposes.
+    pub fn to_sol(self) -> f64 {
+        self.0 as f64 / LAMPORTS_PER_SOL as f64
+    }
+
     /// Multiplies the inner value by the given value, returning `None` if the result would overflow.
     pub fn checked_mul(&self, rhs: u32) -> Option<Self> {
         self.0.checked_mul(rhs).map(Lamports)
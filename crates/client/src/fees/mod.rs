@@ -4,7 +4,7 @@ mod lamports;
 mod microlamports;
 mod priority;
 
-pub use fee::Fee;
+pub use fee::{Fee, FeeBreakdown, FeeFromSolError};
 pub use fee_strategy::FeeStrategy;
 pub use lamports::Lamports;
 pub use microlamports::MicroLamports;
@@ -5,53 +5,167 @@ use std::{
     time::{Duration, Instant, SystemTime},
 };
 
-use anchor_lang::{prelude::Pubkey, solana_program::message::Message};
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{hash::HASH_BYTES, message::Message},
+};
 use data_anchor_api::RelevantInstructionWithAccounts;
 use data_anchor_blober::{
-    CHUNK_SIZE, COMPOUND_DECLARE_TX_SIZE, COMPOUND_TX_SIZE,
-    instruction::{DeclareBlob, FinalizeBlob, InsertChunk},
+    CHUNK_SIZE, COMPOUND_DECLARE_TX_SIZE, COMPOUND_TX_SIZE, chunk_crc, compute_blob_digest,
+    instruction::{DeclareBlob, FinalizeBlob, InsertChunk, InsertChunkWithCrc},
 };
 use jsonrpsee::http_client::HttpClient;
 use nitro_sender::{SuccessfulTransaction, TransactionOutcome};
 use solana_commitment_config::CommitmentConfig;
 use solana_signer::Signer;
+use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, Span, info_span};
 
 use crate::{
-    DataAnchorClient, DataAnchorClientResult, FeeStrategy, OutcomeError,
+    DataAnchorClient, DataAnchorClientError, DataAnchorClientResult, FeeStrategy, OutcomeError,
     client::ChainError,
-    tx::{Compound, CompoundDeclare, CompoundFinalize, MessageArguments, MessageBuilder},
+    tx::{Compound, CompoundDeclare, CompoundFinalize, MessageBuilder},
     types::TransactionType,
 };
 
+#[derive(Clone)]
 pub enum UploadMessages {
     CompoundUpload(Message),
     StaggeredUpload {
         declare_blob: Message,
         insert_chunks: Vec<Message>,
-        finalize_blob: Message,
+        /// `None` when finalizing is deferred to [`DataAnchorClient::finalize_blobs`].
+        finalize_blob: Option<Message>,
+        /// The blob PDA and the digest it should have before finalizing, checked when set. See
+        /// [`UploadMessages::verify_before_finalize`].
+        verify_digest: Option<(Pubkey, [u8; HASH_BYTES])>,
     },
 }
 
+impl UploadMessages {
+    /// Flattens the messages into the order they are sent in, tagged with the transaction type
+    /// [`DataAnchorClient::do_upload`] sends them as.
+    pub(crate) fn into_messages(self) -> Vec<(TransactionType, Message)> {
+        match self {
+            UploadMessages::CompoundUpload(tx) => vec![(TransactionType::Compound, tx)],
+            UploadMessages::StaggeredUpload {
+                declare_blob,
+                insert_chunks,
+                finalize_blob,
+                ..
+            } => std::iter::once((TransactionType::DeclareBlob, declare_blob))
+                .chain(
+                    insert_chunks
+                        .into_iter()
+                        .enumerate()
+                        .map(|(idx, tx)| (TransactionType::InsertChunk(idx as u16), tx)),
+                )
+                .chain(finalize_blob.map(|tx| (TransactionType::FinalizeBlob, tx)))
+                .collect(),
+        }
+    }
+
+    /// Rebuilds the upload from messages produced by [`Self::into_messages`].
+    pub(crate) fn from_messages(
+        messages: Vec<(TransactionType, Message)>,
+    ) -> DataAnchorClientResult<Self> {
+        let mut messages = messages.into_iter();
+        let declare_blob = match messages.next() {
+            Some((TransactionType::Compound, tx)) if messages.len() == 0 => {
+                return Ok(UploadMessages::CompoundUpload(tx));
+            }
+            Some((TransactionType::DeclareBlob, tx)) => tx,
+            Some((tx_type, _)) => {
+                return Err(DataAnchorClientError::InvalidData(format!(
+                    "Recorded upload starts with an unexpected {tx_type} transaction"
+                )));
+            }
+            None => {
+                return Err(DataAnchorClientError::InvalidData(
+                    "Recorded upload has no messages".to_owned(),
+                ));
+            }
+        };
+
+        let mut insert_chunks = Vec::new();
+        let mut finalize_blob = None;
+        for (tx_type, tx) in messages {
+            match tx_type {
+                TransactionType::InsertChunk(_) if finalize_blob.is_none() => {
+                    insert_chunks.push(tx)
+                }
+                TransactionType::FinalizeBlob if finalize_blob.is_none() => {
+                    finalize_blob = Some(tx)
+                }
+                _ => {
+                    return Err(DataAnchorClientError::InvalidData(format!(
+                        "Recorded upload has an unexpected {tx_type} transaction"
+                    )));
+                }
+            }
+        }
+
+        Ok(UploadMessages::StaggeredUpload {
+            declare_blob,
+            insert_chunks,
+            finalize_blob,
+            verify_digest: None,
+        })
+    }
+
+    /// Makes the upload check that the digest of the blob matches `blob_data` before sending the
+    /// finalize transaction, so a blob missing a chunk is never finalized. The digest depends on
+    /// the order the chunks land in, so the chunks are inserted one at a time when checking it.
+    ///
+    /// Uploads without a separate finalize transaction are left unchanged.
+    pub(crate) fn verify_before_finalize(&mut self, blob: Pubkey, blob_data: &[u8]) {
+        let UploadMessages::StaggeredUpload {
+            insert_chunks,
+            finalize_blob: Some(_),
+            verify_digest,
+            ..
+        } = self
+        else {
+            return;
+        };
+
+        let chunks = split_blob_into_chunks(blob_data);
+        // Small blobs are inserted along with the declare, otherwise the last chunk is only
+        // inserted along with the finalize.
+        let inserted = if blob_data.len() <= COMPOUND_DECLARE_TX_SIZE as usize {
+            chunks.len()
+        } else {
+            insert_chunks.len()
+        };
+        *verify_digest = Some((blob, compute_blob_digest(&chunks[..inserted])));
+    }
+}
+
 impl DataAnchorClient {
     /// Uploads the blob: [`data_anchor_blober::DeclareBlob`], [`data_anchor_blober::InsertChunk`] * N,
-    /// [`data_anchor_blober::FinalizeBlob`].
+    /// [`data_anchor_blober::FinalizeBlob`]. The finalize step is skipped for deferred uploads.
+    ///
+    /// Once `cancel` is cancelled, the transactions in flight are dropped and no new ones are sent.
     pub(crate) async fn do_upload(
         &self,
         upload_messages: UploadMessages,
         timeout: Option<Duration>,
+        cancel: &CancellationToken,
     ) -> DataAnchorClientResult<Vec<SuccessfulTransaction<TransactionType>>> {
         let before = Instant::now();
-        let commitment = self.rpc_client.commitment();
+        let commitment = self.commitment;
 
         match upload_messages {
             UploadMessages::CompoundUpload(tx) => {
                 let span = info_span!(parent: Span::current(), "compound_upload");
                 Ok(check_outcomes(
-                    self.nitro_sender
-                        .send(vec![(TransactionType::Compound, tx)], timeout)
-                        .instrument(span)
-                        .await,
+                    unless_cancelled(
+                        cancel,
+                        self.nitro_sender
+                            .send(vec![(TransactionType::Compound, tx)], timeout)
+                            .instrument(span),
+                    )
+                    .await?,
                     commitment,
                 )
                 .map_err(ChainError::CompoundUpload)?)
@@ -60,13 +174,17 @@ impl DataAnchorClient {
                 declare_blob,
                 insert_chunks,
                 finalize_blob,
+                verify_digest,
             } => {
                 let span = info_span!(parent: Span::current(), "declare_blob");
                 let tx1 = check_outcomes(
-                    self.nitro_sender
-                        .send(vec![(TransactionType::DeclareBlob, declare_blob)], timeout)
-                        .instrument(span)
-                        .await,
+                    unless_cancelled(
+                        cancel,
+                        self.nitro_sender
+                            .send(vec![(TransactionType::DeclareBlob, declare_blob)], timeout)
+                            .instrument(span),
+                    )
+                    .await?,
                     commitment,
                 )
                 .map_err(ChainError::DeclareBlob)?;
@@ -74,33 +192,60 @@ impl DataAnchorClient {
                 let span = info_span!(parent: Span::current(), "insert_chunks");
                 let timeout =
                     timeout.map(|timeout| timeout.saturating_sub(Instant::now() - before));
-                let tx2 = check_outcomes(
-                    self.nitro_sender
-                        .send(
-                            insert_chunks
-                                .into_iter()
-                                .enumerate()
-                                .map(|(idx, tx)| (TransactionType::InsertChunk(idx as u16), tx))
-                                .collect(),
-                            timeout,
-                        )
+                let tx2 = if let Some((blob, expected_digest)) = verify_digest {
+                    let tx2 = self
+                        .insert_chunks_in_order(insert_chunks, timeout, cancel)
                         .instrument(span)
-                        .await,
-                    commitment,
-                )
-                .map_err(ChainError::InsertChunks)?;
+                        .await?;
+                    let digest = self
+                        .get_blob_account(blob)
+                        .await?
+                        .map(|blob| *blob.blob_digest());
+                    if digest != Some(expected_digest) {
+                        return Err(ChainError::BlobDigestMismatch(blob).into());
+                    }
+                    tx2
+                } else {
+                    check_outcomes(
+                        unless_cancelled(
+                            cancel,
+                            self.nitro_sender
+                                .send(
+                                    insert_chunks
+                                        .into_iter()
+                                        .enumerate()
+                                        .map(|(idx, tx)| {
+                                            (TransactionType::InsertChunk(idx as u16), tx)
+                                        })
+                                        .collect(),
+                                    timeout,
+                                )
+                                .instrument(span),
+                        )
+                        .await?,
+                        commitment,
+                    )
+                    .map_err(ChainError::InsertChunks)?
+                };
+
+                let Some(finalize_blob) = finalize_blob else {
+                    return Ok(tx1.into_iter().chain(tx2.into_iter()).collect());
+                };
 
                 let span = info_span!(parent: Span::current(), "finalize_blob");
                 let timeout =
                     timeout.map(|timeout| timeout.saturating_sub(Instant::now() - before));
                 let tx3 = check_outcomes(
-                    self.nitro_sender
-                        .send(
-                            vec![(TransactionType::FinalizeBlob, finalize_blob)],
-                            timeout,
-                        )
-                        .instrument(span)
-                        .await,
+                    unless_cancelled(
+                        cancel,
+                        self.nitro_sender
+                            .send(
+                                vec![(TransactionType::FinalizeBlob, finalize_blob)],
+                                timeout,
+                            )
+                            .instrument(span),
+                    )
+                    .await?,
                     commitment,
                 )
                 .map_err(ChainError::FinalizeBlob)?;
@@ -114,8 +259,41 @@ impl DataAnchorClient {
         }
     }
 
+    /// Sends the [`data_anchor_blober::InsertChunk`] messages one at a time, each after the
+    /// previous one has landed, so the chunks are hashed into the blob digest in order.
+    async fn insert_chunks_in_order(
+        &self,
+        insert_chunks: Vec<Message>,
+        timeout: Option<Duration>,
+        cancel: &CancellationToken,
+    ) -> DataAnchorClientResult<Vec<SuccessfulTransaction<TransactionType>>> {
+        let before = Instant::now();
+        let commitment = self.commitment;
+
+        let mut transactions = Vec::with_capacity(insert_chunks.len());
+        for (idx, tx) in insert_chunks.into_iter().enumerate() {
+            let timeout = timeout.map(|timeout| timeout.saturating_sub(Instant::now() - before));
+            transactions.extend(
+                check_outcomes(
+                    unless_cancelled(
+                        cancel,
+                        self.nitro_sender.send(
+                            vec![(TransactionType::InsertChunk(idx as u16), tx)],
+                            timeout,
+                        ),
+                    )
+                    .await?,
+                    commitment,
+                )
+                .map_err(ChainError::InsertChunks)?,
+            );
+        }
+        Ok(transactions)
+    }
+
     /// Generates a [`data_anchor_blober::DeclareBlob`], vector of [`data_anchor_blober::InsertChunk`]
-    /// and a [`data_anchor_blober::FinalizeBlob`] message.
+    /// and a [`data_anchor_blober::FinalizeBlob`] message. If `finalize` is false, every chunk is
+    /// inserted with an [`data_anchor_blober::InsertChunk`] and no finalize message is generated.
     pub(crate) async fn generate_messages(
         &self,
         blob: Pubkey,
@@ -123,22 +301,20 @@ impl DataAnchorClient {
         blob_data: &[u8],
         fee_strategy: FeeStrategy,
         blober: Pubkey,
+        finalize: bool,
     ) -> DataAnchorClientResult<UploadMessages> {
         tracing::warn!("Blob size: {}", blob_data.len());
-        if blob_data.len() <= COMPOUND_TX_SIZE as usize {
-            let fee_compound = fee_strategy
-                .convert_fee_strategy_to_fixed(
-                    &self.rpc_client,
+        if finalize && blob_data.len() <= COMPOUND_TX_SIZE as usize {
+            let fee_compound = self
+                .fixed_fee(
+                    fee_strategy,
                     &[blober, blob, self.payer.pubkey()],
                     TransactionType::Compound,
                 )
                 .await?;
 
-            let compound = Compound::build_message(MessageArguments::new(
-                self.program_id,
+            let compound = Compound::build_message(self.message_arguments(
                 blober,
-                &self.payer,
-                self.rpc_client.clone(),
                 fee_compound,
                 Compound::new(blob, timestamp, blob_data.to_vec()),
             ))
@@ -149,66 +325,51 @@ impl DataAnchorClient {
         }
 
         if blob_data.len() <= COMPOUND_DECLARE_TX_SIZE as usize {
-            let fee_compound_declare = fee_strategy
-                .convert_fee_strategy_to_fixed(
-                    &self.rpc_client,
+            let fee_compound_declare = self
+                .fixed_fee(
+                    fee_strategy,
                     &[blober, blob, self.payer.pubkey()],
                     TransactionType::Compound,
                 )
                 .await?;
 
-            let declare_blob = CompoundDeclare::build_message(MessageArguments::new(
-                self.program_id,
+            let declare_blob = CompoundDeclare::build_message(self.message_arguments(
                 blober,
-                &self.payer,
-                self.rpc_client.clone(),
                 fee_compound_declare,
                 CompoundDeclare::new(blob, timestamp, blob_data.to_vec()),
             ))
             .in_current_span()
             .await;
 
-            let fee_finalize = fee_strategy
-                .convert_fee_strategy_to_fixed(
-                    &self.rpc_client,
-                    &[blober, blob, self.payer.pubkey()],
-                    TransactionType::FinalizeBlob,
+            let finalize_blob = if finalize {
+                Some(
+                    self.generate_finalize_message(blob, fee_strategy, blober)
+                        .await?,
                 )
-                .await?;
-
-            let finalize_blob = FinalizeBlob::build_message(MessageArguments::new(
-                self.program_id,
-                blober,
-                &self.payer,
-                self.rpc_client.clone(),
-                fee_finalize,
-                blob,
-            ))
-            .in_current_span()
-            .await;
+            } else {
+                None
+            };
 
             return Ok(UploadMessages::StaggeredUpload {
                 declare_blob,
                 insert_chunks: Vec::new(),
                 finalize_blob,
+                verify_digest: None,
             });
         }
 
         let chunks = split_blob_into_chunks(blob_data);
 
-        let fee_declare = fee_strategy
-            .convert_fee_strategy_to_fixed(
-                &self.rpc_client,
+        let fee_declare = self
+            .fixed_fee(
+                fee_strategy,
                 &[blob, self.payer.pubkey()],
                 TransactionType::DeclareBlob,
             )
             .await?;
 
-        let declare_blob = DeclareBlob::build_message(MessageArguments::new(
-            self.program_id,
+        let declare_blob = DeclareBlob::build_message(self.message_arguments(
             blober,
-            &self.payer,
-            self.rpc_client.clone(),
             fee_declare,
             (
                 DeclareBlob {
@@ -221,84 +382,113 @@ impl DataAnchorClient {
         .in_current_span()
         .await;
 
-        let fee_insert = fee_strategy
-            .convert_fee_strategy_to_fixed(
-                &self.rpc_client,
+        let fee_insert = self
+            .fixed_fee(
+                fee_strategy,
                 &[blob, self.payer.pubkey()],
                 TransactionType::InsertChunk(0),
             )
             .await?;
 
         let mut chunk_iterator = chunks.iter();
-        let last_chunk = chunk_iterator.next_back();
+        let last_chunk = if finalize {
+            chunk_iterator.next_back()
+        } else {
+            None
+        };
 
         let insert_chunks =
             futures::future::join_all(chunk_iterator.map(|(chunk_index, chunk_data)| async move {
-                InsertChunk::build_message(MessageArguments::new(
-                    self.program_id,
-                    blober,
-                    &self.payer,
-                    self.rpc_client.clone(),
-                    fee_insert,
-                    (
-                        InsertChunk {
-                            idx: *chunk_index,
-                            data: chunk_data.to_vec(),
-                        },
-                        blob,
-                    ),
-                ))
-                .in_current_span()
-                .await
+                if self.chunk_crc {
+                    InsertChunkWithCrc::build_message(self.message_arguments(
+                        blober,
+                        fee_insert,
+                        (
+                            InsertChunkWithCrc {
+                                idx: *chunk_index,
+                                data: chunk_data.to_vec(),
+                                crc: chunk_crc(chunk_data),
+                            },
+                            blob,
+                        ),
+                    ))
+                    .in_current_span()
+                    .await
+                } else {
+                    InsertChunk::build_message(self.message_arguments(
+                        blober,
+                        fee_insert,
+                        (
+                            InsertChunk {
+                                idx: *chunk_index,
+                                data: chunk_data.to_vec(),
+                            },
+                            blob,
+                        ),
+                    ))
+                    .in_current_span()
+                    .await
+                }
             }))
             .await;
 
-        let finalize_blob = if let Some((chunk_idx, chunk_data)) = last_chunk {
-            let fee_compound_finalize = fee_strategy
-                .convert_fee_strategy_to_fixed(
-                    &self.rpc_client,
+        let finalize_blob = if !finalize {
+            None
+        } else if let Some((chunk_idx, chunk_data)) = last_chunk {
+            let fee_compound_finalize = self
+                .fixed_fee(
+                    fee_strategy,
                     &[blober, blob, self.payer.pubkey()],
                     TransactionType::CompoundFinalize,
                 )
                 .await?;
 
-            CompoundFinalize::build_message(MessageArguments::new(
-                self.program_id,
-                blober,
-                &self.payer,
-                self.rpc_client.clone(),
-                fee_compound_finalize,
-                CompoundFinalize::new(*chunk_idx, chunk_data.to_vec(), blob),
-            ))
-            .await
+            Some(
+                CompoundFinalize::build_message(self.message_arguments(
+                    blober,
+                    fee_compound_finalize,
+                    CompoundFinalize::new(*chunk_idx, chunk_data.to_vec(), blob),
+                ))
+                .await,
+            )
         } else {
-            let fee_finalize = fee_strategy
-                .convert_fee_strategy_to_fixed(
-                    &self.rpc_client,
-                    &[blober, blob, self.payer.pubkey()],
-                    TransactionType::FinalizeBlob,
-                )
-                .await?;
-
-            FinalizeBlob::build_message(MessageArguments::new(
-                self.program_id,
-                blober,
-                &self.payer,
-                self.rpc_client.clone(),
-                fee_finalize,
-                blob,
-            ))
-            .in_current_span()
-            .await
+            Some(
+                self.generate_finalize_message(blob, fee_strategy, blober)
+                    .await?,
+            )
         };
 
         Ok(UploadMessages::StaggeredUpload {
             declare_blob,
             insert_chunks,
             finalize_blob,
+            verify_digest: None,
         })
     }
 
+    /// Generates a [`data_anchor_blober::FinalizeBlob`] message for a blob whose chunks have all
+    /// been inserted.
+    pub(crate) async fn generate_finalize_message(
+        &self,
+        blob: Pubkey,
+        fee_strategy: FeeStrategy,
+        blober: Pubkey,
+    ) -> DataAnchorClientResult<Message> {
+        let fee_finalize = self
+            .fixed_fee(
+                fee_strategy,
+                &[blober, blob, self.payer.pubkey()],
+                TransactionType::FinalizeBlob,
+            )
+            .await?;
+
+        Ok(
+            FinalizeBlob::build_message(self.message_arguments(blober, fee_finalize, blob))
+                .in_current_span()
+                .await,
+        )
+    }
+
     /// Get a reference to the Indexer RPC client.
     ///
     /// # Panics
@@ -374,6 +564,19 @@ pub(crate) fn check_outcomes(
     }
 }
 
+/// Waits for `future` unless `cancel` is cancelled first, in which case the future is dropped.
+/// A token that is already cancelled never polls the future.
+async fn unless_cancelled<F: Future>(
+    cancel: &CancellationToken,
+    future: F,
+) -> Result<F::Output, ChainError> {
+    tokio::select! {
+        biased;
+        _ = cancel.cancelled() => Err(ChainError::UploadCancelled),
+        output = future => Ok(output),
+    }
+}
+
 /// Filters out the relevant instructions for finalized blobs into a [`HashMap`].
 pub fn filter_relevant_instructions(
     instructions: Vec<RelevantInstructionWithAccounts>,
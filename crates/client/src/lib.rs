@@ -1,5 +1,6 @@
 #![doc = include_str!("../README.md")]
 
+mod blob_reader;
 mod client;
 mod constants;
 mod fees;
@@ -10,8 +11,12 @@ mod tx;
 mod types;
 
 pub use crate::{
-    client::{BloberIdentifier, ChainError, DataAnchorClient, IndexerError, ProofError},
-    constants::IndexerUrl,
+    blob_reader::BlobReader,
+    client::{
+        BloberIdentifier, BloberIdentifierError, ChainError, DataAnchorClient, IndexerBudget,
+        IndexerError, ProofError,
+    },
+    constants::{IndexerUrl, MAX_UPLOAD_ATTEMPTS, SIMULATION_COMPUTE_UNIT_LIMIT},
     fees::*,
     types::*,
 };
@@ -1,18 +1,58 @@
 use std::{
-    sync::Arc,
+    collections::{BTreeMap, HashMap},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
     time::{Duration, SystemTime},
 };
 
 use anchor_lang::{
+    AnchorSerialize, Discriminator,
     prelude::Pubkey,
-    solana_program::{clock::DEFAULT_MS_PER_SLOT, hash::Hash},
+    solana_program::{
+        bpf_loader_upgradeable,
+        clock::DEFAULT_MS_PER_SLOT,
+        hash::{Hash, hashv},
+        message::VersionedMessage,
+    },
 };
 use async_trait::async_trait;
-use data_anchor_blober::find_blober_address;
-use data_anchor_utils::encode_and_compress_async;
+use base64::{Engine, prelude::BASE64_STANDARD};
+use data_anchor_api::{
+    BlobsBySlotRange, CompoundInclusionProof, CustomerElf, MAX_PROOF_SLOTS_PER_CALL, PageRequest,
+    ProofData, ProofSystem, PubkeyFromStr, SlotRangeError, TimeRange,
+};
+use data_anchor_blober::{
+    CHECKPOINT_PDA_SIGNER_SEED, CHECKPOINT_SEED, CHUNK_SIZE, GROTH16_PROOF_SIZE, SEED,
+    blob::Blob,
+    checkpoint::{Checkpoint, CheckpointConfig},
+    compute_blob_digest, find_blob_address, find_blober_address, find_checkpoint_address,
+    find_checkpoint_config_address, find_checkpoint_signer_address, initial_hash,
+    instruction::{
+        DiscardBlob, FinalizeBlob, FinalizeBlobs as FinalizeBlobsInstruction, InsertChunk,
+        InsertChunkWithCrc,
+    },
+    state::blober::Blober,
+};
+use data_anchor_proofs::blober_account_state::BloberAccountStateProof;
+use data_anchor_utils::{
+    compression::{CompressionType, ZstdCompression},
+    encode_and_compress_async,
+    encoding::EncodingType,
+};
+use futures::StreamExt;
 use itertools::Itertools;
+use jsonrpsee::{
+    RpcModule,
+    http_client::HttpClientBuilder,
+    server::{Server, ServerHandle},
+    types::ErrorObjectOwned,
+};
 use nitro_sender::NitroSender;
 use rand::Rng;
+use serde_json::json;
+use solana_account_decoder_client_types::{UiAccount, UiAccountData, UiAccountEncoding};
 use solana_client::{
     client_error::{ClientError as Error, ClientErrorKind as ErrorKind},
     nonblocking::rpc_client::RpcClient,
@@ -31,11 +71,19 @@ use solana_rpc_client_api::{
     config::RpcRequestAirdropConfig, request::RpcRequest, response::Response,
 };
 use solana_signer::Signer;
-use solana_transaction_status::TransactionStatus;
+use solana_transaction::Transaction;
+use solana_transaction_status::{TransactionStatus, UiTransactionEncoding};
 use tokio::time::Instant;
 use tokio_util::sync::CancellationToken;
 
-use crate::{DataAnchorClient, FeeStrategy, helpers::get_unique_timestamp};
+use crate::{
+    BlobInfo, BlobStatus, BloberIdentifier, BloberIdentifierError, ChainError, CheckpointArgs,
+    DataAnchorClient, DataAnchorClientError, DataAnchorClientResult, Fee, FeeStrategy,
+    IndexerBudget, IndexerError, Lamports, MicroLamports, RecordedUpload, TransactionType,
+    UploadOptions,
+    helpers::get_unique_timestamp,
+    tx::{FinalizeBlobs, MessageBuilder},
+};
 
 #[tokio::test]
 async fn full_workflow_mock() {
@@ -180,12 +228,13 @@ async fn full_workflow(blober_rpc_client: Arc<RpcClient>, check_ledger: bool) {
         .await
         .unwrap();
 
-    let (result, _) = data_anchor_client
+    let result = data_anchor_client
         .upload_blob(
             &data,
             fee_strategy,
             &namespace,
             Some(Duration::from_secs(20)),
+            UploadOptions::default(),
         )
         .await
         .unwrap();
@@ -213,7 +262,11 @@ async fn full_workflow(blober_rpc_client: Arc<RpcClient>, check_ledger: bool) {
         return;
     }
 
-    let signatures = result.iter().map(|r| r.signature).collect::<Vec<_>>();
+    let signatures = result
+        .transactions
+        .iter()
+        .map(|r| r.signature)
+        .collect::<Vec<_>>();
 
     let ledger_data = data_anchor_client
         .get_ledger_blobs_from_signatures::<Vec<u8>>(blober_pubkey.into(), signatures)
@@ -222,7 +275,7 @@ async fn full_workflow(blober_rpc_client: Arc<RpcClient>, check_ledger: bool) {
 
     assert_eq!(data, ledger_data);
 
-    let finalized_slot = result.last().unwrap().slot;
+    let finalized_slot = result.finalize_slot.unwrap();
 
     let all_ledger_blobs = data_anchor_client
         .get_ledger_blobs::<Vec<u8>>(
@@ -273,6 +326,7 @@ async fn failing_upload_returns_error() {
             FeeStrategy::default(),
             "test",
             Some(Duration::from_secs(5)),
+            UploadOptions::default(),
         )
         .await
         .unwrap_err();
@@ -281,138 +335,2440 @@ async fn failing_upload_returns_error() {
     cancellation_token.cancel();
 }
 
-// The default MockSender always returns the same value for get_last_blockhash and
-// get_epoch_info, so we wrap that in a bit more logic.
-struct MockBlockSender {
-    sender: MockSender,
-    initial_time: Instant,
+#[tokio::test]
+async fn upload_result_reports_finalize_slot() {
+    let payer = Arc::new(Keypair::new());
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        MockBlockSender {
+            sender: MockSender::new("succeeds".to_string()),
+            initial_time: Instant::now(),
+        },
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    // Random data doesn't compress, so the blob is uploaded in multiple chunks.
+    let mut data = vec![0u8; 5 * CHUNK_SIZE as usize];
+    rand::thread_rng().fill(&mut data[..]);
+
+    let result = data_anchor_client
+        .upload_blob(
+            &data,
+            FeeStrategy::default(),
+            "test",
+            Some(Duration::from_secs(20)),
+            UploadOptions::default(),
+        )
+        .await
+        .unwrap();
+
+    let finalize_tx = result
+        .transactions
+        .iter()
+        .find(|tx| tx.data.finalizes_blob())
+        .expect("the upload should contain a finalize transaction");
+    assert!(matches!(
+        finalize_tx.data,
+        TransactionType::CompoundFinalize
+    ));
+    assert_eq!(result.finalize_slot, Some(finalize_tx.slot));
+    assert!(
+        result
+            .transactions
+            .iter()
+            .any(|tx| matches!(tx.data, TransactionType::InsertChunk(_)))
+    );
+
+    cancellation_token.cancel();
 }
 
-#[async_trait]
-impl RpcSender for MockBlockSender {
-    async fn send(
-        &self,
-        request: RpcRequest,
-        params: serde_json::Value,
-    ) -> Result<serde_json::Value, Error> {
-        // For this test it's fine to pretend that slots and blocks are the same thing.
-        let slot = (Instant::now().duration_since(self.initial_time).as_millis()
-            / DEFAULT_MS_PER_SLOT as u128) as u64;
-        if let RpcRequest::GetLatestBlockhash = request {
-            Ok(serde_json::to_value(Response {
-                context: RpcResponseContext {
-                    slot,
-                    api_version: None,
-                },
-                value: RpcBlockhash {
-                    blockhash: Hash::default().to_string(),
-                    last_valid_block_height: slot + 150,
-                },
-            })?)
-        } else if let RpcRequest::GetEpochInfo = request {
-            Ok(serde_json::to_value(EpochInfo {
-                epoch: 0,
-                slot_index: slot,
-                slots_in_epoch: 256,
-                absolute_slot: slot,
-                block_height: slot,
-                transaction_count: Some(123),
-            })?)
-        } else {
-            self.sender.send(request, params).await
-        }
-    }
+#[tokio::test]
+async fn pre_derived_blob_address_matches_upload() {
+    let payer = Arc::new(Keypair::new());
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        MockBlockSender {
+            sender: MockSender::new("succeeds".to_string()),
+            initial_time: Instant::now(),
+        },
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
 
-    fn get_transport_stats(&self) -> RpcTransportStats {
-        self.sender.get_transport_stats()
-    }
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
 
-    fn url(&self) -> String {
-        self.sender.url()
-    }
+    let data = vec![1u8; 100];
+    let encoded = data_anchor_client.encode_and_compress(&data).await.unwrap();
+    let address = data_anchor_client.derive_blob_address(encoded.len(), "test");
+    // Deriving again never hands out the same timestamp.
+    assert_ne!(
+        data_anchor_client.derive_blob_address(encoded.len(), "test"),
+        address
+    );
+
+    let result = data_anchor_client
+        .upload_blob(
+            &data,
+            FeeStrategy::default(),
+            "test",
+            Some(Duration::from_secs(20)),
+            UploadOptions::builder().address(address).build(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.blob, address.0);
+
+    // An address derived for data of a different length is rejected before anything is sent.
+    let wrong = data_anchor_client.derive_blob_address(encoded.len() + 1, "test");
+    let err = data_anchor_client
+        .upload_blob(
+            &data,
+            FeeStrategy::default(),
+            "test",
+            Some(Duration::from_secs(20)),
+            UploadOptions::builder().address(wrong).build(),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            DataAnchorClientError::ChainErrors(ChainError::BlobAddressMismatch(expected, _))
+                if expected == wrong.0
+        ),
+        "Expected BlobAddressMismatch, got {err:?}"
+    );
+
+    cancellation_token.cancel();
 }
 
-struct UnreliableSender(MockBlockSender);
+#[tokio::test]
+async fn verify_before_finalize_catches_dropped_chunk() {
+    // Random data doesn't compress, so the blob is uploaded in multiple chunks.
+    let mut data = vec![0u8; 5 * CHUNK_SIZE as usize];
+    rand::thread_rng().fill(&mut data[..]);
+    let encoded = encode_and_compress_async(&Default::default(), &Default::default(), &data)
+        .await
+        .unwrap();
+    let chunks = encoded.chunks(CHUNK_SIZE as usize).collect_vec();
 
-#[async_trait]
-impl RpcSender for UnreliableSender {
-    async fn send(
-        &self,
-        request: RpcRequest,
-        params: serde_json::Value,
-    ) -> Result<serde_json::Value, Error> {
-        let failure_rate = match &request {
-            // Always let airdrops, balance checks and slot queries through, since those
-            // are used in the test setup itself.
-            RpcRequest::RequestAirdrop | RpcRequest::GetBalance | RpcRequest::GetSlot => 0.0,
-            // This needs special treatment since we want to simulate some of the transactions failing,
-            // not the entire request.
-            RpcRequest::GetSignatureStatuses => {
-                // Small chance to fail the signature request itself.
-                if rand::thread_rng().gen_bool(0.1) {
-                    return Err(Error {
-                        request: None,
-                        kind: ErrorKind::Custom("failed".to_string()),
-                    });
-                }
-                let successful = self.0.send(request, params).await.unwrap();
-                let mut statuses: Response<Vec<Option<TransactionStatus>>> =
-                    serde_json::from_value(successful).unwrap();
-                let mut rng = rand::thread_rng();
-                for status in &mut statuses.value {
-                    // Even if 50% of transactions fail, the client should still work.
-                    // (even higher works too, but the test takes an awfully long time)
-                    if rng.gen_bool(0.5) {
-                        *status = None;
-                    }
-                }
-                return Ok(serde_json::to_value(statuses).unwrap());
+    // The last chunk is only inserted along with the finalize, so it's never part of the digest.
+    for dropped in [None, Some(1)] {
+        let payer = Arc::new(Keypair::new());
+        let blober = find_blober_address(data_anchor_blober::id(), payer.pubkey(), "test");
+        let timestamp = get_unique_timestamp();
+        let blob = find_blob_address(
+            data_anchor_blober::id(),
+            payer.pubkey(),
+            blober,
+            timestamp,
+            encoded.len(),
+        );
+
+        let mut blob_state = Blob::new(10, timestamp, encoded.len() as u32, 255);
+        for (idx, chunk) in chunks[..chunks.len() - 1].iter().enumerate() {
+            if Some(idx) != dropped {
+                blob_state.insert(10, idx as u16, chunk);
             }
-            // Any other request can fail rarely.
-            _ => 0.1,
-        };
-        if rand::thread_rng().gen_bool(failure_rate) {
-            return Err(Error {
-                request: None,
-                kind: ErrorKind::Custom("failed".to_string()),
-            });
         }
-        self.0.send(request, params).await
-    }
+        let rpc_client = Arc::new(RpcClient::new_sender(
+            ScriptedSender::with_accounts(HashMap::from([(
+                blob,
+                mock_account(
+                    data_anchor_blober::id(),
+                    &[
+                        Blob::DISCRIMINATOR,
+                        blob_state.try_to_vec().unwrap().as_ref(),
+                    ]
+                    .concat(),
+                    false,
+                ),
+            )])),
+            RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+        ));
 
-    fn get_transport_stats(&self) -> RpcTransportStats {
-        self.0.get_transport_stats()
-    }
+        let cancellation_token = CancellationToken::new();
+        let batch_client = NitroSender::new(
+            rpc_client.clone(),
+            cancellation_token.clone(),
+            vec![payer.clone()],
+        )
+        .await
+        .unwrap();
+        let data_anchor_client = DataAnchorClient::builder()
+            .payer(payer)
+            .program_id(data_anchor_blober::id())
+            .rpc_client(rpc_client)
+            .nitro_sender(batch_client)
+            .build();
 
-    fn url(&self) -> String {
-        self.0.url()
+        let result = data_anchor_client
+            .upload_blob(
+                &data,
+                FeeStrategy::default(),
+                "test",
+                Some(Duration::from_secs(20)),
+                UploadOptions::builder()
+                    .address((blob, timestamp))
+                    .verify_before_finalize(true)
+                    .build(),
+            )
+            .await;
+        match dropped {
+            None => assert!(result.unwrap().finalize_slot.is_some()),
+            Some(_) => {
+                let err = result.unwrap_err();
+                assert!(
+                    matches!(
+                        err,
+                        DataAnchorClientError::ChainErrors(ChainError::BlobDigestMismatch(b))
+                            if b == blob
+                    ),
+                    "Expected BlobDigestMismatch, got {err:?}"
+                );
+            }
+        }
+
+        cancellation_token.cancel();
     }
 }
 
-#[test]
-fn timestamps_are_unique_under_contention() {
-    let mut threads = Vec::new();
-    for _ in 0..100 {
-        threads.push(std::thread::spawn(|| {
-            let mut timestamps = Vec::new();
-            for _ in 0..1000 {
-                timestamps.push(get_unique_timestamp());
-            }
-            timestamps
-        }));
-    }
+#[tokio::test]
+async fn deferred_upload_skips_finalize() {
+    let payer = Arc::new(Keypair::new());
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        MockBlockSender {
+            sender: MockSender::new("succeeds".to_string()),
+            initial_time: Instant::now(),
+        },
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
 
-    let timestamps = threads
-        .into_iter()
-        .flat_map(|t| t.join().unwrap())
-        .collect::<Vec<_>>();
-    assert_eq!(timestamps.len(), timestamps.iter().unique().count());
-    let min = timestamps.iter().min().unwrap();
-    let max = timestamps.iter().max().unwrap();
-    let count = timestamps.len();
-    let current_time = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    dbg!(min, max, count, current_time);
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    // Random data doesn't compress, so the blob is uploaded in multiple chunks.
+    let mut data = vec![0u8; 5 * CHUNK_SIZE as usize];
+    rand::thread_rng().fill(&mut data[..]);
+
+    let result = data_anchor_client
+        .upload_blob(
+            &data,
+            FeeStrategy::default(),
+            "test",
+            Some(Duration::from_secs(20)),
+            UploadOptions::builder().defer_finalize(true).build(),
+        )
+        .await
+        .unwrap();
+
+    // Every chunk is inserted, but nothing finalizes the blob.
+    assert!(
+        !result
+            .transactions
+            .iter()
+            .any(|tx| tx.data.finalizes_blob())
+    );
+    assert_eq!(result.finalize_slot, None);
+    let inserted = result
+        .transactions
+        .iter()
+        .filter(|tx| matches!(tx.data, TransactionType::InsertChunk(_)))
+        .count();
+    assert!(
+        inserted >= 5,
+        "expected every chunk to be inserted, got {inserted}"
+    );
+
+    let finalized = data_anchor_client
+        .finalize_blobs(
+            &[result.blob],
+            "test",
+            FeeStrategy::default(),
+            Some(Duration::from_secs(20)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(finalized.len(), 1);
+    assert!(matches!(finalized[0].data, TransactionType::FinalizeBlobs));
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn finalize_blobs_batches_blobs_into_transactions() {
+    let payer = Arc::new(Keypair::new());
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        MockBlockSender {
+            sender: MockSender::new("succeeds".to_string()),
+            initial_time: Instant::now(),
+        },
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer.clone())
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    let blobs = (0..FinalizeBlobs::MAX_BLOBS_PER_TX + 2)
+        .map(|_| Pubkey::new_unique())
+        .collect_vec();
+    let finalized = data_anchor_client
+        .finalize_blobs(
+            &blobs,
+            "test",
+            FeeStrategy::default(),
+            Some(Duration::from_secs(20)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(finalized.len(), 2);
+    assert!(
+        finalized
+            .iter()
+            .all(|tx| matches!(tx.data, TransactionType::FinalizeBlobs))
+    );
+
+    // A single instruction finalizes the whole batch, with the blobs as its remaining accounts.
+    let blober = find_blober_address(data_anchor_blober::id(), payer.pubkey(), "test");
+    let batch = blobs[..FinalizeBlobs::MAX_BLOBS_PER_TX].to_vec();
+    let msg = FinalizeBlobs::build_message(data_anchor_client.message_arguments(
+        blober,
+        Fee::ZERO,
+        FinalizeBlobs::new(batch.clone()),
+    ))
+    .await;
+    let finalize = msg.instructions.last().unwrap();
+    assert_eq!(&finalize.data[..8], FinalizeBlobsInstruction::DISCRIMINATOR);
+    let accounts = finalize
+        .accounts
+        .iter()
+        .map(|idx| msg.account_keys[*idx as usize])
+        .collect_vec();
+    assert_eq!(accounts, [vec![blober, payer.pubkey()], batch].concat());
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn cancelled_upload_discards_blob() {
+    let payer = Arc::new(Keypair::new());
+    let cancel = CancellationToken::new();
+    // Cancel once the declare and the first chunk have been sent.
+    let sent = AtomicUsize::new(0);
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        ScriptedSender::new({
+            let cancel = cancel.clone();
+            move |request, _| {
+                if matches!(request, RpcRequest::SendTransaction)
+                    && sent.fetch_add(1, Ordering::SeqCst) + 1 >= 2
+                {
+                    cancel.cancel();
+                }
+                None
+            }
+        }),
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    // Random data doesn't compress, so the blob is uploaded in multiple chunks.
+    let mut data = vec![0u8; 5 * CHUNK_SIZE as usize];
+    rand::thread_rng().fill(&mut data[..]);
+
+    let result = data_anchor_client
+        .upload_blob(
+            &data,
+            FeeStrategy::default(),
+            "test",
+            Some(Duration::from_secs(20)),
+            UploadOptions::builder().cancel(cancel).build(),
+        )
+        .await
+        .unwrap();
+
+    // The partial blob is discarded instead of finalized.
+    assert!(
+        result
+            .transactions
+            .iter()
+            .any(|tx| matches!(tx.data, TransactionType::DiscardBlob))
+    );
+    assert!(
+        !result
+            .transactions
+            .iter()
+            .any(|tx| tx.data.finalizes_blob())
+    );
+    assert_eq!(result.finalize_slot, None);
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn upload_cancelled_before_start_sends_nothing() {
+    let payer = Arc::new(Keypair::new());
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        MockBlockSender {
+            sender: MockSender::new("succeeds".to_string()),
+            initial_time: Instant::now(),
+        },
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    let err = data_anchor_client
+        .upload_blob(
+            &vec![1u8; 10],
+            FeeStrategy::default(),
+            "test",
+            Some(Duration::from_secs(20)),
+            UploadOptions::builder().cancel(cancel).build(),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            DataAnchorClientError::ChainErrors(ChainError::UploadCancelled)
+        ),
+        "Expected UploadCancelled, got {err:?}"
+    );
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn replayed_upload_matches_recording() {
+    let payer = Arc::new(Keypair::new());
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        MockBlockSender {
+            sender: MockSender::new("succeeds".to_string()),
+            initial_time: Instant::now(),
+        },
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    // Random data doesn't compress, so the blob is uploaded in multiple chunks.
+    let mut data = vec![0u8; 5 * CHUNK_SIZE as usize];
+    rand::thread_rng().fill(&mut data[..]);
+
+    let result = data_anchor_client
+        .upload_blob(
+            &data,
+            FeeStrategy::default(),
+            "test",
+            None,
+            UploadOptions::builder().record(true).build(),
+        )
+        .await
+        .unwrap();
+    // Recording doesn't send anything.
+    assert!(result.transactions.is_empty());
+    let recorded = result.recorded.unwrap();
+    let path = std::env::temp_dir().join(format!("upload-{}.json", recorded.blob));
+    recorded.save(&path).unwrap();
+    let loaded = RecordedUpload::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, recorded);
+
+    let result = data_anchor_client
+        .replay_upload(&loaded, Some(Duration::from_secs(20)))
+        .await
+        .unwrap();
+
+    // Every recorded message is sent exactly once, and nothing else is.
+    assert_eq!(result.blob, recorded.blob);
+    let mut sent = result
+        .transactions
+        .iter()
+        .map(|tx| tx.data)
+        .collect::<Vec<_>>();
+    let mut expected = recorded
+        .messages
+        .iter()
+        .map(|(tx_type, _)| *tx_type)
+        .collect::<Vec<_>>();
+    sent.sort_by_key(ToString::to_string);
+    expected.sort_by_key(ToString::to_string);
+    assert_eq!(sent, expected);
+    assert!(result.finalize_slot.is_some());
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn chunk_crc_is_opt_in() {
+    // The largest serialized transaction that fits in a packet.
+    const PACKET_DATA_SIZE: usize = 1232;
+
+    let payer = Arc::new(Keypair::new());
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        MockBlockSender {
+            sender: MockSender::new("succeeds".to_string()),
+            initial_time: Instant::now(),
+        },
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        CancellationToken::new(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+
+    // Random data doesn't compress, so the blob is uploaded in multiple full chunks.
+    let mut data = vec![0u8; 5 * CHUNK_SIZE as usize];
+    rand::thread_rng().fill(&mut data[..]);
+
+    for chunk_crc in [false, true] {
+        let data_anchor_client = DataAnchorClient::builder()
+            .payer(payer.clone())
+            .program_id(data_anchor_blober::id())
+            .rpc_client(rpc_client.clone())
+            .nitro_sender(batch_client.clone())
+            .chunk_crc(chunk_crc)
+            .build();
+
+        let recorded = data_anchor_client
+            .upload_blob(
+                &data,
+                FeeStrategy::default(),
+                "test",
+                None,
+                UploadOptions::builder().record(true).build(),
+            )
+            .await
+            .unwrap()
+            .recorded
+            .unwrap();
+
+        let expected = if chunk_crc {
+            InsertChunkWithCrc::DISCRIMINATOR
+        } else {
+            InsertChunk::DISCRIMINATOR
+        };
+        let inserts = recorded
+            .messages
+            .iter()
+            .filter(|(tx_type, _)| matches!(tx_type, TransactionType::InsertChunk(_)))
+            .map(|(_, message)| message)
+            .collect::<Vec<_>>();
+        assert!(!inserts.is_empty());
+        for message in inserts {
+            let insert = message.instructions.last().unwrap();
+            assert_eq!(&insert.data[..8], expected);
+
+            let signatures = message.header.num_required_signatures as usize;
+            assert!(message.serialize().len() + 1 + 64 * signatures <= PACKET_DATA_SIZE);
+        }
+    }
+}
+
+#[tokio::test]
+async fn discard_multiple_blobs() {
+    let payer = Arc::new(Keypair::new());
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        MockBlockSender {
+            sender: MockSender::new("succeeds".to_string()),
+            initial_time: Instant::now(),
+        },
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    let blobs = (0..3).map(|_| Pubkey::new_unique()).collect_vec();
+
+    let results = data_anchor_client
+        .discard_blobs(
+            &blobs,
+            "test",
+            FeeStrategy::default(),
+            Some(Duration::from_secs(20)),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        results.iter().map(|(blob, _)| *blob).collect_vec(),
+        blobs,
+        "every blob should have an outcome, in input order"
+    );
+    assert!(results.iter().all(|(_, outcome)| outcome.is_discarded()));
+    // All three fit in a single transaction.
+    assert!(results.iter().map(|(_, outcome)| outcome).all_equal());
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn close_blober_not_owned_by_payer() {
+    let payer = Arc::new(Keypair::new());
+    let blober = Pubkey::new_unique();
+    let blober_state = Blober {
+        hash: Default::default(),
+        slot: 1,
+        caller: Pubkey::new_unique(),
+        namespace: "test".to_string(),
+    };
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        ScriptedSender::with_accounts(HashMap::from([(
+            blober,
+            mock_account(
+                data_anchor_blober::id(),
+                &[
+                    Blober::DISCRIMINATOR,
+                    blober_state.try_to_vec().unwrap().as_ref(),
+                ]
+                .concat(),
+                false,
+            ),
+        )])),
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    let err = data_anchor_client
+        .close_blober(FeeStrategy::default(), blober.into(), None)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            DataAnchorClientError::ChainErrors(ChainError::NotBloberOwner { blober: b, owner })
+                if b == blober && owner == blober_state.caller
+        ),
+        "Expected NotBloberOwner, got {err:?}"
+    );
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn program_not_deployed() {
+    let payer = Arc::new(Keypair::new());
+    let program_id = Pubkey::new_unique();
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        ScriptedSender::with_accounts(HashMap::from([(
+            program_id,
+            mock_account(bpf_loader_upgradeable::ID, &[0; 36], false),
+        )])),
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(program_id)
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    let err = data_anchor_client
+        .check_program_deployed()
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            DataAnchorClientError::ChainErrors(ChainError::ProgramNotDeployed(id)) if id == program_id
+        ),
+        "Expected ProgramNotDeployed, got {err:?}"
+    );
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn get_blob_account() {
+    let payer = Arc::new(Keypair::new());
+    let blob = Pubkey::new_unique();
+    let timestamp = get_unique_timestamp();
+    let blob_size = 3 * CHUNK_SIZE as u32 + 1;
+    let blob_state = Blob::new(10, timestamp, blob_size, 255);
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        ScriptedSender::with_accounts(HashMap::from([(
+            blob,
+            mock_account(
+                data_anchor_blober::id(),
+                &[
+                    Blob::DISCRIMINATOR,
+                    blob_state.try_to_vec().unwrap().as_ref(),
+                ]
+                .concat(),
+                false,
+            ),
+        )])),
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    let fetched = data_anchor_client
+        .get_blob_account(blob)
+        .await
+        .unwrap()
+        .expect("blob account should exist");
+    assert_eq!(fetched.size(), blob_size);
+    assert_eq!(fetched.timestamp(), timestamp);
+
+    let missing = data_anchor_client
+        .get_blob_account(Pubkey::new_unique())
+        .await
+        .unwrap();
+    assert!(missing.is_none());
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn get_blob_info() {
+    let payer = Arc::new(Keypair::new());
+    let blob = Pubkey::new_unique();
+    let timestamp = get_unique_timestamp();
+    let data = b"hello world";
+    let mut blob_state = Blob::new(10, timestamp, data.len() as u32, 255);
+    blob_state.insert(12, 0, data);
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        ScriptedSender::with_accounts(HashMap::from([(
+            blob,
+            mock_account(
+                data_anchor_blober::id(),
+                &[
+                    Blob::DISCRIMINATOR,
+                    blob_state.try_to_vec().unwrap().as_ref(),
+                ]
+                .concat(),
+                false,
+            ),
+        )])),
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    let info = data_anchor_client
+        .get_blob_info(blob)
+        .await
+        .unwrap()
+        .expect("blob account should exist");
+    assert_eq!(
+        info,
+        BlobInfo {
+            size: data.len() as u32,
+            timestamp,
+            created_at: 10,
+            last_updated_at: 12,
+            is_complete: true,
+            digest: compute_blob_digest(&[(0, data)]),
+        }
+    );
+
+    let missing = data_anchor_client
+        .get_blob_info(Pubkey::new_unique())
+        .await
+        .unwrap();
+    assert!(missing.is_none());
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn blob_status_distinguishes_complete_from_finalized() {
+    let payer = Arc::new(Keypair::new());
+    let timestamp = get_unique_timestamp();
+    let blob_size = 2 * CHUNK_SIZE as u32;
+    let chunk = vec![1; CHUNK_SIZE as usize];
+
+    let incomplete_blob = Pubkey::new_unique();
+    let mut incomplete_state = Blob::new(10, timestamp, blob_size, 255);
+    incomplete_state.insert(10, 0, &chunk);
+
+    let complete_blob = Pubkey::new_unique();
+    let mut complete_state = Blob::new(10, timestamp, blob_size, 255);
+    complete_state.insert(10, 0, &chunk);
+    complete_state.insert(10, 1, &chunk);
+
+    // Finalizing closes the blob account.
+    let finalized_blob = Pubkey::new_unique();
+
+    let blob_account = |state: &Blob| {
+        mock_account(
+            data_anchor_blober::id(),
+            &[Blob::DISCRIMINATOR, state.try_to_vec().unwrap().as_ref()].concat(),
+            false,
+        )
+    };
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        ScriptedSender::with_accounts(HashMap::from([
+            (incomplete_blob, blob_account(&incomplete_state)),
+            (complete_blob, blob_account(&complete_state)),
+        ])),
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    assert_eq!(
+        data_anchor_client
+            .blob_status(incomplete_blob)
+            .await
+            .unwrap(),
+        BlobStatus {
+            complete: false,
+            finalized: false,
+        }
+    );
+    assert_eq!(
+        data_anchor_client.blob_status(complete_blob).await.unwrap(),
+        BlobStatus {
+            complete: true,
+            finalized: false,
+        }
+    );
+    assert_eq!(
+        data_anchor_client
+            .blob_status(finalized_blob)
+            .await
+            .unwrap(),
+        BlobStatus {
+            complete: true,
+            finalized: true,
+        }
+    );
+
+    cancellation_token.cancel();
+}
+
+/// Builds an RPC representation of an account with the given owner and data.
+fn mock_account(owner: Pubkey, data: &[u8], executable: bool) -> UiAccount {
+    UiAccount {
+        lamports: 1_000_000,
+        data: UiAccountData::Binary(BASE64_STANDARD.encode(data), UiAccountEncoding::Base64),
+        owner: owner.to_string(),
+        executable,
+        rent_epoch: 0,
+        space: Some(data.len() as u64),
+    }
+}
+
+/// The requests received by a [`ScriptedSender`], in order, along with their parameters.
+type RequestLog = Arc<Mutex<Vec<(RpcRequest, serde_json::Value)>>>;
+
+/// Answers a request in place of the [`MockBlockSender`], or returns `None` to pass it through.
+type RequestHandler =
+    Box<dyn Fn(RpcRequest, &serde_json::Value) -> Option<serde_json::Value> + Send + Sync>;
+
+/// Logs every request and lets a handler answer it, the requests the handler doesn't answer are
+/// passed through to the [`MockBlockSender`].
+struct ScriptedSender {
+    sender: MockBlockSender,
+    handler: RequestHandler,
+    log: RequestLog,
+    url: Option<String>,
+}
+
+impl ScriptedSender {
+    fn new(
+        handler: impl Fn(RpcRequest, &serde_json::Value) -> Option<serde_json::Value>
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        Self {
+            sender: MockBlockSender {
+                sender: MockSender::new("succeeds".to_string()),
+                initial_time: Instant::now(),
+            },
+            handler: Box::new(handler),
+            log: RequestLog::default(),
+            url: None,
+        }
+    }
+
+    /// Reports `url` as the RPC URL, so the client doesn't skip the checks it skips for mocks.
+    fn with_url(self, url: &str) -> Self {
+        Self {
+            url: Some(url.to_owned()),
+            ..self
+        }
+    }
+
+    /// Serves the given accounts for `getAccountInfo` requests and reports any other account as
+    /// missing.
+    fn with_accounts(accounts: HashMap<Pubkey, UiAccount>) -> Self {
+        Self::new(move |request, params| {
+            let RpcRequest::GetAccountInfo = request else {
+                return None;
+            };
+            let pubkey: Pubkey = params[0]
+                .as_str()
+                .and_then(|pubkey| pubkey.parse().ok())
+                .expect("getAccountInfo to be called with a pubkey");
+            Some(
+                serde_json::to_value(Response {
+                    context: RpcResponseContext {
+                        slot: 1,
+                        api_version: None,
+                    },
+                    value: accounts.get(&pubkey),
+                })
+                .unwrap(),
+            )
+        })
+    }
+
+    /// Returns the request log, which keeps filling up after the sender is moved into a client.
+    fn log(&self) -> RequestLog {
+        self.log.clone()
+    }
+}
+
+#[async_trait]
+impl RpcSender for ScriptedSender {
+    async fn send(
+        &self,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, Error> {
+        self.log.lock().unwrap().push((request, params.clone()));
+        match (self.handler)(request, &params) {
+            Some(response) => Ok(response),
+            None => self.sender.send(request, params).await,
+        }
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.sender.get_transport_stats()
+    }
+
+    fn url(&self) -> String {
+        self.url.clone().unwrap_or_else(|| self.sender.url())
+    }
+}
+
+#[tokio::test]
+async fn checkpoint_signer_matches_program_derivation() {
+    let payer = Arc::new(Keypair::new());
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        MockBlockSender {
+            sender: MockSender::new("succeeds".to_string()),
+            initial_time: Instant::now(),
+        },
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        CancellationToken::new(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer.clone())
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    let blober = find_blober_address(data_anchor_blober::id(), payer.pubkey(), "test");
+    let (signer, bump) = data_anchor_client.checkpoint_signer(blober);
+
+    assert_eq!(
+        signer,
+        find_checkpoint_signer_address(data_anchor_blober::id(), blober)
+    );
+    assert_eq!(
+        Pubkey::create_program_address(
+            &[
+                SEED,
+                CHECKPOINT_SEED,
+                CHECKPOINT_PDA_SIGNER_SEED,
+                blober.as_ref(),
+                &[bump],
+            ],
+            &data_anchor_blober::id(),
+        )
+        .unwrap(),
+        signer
+    );
+}
+
+#[tokio::test]
+async fn indexer_budget_is_shared_across_calls() {
+    let payer = Arc::new(Keypair::new());
+    let rpc_client = Arc::new(RpcClient::new_mock("succeeds".to_string()));
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        CancellationToken::new(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    let budget = IndexerBudget::new(&data_anchor_client, Duration::from_millis(500));
+    let call = || async {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        Ok::<_, DataAnchorClientError>(())
+    };
+
+    // Two calls fit in the budget, the third runs out of time and the fourth doesn't start.
+    budget.run(call()).await.unwrap();
+    budget.run(call()).await.unwrap();
+    for _ in 0..2 {
+        let err = budget.run(call()).await.unwrap_err();
+        assert!(
+            matches!(
+                err,
+                DataAnchorClientError::Indexer(IndexerError::BudgetExhausted(_))
+            ),
+            "Expected BudgetExhausted, got {err:?}"
+        );
+    }
+    assert!(budget.remaining().is_zero());
+}
+
+/// Serves `module` as the indexer on a local port and builds a client pointing at it.
+async fn client_with_indexer<Context: Send + Sync + 'static>(
+    module: RpcModule<Context>,
+) -> (DataAnchorClient, ServerHandle) {
+    let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", server.local_addr().unwrap());
+    let handle = server.start(module);
+
+    let payer = Arc::new(Keypair::new());
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        MockBlockSender {
+            sender: MockSender::new("succeeds".to_string()),
+            initial_time: Instant::now(),
+        },
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        CancellationToken::new(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .indexer_client(Arc::new(HttpClientBuilder::new().build(url).unwrap()))
+        .build();
+
+    (data_anchor_client, handle)
+}
+
+#[tokio::test]
+async fn latest_indexed_slot() {
+    // Serve only the queried method, knowing a single blober.
+    let indexed = Pubkey::new_unique();
+    let mut module = RpcModule::new(indexed);
+    module
+        .register_method("get_latest_indexed_slot", |params, indexed, _| {
+            params
+                .one::<PubkeyFromStr>()
+                .map(|blober| (blober.0 == *indexed).then_some(42u64))
+        })
+        .unwrap();
+    let (data_anchor_client, handle) = client_with_indexer(module).await;
+
+    assert_eq!(
+        data_anchor_client
+            .get_latest_indexed_slot(Pubkey::new_unique().into())
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        data_anchor_client
+            .get_latest_indexed_slot(indexed.into())
+            .await
+            .unwrap(),
+        Some(42)
+    );
+
+    handle.stop().unwrap();
+}
+
+#[tokio::test]
+async fn dedup_skips_blobs_the_indexer_already_has() {
+    // The indexer knows a single blob, by the content hash it was uploaded with.
+    let existing = Pubkey::new_unique();
+    let known = Arc::new(Mutex::new(None::<[u8; 32]>));
+    let mut module = RpcModule::new(known.clone());
+    module
+        .register_method("find_blob_by_content_hash", move |params, known, _| {
+            let (_, content_hash): (PubkeyFromStr, [u8; 32]) = params.parse()?;
+            let found = *known.lock().unwrap() == Some(content_hash);
+            Ok::<_, ErrorObjectOwned>(found.then_some(PubkeyFromStr(existing)))
+        })
+        .unwrap();
+    let (data_anchor_client, handle) = client_with_indexer(module).await;
+
+    let uploaded = vec![1u8; CHUNK_SIZE as usize];
+    let encoded = data_anchor_client
+        .encode_and_compress(&uploaded)
+        .await
+        .unwrap();
+    *known.lock().unwrap() = Some(hashv(&[&encoded]).to_bytes());
+
+    let hit = data_anchor_client
+        .upload_blob(
+            &uploaded,
+            FeeStrategy::default(),
+            "test",
+            Some(Duration::from_secs(20)),
+            UploadOptions::builder().dedup(true).build(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(hit.blob, existing);
+    assert!(hit.transactions.is_empty());
+    assert_eq!(hit.finalize_slot, None);
+
+    let miss = data_anchor_client
+        .upload_blob(
+            &vec![2u8; CHUNK_SIZE as usize],
+            FeeStrategy::default(),
+            "test",
+            Some(Duration::from_secs(20)),
+            UploadOptions::builder().dedup(true).build(),
+        )
+        .await
+        .unwrap();
+    assert_ne!(miss.blob, existing);
+    assert!(miss.finalize_slot.is_some());
+
+    // A pre-derived address can't be kept if the upload resolves to an existing blob.
+    let address = data_anchor_client.derive_blob_address(encoded.len(), "test");
+    let err = data_anchor_client
+        .upload_blob(
+            &uploaded,
+            FeeStrategy::default(),
+            "test",
+            Some(Duration::from_secs(20)),
+            UploadOptions::builder()
+                .address(address)
+                .dedup(true)
+                .build(),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        DataAnchorClientError::ChainErrors(ChainError::DedupWithAddress)
+    ));
+
+    handle.stop().unwrap();
+}
+
+#[tokio::test]
+async fn subscribed_blobs_are_yielded_in_order_once() {
+    // The first poll sees slot 3 partially indexed, the second sees the rest of it and slot 5.
+    let polls = Arc::new(AtomicUsize::new(0));
+    let mut module = RpcModule::new(polls.clone());
+    module
+        .register_method("get_latest_indexed_slot", |_, polls, _| {
+            let latest = if polls.fetch_add(1, Ordering::SeqCst) == 0 {
+                3u64
+            } else {
+                5
+            };
+            Ok::<_, ErrorObjectOwned>(Some(latest))
+        })
+        .unwrap();
+    module
+        .register_method("get_blobs_by_slot_range", |params, polls, _| {
+            let request: BlobsBySlotRange = params.one()?;
+            let ledger = if polls.load(Ordering::SeqCst) == 1 {
+                BTreeMap::from([(1u64, vec![vec![1u8]]), (3, vec![vec![3]])])
+            } else {
+                BTreeMap::from([
+                    (1u64, vec![vec![1u8]]),
+                    (3, vec![vec![3], vec![4]]),
+                    (5, vec![vec![5]]),
+                ])
+            };
+            Ok::<_, ErrorObjectOwned>(
+                ledger
+                    .range(request.start_slot..=request.end_slot)
+                    .map(|(slot, blobs)| (*slot, blobs.clone()))
+                    .collect::<BTreeMap<_, _>>(),
+            )
+        })
+        .unwrap();
+    let (data_anchor_client, handle) = client_with_indexer(module).await;
+
+    let blobs = tokio::time::timeout(
+        Duration::from_secs(5),
+        data_anchor_client
+            .subscribe_blobs(Pubkey::new_unique().into(), 1, Duration::from_millis(10))
+            .take(4)
+            .collect::<Vec<_>>(),
+    )
+    .await
+    .unwrap()
+    .into_iter()
+    .collect::<DataAnchorClientResult<Vec<_>>>()
+    .unwrap();
+
+    assert_eq!(
+        blobs,
+        vec![(1, vec![1]), (3, vec![3]), (3, vec![4]), (5, vec![5])]
+    );
+    assert_eq!(polls.load(Ordering::SeqCst), 2);
+
+    handle.stop().unwrap();
+}
+
+#[tokio::test]
+async fn blobs_by_payer_pages_through_indexer() {
+    // The indexer knows five blobs of a single payer and pages them with the real cursor scheme.
+    let payer = Pubkey::new_unique();
+    let blobs_for = |requested: Pubkey, payer: &Pubkey| -> Vec<Vec<u8>> {
+        if requested == *payer {
+            (0..5).map(|blob| vec![blob]).collect()
+        } else {
+            Vec::new()
+        }
+    };
+    let mut module = RpcModule::new(payer);
+    module
+        .register_method("get_blobs_by_payer", move |params, payer, _| {
+            let requested: PubkeyFromStr = params.sequence().next()?;
+            Ok::<_, ErrorObjectOwned>(blobs_for(requested.0, payer))
+        })
+        .unwrap();
+    module
+        .register_method("get_blobs_by_payer_paginated", move |params, payer, _| {
+            let mut params = params.sequence();
+            let requested: PubkeyFromStr = params.next()?;
+            let _network_name: String = params.next()?;
+            let _time_range: Option<TimeRange> = params.next()?;
+            let page: PageRequest = params.next()?;
+            page.paginate(blobs_for(requested.0, payer))
+                .map_err(|e| ErrorObjectOwned::owned(-32602, e.to_string(), None::<()>))
+        })
+        .unwrap();
+    let (data_anchor_client, handle) = client_with_indexer(module).await;
+
+    let mut request = Some(PageRequest::first(2));
+    let mut pages = Vec::new();
+    while let Some(current) = request {
+        let page = data_anchor_client
+            .get_blobs_by_payer_paginated::<Vec<u8>>(
+                payer,
+                "network".to_owned(),
+                None,
+                current.clone(),
+            )
+            .await
+            .unwrap();
+        request = current.next(&page);
+        pages.push(page.items);
+    }
+    assert_eq!(
+        pages,
+        vec![
+            vec![vec![0], vec![1]],
+            vec![vec![2], vec![3]],
+            vec![vec![4]]
+        ]
+    );
+    assert_eq!(
+        pages.concat(),
+        data_anchor_client
+            .get_blobs_by_payer::<Vec<u8>>(payer, "network".to_owned(), None)
+            .await
+            .unwrap()
+    );
+
+    // A limit of 0 is clamped to a single blob rather than rejected.
+    let page = data_anchor_client
+        .get_blobs_by_payer_paginated::<Vec<u8>>(
+            payer,
+            "network".to_owned(),
+            None,
+            PageRequest::first(0),
+        )
+        .await
+        .unwrap();
+    assert_eq!(page.items, vec![vec![0]]);
+    assert!(page.next_cursor.is_some());
+
+    let page = data_anchor_client
+        .get_blobs_by_payer_paginated::<Vec<u8>>(
+            Pubkey::new_unique(),
+            "network".to_owned(),
+            None,
+            PageRequest::first(2),
+        )
+        .await
+        .unwrap();
+    assert!(page.items.is_empty());
+    assert_eq!(page.next_cursor, None);
+
+    handle.stop().unwrap();
+}
+
+#[tokio::test]
+async fn proofs_for_slots_preserve_order() {
+    // Only even slots have a proof.
+    let mut module = RpcModule::new(());
+    module
+        .register_method("get_proofs_for_slots", |params, _, _| {
+            let mut params = params.sequence();
+            let blober: PubkeyFromStr = params.next()?;
+            let slots: Vec<u64> = params.next()?;
+            Ok::<_, ErrorObjectOwned>(
+                slots
+                    .into_iter()
+                    .map(|slot| {
+                        let proof = (slot % 2 == 0).then(|| {
+                            CompoundInclusionProof::new(
+                                Vec::new(),
+                                blober.0,
+                                BloberAccountStateProof::new(initial_hash(), slot, BTreeMap::new()),
+                            )
+                        });
+                        (slot, proof)
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .unwrap();
+    let (data_anchor_client, handle) = client_with_indexer(module).await;
+
+    let slots = vec![8, 3, 2, 5];
+    let proofs = data_anchor_client
+        .get_proofs_for_slots(Pubkey::new_unique().into(), slots.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        proofs.iter().map(|(slot, _)| *slot).collect::<Vec<_>>(),
+        slots
+    );
+    assert_eq!(
+        proofs
+            .iter()
+            .map(|(_, proof)| proof.is_some())
+            .collect::<Vec<_>>(),
+        vec![true, false, true, false]
+    );
+
+    let err = data_anchor_client
+        .get_proofs_for_slots(
+            Pubkey::new_unique().into(),
+            (0..=MAX_PROOF_SLOTS_PER_CALL as u64).collect(),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            DataAnchorClientError::Indexer(IndexerError::TooManySlots(_))
+        ),
+        "Expected TooManySlots, got {err:?}"
+    );
+
+    handle.stop().unwrap();
+}
+
+#[tokio::test]
+async fn inverted_slot_range_is_rejected_before_querying() {
+    let payer = Arc::new(Keypair::new());
+    let rpc_client = Arc::new(RpcClient::new_mock("succeeds".to_string()));
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        CancellationToken::new(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    // No indexer is configured, so reaching it would panic.
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    let err = data_anchor_client
+        .get_blobs_by_slot_range::<Vec<u8>>(Pubkey::new_unique().into(), 20, 10)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            DataAnchorClientError::Indexer(IndexerError::InvalidSlotRange(
+                SlotRangeError::Inverted { .. }
+            ))
+        ),
+        "Expected an inverted slot range error, got {err:?}"
+    );
+}
+
+// The default MockSender always returns the same value for get_last_blockhash and
+// get_epoch_info, so we wrap that in a bit more logic.
+struct MockBlockSender {
+    sender: MockSender,
+    initial_time: Instant,
+}
+
+#[async_trait]
+impl RpcSender for MockBlockSender {
+    async fn send(
+        &self,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, Error> {
+        // For this test it's fine to pretend that slots and blocks are the same thing.
+        let slot = (Instant::now().duration_since(self.initial_time).as_millis()
+            / DEFAULT_MS_PER_SLOT as u128) as u64;
+        if let RpcRequest::GetLatestBlockhash = request {
+            Ok(serde_json::to_value(Response {
+                context: RpcResponseContext {
+                    slot,
+                    api_version: None,
+                },
+                value: RpcBlockhash {
+                    blockhash: Hash::default().to_string(),
+                    last_valid_block_height: slot + 150,
+                },
+            })?)
+        } else if let RpcRequest::GetEpochInfo = request {
+            Ok(serde_json::to_value(EpochInfo {
+                epoch: 0,
+                slot_index: slot,
+                slots_in_epoch: 256,
+                absolute_slot: slot,
+                block_height: slot,
+                transaction_count: Some(123),
+            })?)
+        } else {
+            self.sender.send(request, params).await
+        }
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.sender.get_transport_stats()
+    }
+
+    fn url(&self) -> String {
+        self.sender.url()
+    }
+}
+
+struct UnreliableSender(MockBlockSender);
+
+#[async_trait]
+impl RpcSender for UnreliableSender {
+    async fn send(
+        &self,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, Error> {
+        let failure_rate = match &request {
+            // Always let airdrops, balance checks and slot queries through, since those
+            // are used in the test setup itself.
+            RpcRequest::RequestAirdrop | RpcRequest::GetBalance | RpcRequest::GetSlot => 0.0,
+            // This needs special treatment since we want to simulate some of the transactions failing,
+            // not the entire request.
+            RpcRequest::GetSignatureStatuses => {
+                // Small chance to fail the signature request itself.
+                if rand::thread_rng().gen_bool(0.1) {
+                    return Err(Error {
+                        request: None,
+                        kind: ErrorKind::Custom("failed".to_string()),
+                    });
+                }
+                let successful = self.0.send(request, params).await.unwrap();
+                let mut statuses: Response<Vec<Option<TransactionStatus>>> =
+                    serde_json::from_value(successful).unwrap();
+                let mut rng = rand::thread_rng();
+                for status in &mut statuses.value {
+                    // Even if 50% of transactions fail, the client should still work.
+                    // (even higher works too, but the test takes an awfully long time)
+                    if rng.gen_bool(0.5) {
+                        *status = None;
+                    }
+                }
+                return Ok(serde_json::to_value(statuses).unwrap());
+            }
+            // Any other request can fail rarely.
+            _ => 0.1,
+        };
+        if rand::thread_rng().gen_bool(failure_rate) {
+            return Err(Error {
+                request: None,
+                kind: ErrorKind::Custom("failed".to_string()),
+            });
+        }
+        self.0.send(request, params).await
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.0.get_transport_stats()
+    }
+
+    fn url(&self) -> String {
+        self.0.url()
+    }
+}
+
+#[test]
+fn timestamps_are_unique_under_contention() {
+    let mut threads = Vec::new();
+    for _ in 0..100 {
+        threads.push(std::thread::spawn(|| {
+            let mut timestamps = Vec::new();
+            for _ in 0..1000 {
+                timestamps.push(get_unique_timestamp());
+            }
+            timestamps
+        }));
+    }
+
+    let timestamps = threads
+        .into_iter()
+        .flat_map(|t| t.join().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(timestamps.len(), timestamps.iter().unique().count());
+    let min = timestamps.iter().min().unwrap();
+    let max = timestamps.iter().max().unwrap();
+    let count = timestamps.len();
+    let current_time = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    dbg!(min, max, count, current_time);
+}
+
+#[test]
+fn blober_identifier_rejects_invalid_namespace() {
+    let payer = Pubkey::new_unique();
+    for namespace in ["", "with space", "näme", &"a".repeat(33)] {
+        // Blobers created before namespaces were validated must still be addressable.
+        let identifier =
+            BloberIdentifier::try_from((Some(namespace.to_owned()), Some(payer))).unwrap();
+        assert_eq!(identifier.namespace(), Some(namespace));
+
+        let err = identifier.validate().unwrap_err();
+        assert!(
+            matches!(&err, BloberIdentifierError::InvalidNamespace(n) if n == namespace),
+            "Expected InvalidNamespace for {namespace:?}, got {err:?}"
+        );
+    }
+
+    BloberIdentifier::try_from((Some("a".repeat(32)), None))
+        .unwrap()
+        .validate()
+        .unwrap();
+    BloberIdentifier::try_from((None, Some(payer)))
+        .unwrap()
+        .validate()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn fee_payer_pays_while_payer_owns_pdas() {
+    let payer = Arc::new(Keypair::new());
+    let fee_payer = Arc::new(Keypair::new());
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        MockBlockSender {
+            sender: MockSender::new("succeeds".to_string()),
+            initial_time: Instant::now(),
+        },
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone(), fee_payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer.clone())
+        .fee_payer(fee_payer.clone())
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+    assert_eq!(data_anchor_client.fee_payer().pubkey(), fee_payer.pubkey());
+
+    let blober = find_blober_address(data_anchor_blober::id(), payer.pubkey(), "test");
+    let blob = find_blob_address(data_anchor_blober::id(), payer.pubkey(), blober, 0, 10);
+    let msg =
+        DiscardBlob::build_message(data_anchor_client.message_arguments(blober, Fee::ZERO, blob))
+            .await;
+
+    // The fee payer is always the first account, and both keys have to sign.
+    assert_eq!(msg.account_keys[0], fee_payer.pubkey());
+    assert_eq!(msg.header.num_required_signatures, 2);
+    assert!(
+        msg.is_signer(
+            msg.account_keys
+                .iter()
+                .position(|k| *k == payer.pubkey())
+                .unwrap()
+        )
+    );
+    assert!(msg.account_keys.contains(&blober));
+    assert!(msg.account_keys.contains(&blob));
+
+    data_anchor_client
+        .discard_blob(
+            FeeStrategy::default(),
+            blob,
+            "test",
+            Some(Duration::from_secs(20)),
+        )
+        .await
+        .unwrap();
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn fee_payer_balance_is_checked_for_fees() {
+    let payer = Arc::new(Keypair::new());
+    let fee_payer = Arc::new(Keypair::new());
+    let balances = HashMap::from([(payer.pubkey(), LAMPORTS_PER_SOL), (fee_payer.pubkey(), 0)]);
+    let sender = ScriptedSender::new(move |request, params| {
+        let value = match request {
+            // Every account exists, so that only the balance check can fail.
+            RpcRequest::GetAccountInfo => {
+                json!(mock_account(
+                    data_anchor_blober::id(),
+                    Blob::DISCRIMINATOR,
+                    false
+                ))
+            }
+            RpcRequest::GetBalance => {
+                let pubkey: Pubkey = params[0]
+                    .as_str()
+                    .and_then(|pubkey| pubkey.parse().ok())
+                    .expect("getBalance to be called with a pubkey");
+                json!(balances[&pubkey])
+            }
+            RpcRequest::GetRecentPrioritizationFees => return Some(json!([])),
+            _ => return None,
+        };
+        Some(json!({ "context": { "slot": 1 }, "value": value }))
+    })
+    .with_url("http://localhost:8899");
+    let requests = sender.log();
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        sender,
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone(), fee_payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .fee_payer(fee_payer.clone())
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    // The payer could afford the fees, but the fee payer is the one paying them.
+    let err = data_anchor_client
+        .discard_blob(
+            FeeStrategy::default(),
+            Pubkey::new_unique(),
+            "test",
+            Some(Duration::from_secs(5)),
+        )
+        .await
+        .unwrap_err();
+    let DataAnchorClientError::ChainErrors(ChainError::InsufficientBalance(required, 0)) = err
+    else {
+        panic!("Expected InsufficientBalance, got {err:?}");
+    };
+
+    // Both the payer and the fee payer sign the transaction, so two signatures are paid for.
+    assert_eq!(data_anchor_client.num_signatures(), 2);
+    let expected = Fee {
+        num_signatures: 2,
+        price_per_signature: Lamports::new(5000),
+        compute_unit_limit: TransactionType::DiscardBlob.compute_unit_limit(),
+        prioritization_fee_rate: MicroLamports::MIN,
+        blob_account_size: 0,
+    };
+    assert_eq!(required, expected.total_fee().into_inner() as u64);
+
+    // The fee payer is written to as well, so priority fees are estimated for it.
+    let fee_accounts = requests
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(request, _)| matches!(request, RpcRequest::GetRecentPrioritizationFees))
+        .map(|(_, params)| params[0].clone())
+        .unwrap();
+    assert!(
+        fee_accounts
+            .as_array()
+            .unwrap()
+            .contains(&json!(fee_payer.pubkey().to_string()))
+    );
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn blob_messages_include_finalized_blobs() {
+    let payer = Arc::new(Keypair::new());
+    let blober = find_blober_address(data_anchor_blober::id(), payer.pubkey(), "test");
+    let finalized_blob = Pubkey::new_unique();
+
+    // Only used to build the messages in the block.
+    let cancellation_token = CancellationToken::new();
+    let mock_rpc_client = Arc::new(RpcClient::new_mock("succeeds".to_string()));
+    let message_client = DataAnchorClient::builder()
+        .payer(payer.clone())
+        .program_id(data_anchor_blober::id())
+        .rpc_client(mock_rpc_client.clone())
+        .nitro_sender(
+            NitroSender::new(
+                mock_rpc_client,
+                cancellation_token.clone(),
+                vec![payer.clone()],
+            )
+            .await
+            .unwrap(),
+        )
+        .build();
+    let finalize = FinalizeBlob::build_message(message_client.message_arguments(
+        blober,
+        Fee::ZERO,
+        finalized_blob,
+    ))
+    .await;
+    let discard = DiscardBlob::build_message(message_client.message_arguments(
+        blober,
+        Fee::ZERO,
+        Pubkey::new_unique(),
+    ))
+    .await;
+
+    let transactions = [finalize.clone(), discard]
+        .into_iter()
+        .map(|message| {
+            json!({
+                "transaction": solana_transaction_status::Encodable::encode(
+                    &Transaction::new_unsigned(message),
+                    UiTransactionEncoding::Base58,
+                ),
+                "meta": null,
+            })
+        })
+        .collect_vec();
+    let block = json!({
+        "previousBlockhash": Hash::default().to_string(),
+        "blockhash": Hash::default().to_string(),
+        "parentSlot": 0,
+        "transactions": transactions,
+        "blockTime": null,
+        "blockHeight": null,
+    });
+
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        ScriptedSender::new(move |request, _| {
+            matches!(request, RpcRequest::GetBlock).then(|| block.clone())
+        }),
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer.clone())
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client.clone())
+        .nitro_sender(
+            NitroSender::new(rpc_client, cancellation_token.clone(), vec![payer])
+                .await
+                .unwrap(),
+        )
+        .build();
+
+    let messages = data_anchor_client
+        .get_blob_messages(1, BloberIdentifier::Namespace("test".to_owned()))
+        .await
+        .unwrap();
+    assert_eq!(
+        messages,
+        vec![(finalized_blob, VersionedMessage::Legacy(finalize))]
+    );
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn compressed_upload_is_fetched_regardless_of_client_defaults() {
+    let payer = Arc::new(Keypair::new());
+    let cancellation_token = CancellationToken::new();
+
+    let sender = ScriptedSender::new(|_, _| None);
+    let requests = sender.log();
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        sender,
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+    let upload_client = DataAnchorClient::builder()
+        .payer(payer.clone())
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client.clone())
+        .nitro_sender(
+            NitroSender::new(rpc_client, cancellation_token.clone(), vec![payer.clone()])
+                .await
+                .unwrap(),
+        )
+        .build();
+
+    let data = (0..2000u32).map(|i| (i % 7) as u8).collect::<Vec<_>>();
+    upload_client
+        .upload_blob(
+            &data,
+            FeeStrategy::default(),
+            "test",
+            Some(Duration::from_secs(20)),
+            UploadOptions::builder()
+                .compression(CompressionType::ZstdCompression(
+                    ZstdCompression::default().0,
+                ))
+                .encoding(EncodingType::Json)
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    // Serve every sent transaction in a single block.
+    let transactions = requests
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(request, _)| matches!(request, RpcRequest::SendTransaction))
+        .map(|(_, params)| {
+            params[0]
+                .as_str()
+                .expect("sendTransaction to be called with an encoded transaction")
+                .to_owned()
+        })
+        .unique()
+        .map(|transaction| json!({ "transaction": [transaction, "base64"], "meta": null }))
+        .collect_vec();
+    let block = json!({
+        "previousBlockhash": Hash::default().to_string(),
+        "blockhash": Hash::default().to_string(),
+        "parentSlot": 0,
+        "transactions": transactions,
+        "blockTime": null,
+        "blockHeight": null,
+    });
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        ScriptedSender::new(move |request, _| {
+            matches!(request, RpcRequest::GetBlock).then(|| block.clone())
+        }),
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+    // Fetched through a client with the default LZ4 compression and Postcard encoding.
+    let fetch_client = DataAnchorClient::builder()
+        .payer(payer.clone())
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client.clone())
+        .nitro_sender(
+            NitroSender::new(rpc_client, cancellation_token.clone(), vec![payer])
+                .await
+                .unwrap(),
+        )
+        .build();
+    assert!(matches!(
+        fetch_client.compression,
+        CompressionType::Lz4Compression
+    ));
+
+    let blobs = fetch_client
+        .get_ledger_blobs::<Vec<u8>>(1, "test".to_owned().into(), None)
+        .await
+        .unwrap();
+    assert_eq!(blobs, vec![data]);
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn configured_commitment_is_used_for_rpc_calls() {
+    let payer = Arc::new(Keypair::new());
+    let sender = ScriptedSender::with_accounts(HashMap::from([(
+        data_anchor_blober::id(),
+        mock_account(bpf_loader_upgradeable::ID, &[0; 36], true),
+    )]));
+    let requests = sender.log();
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        sender,
+        // The RPC client's own commitment is overridden by the one configured on the client.
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .commitment(CommitmentConfig::finalized())
+        .nitro_sender(batch_client)
+        .build();
+    assert_eq!(
+        data_anchor_client.commitment(),
+        CommitmentConfig::finalized()
+    );
+
+    data_anchor_client.check_program_deployed().await.unwrap();
+    data_anchor_client
+        .blob_status(Pubkey::new_unique())
+        .await
+        .unwrap();
+
+    // The batch client may poll in the background, so only look at the account lookups.
+    let account_commitments = requests
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(request, _)| matches!(request, RpcRequest::GetAccountInfo))
+        .map(|(_, params)| params[1]["commitment"].clone())
+        .collect_vec();
+    assert_eq!(account_commitments, vec![json!("finalized"); 2]);
+
+    cancellation_token.cancel();
+}
+
+async fn simulate_verify_with(
+    simulation: serde_json::Value,
+) -> (DataAnchorClientResult<u64>, Vec<serde_json::Value>) {
+    let payer = Arc::new(Keypair::new());
+    let sender = ScriptedSender::new(move |request, _| {
+        matches!(request, RpcRequest::SimulateTransaction).then(|| {
+            json!({
+                "context": { "slot": 1 },
+                "value": simulation,
+            })
+        })
+    });
+    let requests = sender.log();
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        sender,
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    let result = data_anchor_client
+        .simulate_verify(CheckpointArgs {
+            blober: BloberIdentifier::Pubkey(Pubkey::new_unique()),
+            verifier: CustomerElf::DataCorrectness,
+            proof: [0; GROTH16_PROOF_SIZE],
+            public_values: vec![1, 2, 3],
+            verification_key: "0x00".to_owned(),
+            slot: 1,
+        })
+        .await;
+    cancellation_token.cancel();
+
+    let configs = requests
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(request, _)| matches!(request, RpcRequest::SimulateTransaction))
+        .map(|(_, params)| params[1].clone())
+        .collect();
+    (result, configs)
+}
+
+#[tokio::test]
+async fn simulate_verify_returns_consumed_units() {
+    let (result, configs) = simulate_verify_with(json!({
+        "err": null,
+        "logs": [],
+        "accounts": null,
+        "unitsConsumed": 271_828,
+        "returnData": null,
+    }))
+    .await;
+
+    assert_eq!(result.unwrap(), 271_828);
+    assert_eq!(configs.len(), 1);
+    assert_eq!(configs[0]["sigVerify"], false);
+    assert_eq!(configs[0]["replaceRecentBlockhash"], true);
+    assert_eq!(configs[0]["commitment"], "confirmed");
+}
+
+#[tokio::test]
+async fn simulate_verify_surfaces_simulation_errors() {
+    let (result, _) = simulate_verify_with(json!({
+        "err": "AccountNotFound",
+        "logs": ["Program log: Error: proof verification failed"],
+        "accounts": null,
+        "unitsConsumed": 1_000,
+        "returnData": null,
+    }))
+    .await;
+
+    let Err(DataAnchorClientError::ChainErrors(ChainError::SimulationFailed(message))) = result
+    else {
+        panic!("expected a simulation failure, got {result:?}");
+    };
+    assert!(message.contains("proof verification failed"));
+}
+
+#[tokio::test]
+async fn create_checkpoint_uses_configured_verifier() {
+    let payer = Arc::new(Keypair::new());
+    let blober = find_blober_address(data_anchor_blober::id(), payer.pubkey(), "test");
+    let mut config = CheckpointConfig::DISCRIMINATOR.to_vec();
+    CheckpointConfig {
+        blober,
+        authority: CustomerElf::PobSla.authority(),
+    }
+    .serialize(&mut config)
+    .unwrap();
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        ScriptedSender::with_accounts(HashMap::from([(
+            find_checkpoint_config_address(data_anchor_blober::id(), blober),
+            mock_account(data_anchor_blober::id(), &config, false),
+        )])),
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    let proof_for = |blober: Pubkey| ProofData {
+        proof: vec![1; GROTH16_PROOF_SIZE],
+        public_values: [blober.to_bytes(), [2; 32], [3; 32]].concat(),
+        verification_key: format!("0x{}", "ab".repeat(32)),
+        proof_system: ProofSystem::Groth16,
+    };
+    let create_checkpoint = |proof| {
+        data_anchor_client.create_checkpoint(
+            FeeStrategy::default(),
+            BloberIdentifier::Pubkey(blober),
+            proof,
+            42,
+            Some(Duration::from_secs(20)),
+        )
+    };
+
+    let (_, checkpoint) = create_checkpoint(proof_for(blober)).await.unwrap();
+    assert_eq!(
+        checkpoint,
+        find_checkpoint_address(data_anchor_blober::id(), blober)
+    );
+
+    let other = Pubkey::new_unique();
+    let err = create_checkpoint(proof_for(other)).await.unwrap_err();
+    assert!(
+        matches!(
+            err,
+            DataAnchorClientError::ChainErrors(ChainError::ProofBloberMismatch(expected, got))
+                if expected == blober && got == other
+        ),
+        "Expected ProofBloberMismatch, got {err:?}"
+    );
+
+    let err = create_checkpoint(ProofData {
+        proof: vec![1; GROTH16_PROOF_SIZE - 1],
+        ..proof_for(blober)
+    })
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            DataAnchorClientError::ChainErrors(ChainError::InvalidCheckpointProof(_))
+        ),
+        "Expected InvalidCheckpointProof, got {err:?}"
+    );
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn configure_checkpoint_requires_current_authority() {
+    let payer = Arc::new(Keypair::new());
+    let blober = find_blober_address(data_anchor_blober::id(), payer.pubkey(), "test");
+    let authority = Pubkey::new_unique();
+    let mut config = CheckpointConfig::DISCRIMINATOR.to_vec();
+    CheckpointConfig { blober, authority }
+        .serialize(&mut config)
+        .unwrap();
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        ScriptedSender::with_accounts(HashMap::from([(
+            find_checkpoint_config_address(data_anchor_blober::id(), blober),
+            mock_account(data_anchor_blober::id(), &config, false),
+        )])),
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer.clone())
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    let stored = data_anchor_client
+        .get_checkpoint_config(BloberIdentifier::Pubkey(blober))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.authority, authority);
+    assert_eq!(stored.blober, blober);
+
+    let err = data_anchor_client
+        .configure_checkpoint(
+            FeeStrategy::default(),
+            BloberIdentifier::Pubkey(blober),
+            payer.pubkey(),
+            Some(Duration::from_secs(20)),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            DataAnchorClientError::ChainErrors(ChainError::NotCheckpointAuthority {
+                blober: b,
+                authority: a,
+            }) if b == blober && a == authority
+        ),
+        "Expected NotCheckpointAuthority, got {err:?}"
+    );
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn initialize_blober_if_absent_only_creates_missing_blobers() {
+    let payer = Arc::new(Keypair::new());
+    let existing = find_blober_address(data_anchor_blober::id(), payer.pubkey(), "existing");
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        ScriptedSender::with_accounts(HashMap::from([(
+            existing,
+            mock_account(data_anchor_blober::id(), Blober::DISCRIMINATOR, false),
+        )])),
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    for (namespace, created) in [("existing", false), ("missing", true)] {
+        assert_eq!(
+            data_anchor_client
+                .initialize_blober_if_absent(
+                    FeeStrategy::default(),
+                    namespace.to_owned().into(),
+                    Some(Duration::from_secs(20)),
+                )
+                .await
+                .unwrap(),
+            created,
+            "namespace {namespace}"
+        );
+    }
+
+    cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn checkpoint_sla_score_is_read_from_commitments() {
+    let payer = Arc::new(Keypair::new());
+    let checkpoint_account = |blober: Pubkey, commitments: &[u8]| {
+        let checkpoint = Checkpoint::new(
+            [1; GROTH16_PROOF_SIZE],
+            [
+                blober.to_bytes().as_slice(),
+                &[2; 32],
+                &[3; 32],
+                commitments,
+            ]
+            .concat(),
+            format!("0x{}", "ab".repeat(32)),
+            42,
+        )
+        .unwrap();
+        let mut data = Checkpoint::DISCRIMINATOR.to_vec();
+        checkpoint.serialize(&mut data).unwrap();
+        (
+            find_checkpoint_address(data_anchor_blober::id(), blober),
+            mock_account(data_anchor_blober::id(), &data, false),
+        )
+    };
+    let data_correctness = Pubkey::new_unique();
+    let sla_f64 = Pubkey::new_unique();
+    let sla_f32 = Pubkey::new_unique();
+    let rpc_client = Arc::new(RpcClient::new_sender(
+        ScriptedSender::with_accounts(HashMap::from([
+            checkpoint_account(data_correctness, &[]),
+            checkpoint_account(sla_f64, &[[0].as_slice(), &0.75f64.to_le_bytes()].concat()),
+            checkpoint_account(sla_f32, &[[1].as_slice(), &0.5f32.to_le_bytes()].concat()),
+        ])),
+        RpcClientConfig::with_commitment(CommitmentConfig::confirmed()),
+    ));
+    let cancellation_token = CancellationToken::new();
+    let batch_client = NitroSender::new(
+        rpc_client.clone(),
+        cancellation_token.clone(),
+        vec![payer.clone()],
+    )
+    .await
+    .unwrap();
+    let data_anchor_client = DataAnchorClient::builder()
+        .payer(payer)
+        .program_id(data_anchor_blober::id())
+        .rpc_client(rpc_client)
+        .nitro_sender(batch_client)
+        .build();
+
+    let sla_score =
+        |blober| data_anchor_client.get_checkpoint_sla_score(BloberIdentifier::Pubkey(blober));
+    assert_eq!(sla_score(data_correctness).await.unwrap(), None);
+    assert_eq!(sla_score(sla_f64).await.unwrap(), Some(0.75));
+    assert_eq!(sla_score(sla_f32).await.unwrap(), Some(0.5));
+
+    cancellation_token.cancel();
 }
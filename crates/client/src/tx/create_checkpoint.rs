@@ -0,0 +1,119 @@
+use anchor_lang::{
+    Discriminator, InstructionData, Space, ToAccountMetas,
+    prelude::Pubkey,
+    solana_program::{instruction::Instruction, system_program},
+};
+use data_anchor_api::CustomerElf;
+use data_anchor_blober::{
+    checkpoint::{Checkpoint, CheckpointConfig},
+    find_checkpoint_address, find_checkpoint_config_address, find_checkpoint_signer_address,
+    instruction::CreateCheckpoint,
+    state::blober::Blober,
+};
+
+use crate::{
+    TransactionType,
+    tx::{DATA_ANCHOR_BLOBER_PROGRAM_DATA_SIZE, MessageArguments, MessageBuilder},
+};
+
+/// The verifier programs aren't measured separately, so budget as much as for the blober program
+/// whose CPI interface they link in.
+pub const VERIFIER_PROGRAM_DATA_SIZE: u32 = DATA_ANCHOR_BLOBER_PROGRAM_DATA_SIZE;
+
+/// Checkpoints can only be created by the verifier program configured as the checkpoint authority,
+/// which verifies the proof and then calls `create_checkpoint` on the blober program. The
+/// instruction sent is therefore the verifier's `verify` instruction, with the same arguments.
+impl MessageBuilder for CreateCheckpoint {
+    type Input = (Self, CustomerElf);
+    const TX_TYPE: TransactionType = TransactionType::CreateCheckpoint;
+    const COMPUTE_UNIT_LIMIT: u32 = 300_000;
+    const LOADED_ACCOUNT_DATA_SIZE: u32 = (Blober::DISCRIMINATOR.len()
+        + Blober::INIT_SPACE
+        + Checkpoint::DISCRIMINATOR.len()
+        + Checkpoint::INIT_SPACE
+        + CheckpointConfig::DISCRIMINATOR.len()
+        + CheckpointConfig::INIT_SPACE) as u32
+        + VERIFIER_PROGRAM_DATA_SIZE;
+
+    fn mutable_accounts(args: &MessageArguments<Self::Input>) -> Vec<Pubkey> {
+        vec![
+            find_checkpoint_address(args.program_id, args.blober),
+            find_checkpoint_signer_address(args.input.1.authority(), args.blober),
+            args.payer,
+        ]
+    }
+
+    fn generate_instructions(args: &MessageArguments<Self::Input>) -> Vec<Instruction> {
+        let (input, verifier) = &args.input;
+        let checkpoint = find_checkpoint_address(args.program_id, args.blober);
+        let checkpoint_config = find_checkpoint_config_address(args.program_id, args.blober);
+        // The PDA signer is derived from the verifier program, which signs for it in the CPI.
+        let pda_signer = find_checkpoint_signer_address(verifier.authority(), args.blober);
+
+        // Both verifier programs take the same accounts and arguments.
+        let (accounts, data) = match verifier {
+            CustomerElf::DataCorrectness => (
+                data_anchor_data_correctness_verifier::accounts::Verify {
+                    checkpoint,
+                    checkpoint_config,
+                    blober: args.blober,
+                    pda_signer,
+                    payer: args.payer,
+                    data_anchor: args.program_id,
+                    system_program: system_program::id(),
+                }
+                .to_account_metas(None),
+                data_anchor_data_correctness_verifier::instruction::Verify {
+                    proof: input.proof,
+                    public_values: input.public_values.clone(),
+                    verification_key: input.verification_key.clone(),
+                    slot: input.slot,
+                }
+                .data(),
+            ),
+            CustomerElf::PobSla => (
+                data_anchor_pob_sla_verifier::accounts::Verify {
+                    checkpoint,
+                    checkpoint_config,
+                    blober: args.blober,
+                    pda_signer,
+                    payer: args.payer,
+                    data_anchor: args.program_id,
+                    system_program: system_program::id(),
+                }
+                .to_account_metas(None),
+                data_anchor_pob_sla_verifier::instruction::Verify {
+                    proof: input.proof,
+                    public_values: input.public_values.clone(),
+                    verification_key: input.verification_key.clone(),
+                    slot: input.slot,
+                }
+                .data(),
+            ),
+        };
+
+        vec![Instruction {
+            program_id: verifier.authority(),
+            accounts,
+            data,
+        }]
+    }
+
+    #[cfg(test)]
+    fn generate_arbitrary_input(
+        u: &mut arbitrary::Unstructured,
+        _payer: Pubkey,
+        blober: Pubkey,
+    ) -> arbitrary::Result<Self::Input> {
+        Ok((
+            Self {
+                blober,
+                proof: u.arbitrary()?,
+                public_values: blober.to_bytes().to_vec(),
+                verification_key: format!("0x{}", "0".repeat(64)),
+                slot: u.arbitrary()?,
+            },
+            CustomerElf::DataCorrectness,
+        ))
+    }
+}
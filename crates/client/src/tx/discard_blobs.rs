@@ -0,0 +1,102 @@
+use anchor_lang::{
+    Discriminator, InstructionData, Space, ToAccountMetas, prelude::Pubkey,
+    solana_program::instruction::Instruction,
+};
+use data_anchor_blober::{blob::Blob, instruction::DiscardBlob, state::blober::Blober};
+
+use crate::{
+    TransactionType,
+    tx::{MessageArguments, MessageBuilder},
+};
+
+/// Discards several blobs in a single transaction, one [`DiscardBlob`] instruction per blob.
+pub struct DiscardBlobs {
+    blobs: Vec<Pubkey>,
+}
+
+impl DiscardBlobs {
+    /// The maximum number of blobs that can be discarded in a single transaction.
+    pub const MAX_BLOBS_PER_TX: usize = 8;
+
+    pub(crate) fn new(blobs: Vec<Pubkey>) -> Self {
+        debug_assert!(blobs.len() <= Self::MAX_BLOBS_PER_TX);
+        Self { blobs }
+    }
+
+    pub(crate) fn blobs(&self) -> &[Pubkey] {
+        &self.blobs
+    }
+}
+
+impl MessageBuilder for DiscardBlobs {
+    type Input = Self;
+    const TX_TYPE: TransactionType = TransactionType::DiscardBlobs;
+    const COMPUTE_UNIT_LIMIT: u32 = DiscardBlob::COMPUTE_UNIT_LIMIT * Self::MAX_BLOBS_PER_TX as u32;
+    const LOADED_ACCOUNT_DATA_SIZE: u32 = (Blober::DISCRIMINATOR.len()
+        + Blober::INIT_SPACE
+        + (Blob::DISCRIMINATOR.len() + Blob::INIT_SPACE) * Self::MAX_BLOBS_PER_TX)
+        as u32;
+
+    fn mutable_accounts(args: &MessageArguments<Self::Input>) -> Vec<Pubkey> {
+        args.input
+            .blobs
+            .iter()
+            .copied()
+            .chain(std::iter::once(args.payer))
+            .collect()
+    }
+
+    fn generate_instructions(args: &MessageArguments<Self::Input>) -> Vec<Instruction> {
+        args.input
+            .blobs
+            .iter()
+            .map(|blob| {
+                let accounts = data_anchor_blober::accounts::DiscardBlob {
+                    blob: *blob,
+                    blober: args.blober,
+                    payer: args.payer,
+                };
+
+                Instruction {
+                    program_id: args.program_id,
+                    accounts: accounts.to_account_metas(None),
+                    data: DiscardBlob {}.data(),
+                }
+            })
+            .collect()
+    }
+
+    #[cfg(test)]
+    fn generate_arbitrary_input(
+        u: &mut arbitrary::Unstructured,
+        payer: Pubkey,
+        blober: Pubkey,
+    ) -> arbitrary::Result<Self::Input> {
+        let blobs = (0..Self::MAX_BLOBS_PER_TX)
+            .map(|_| {
+                let timestamp: u64 = u.arbitrary()?;
+                let blob_size: usize = u.arbitrary()?;
+                Ok(data_anchor_blober::find_blob_address(
+                    data_anchor_blober::id(),
+                    payer,
+                    blober,
+                    timestamp,
+                    blob_size,
+                ))
+            })
+            .collect::<arbitrary::Result<Vec<_>>>()?;
+
+        Ok(Self::new(blobs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tx::{DiscardBlobs, MessageBuilder};
+
+    #[test]
+    #[ignore]
+    fn test_compute_unit_limit() {
+        DiscardBlobs::test_compute_unit_limit();
+    }
+}
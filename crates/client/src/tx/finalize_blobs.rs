@@ -0,0 +1,109 @@
+use anchor_lang::{
+    Discriminator, InstructionData, Space, ToAccountMetas,
+    prelude::{AccountMeta, Pubkey},
+    solana_program::instruction::Instruction,
+};
+use data_anchor_blober::{
+    blob::Blob,
+    instruction::{FinalizeBlob, FinalizeBlobs as FinalizeBlobsInstruction},
+    state::blober::Blober,
+};
+
+use crate::{
+    TransactionType,
+    tx::{MessageArguments, MessageBuilder},
+};
+
+/// Finalizes several blobs in a single transaction with one [`FinalizeBlobsInstruction`], which
+/// takes the blobs as its remaining accounts.
+pub struct FinalizeBlobs {
+    blobs: Vec<Pubkey>,
+}
+
+impl FinalizeBlobs {
+    /// The maximum number of blobs that can be finalized in a single transaction.
+    pub const MAX_BLOBS_PER_TX: usize = 8;
+
+    pub(crate) fn new(blobs: Vec<Pubkey>) -> Self {
+        debug_assert!(blobs.len() <= Self::MAX_BLOBS_PER_TX);
+        Self { blobs }
+    }
+
+    pub(crate) fn blobs(&self) -> &[Pubkey] {
+        &self.blobs
+    }
+}
+
+impl MessageBuilder for FinalizeBlobs {
+    type Input = Self;
+    const TX_TYPE: TransactionType = TransactionType::FinalizeBlobs;
+    const COMPUTE_UNIT_LIMIT: u32 =
+        FinalizeBlob::COMPUTE_UNIT_LIMIT * Self::MAX_BLOBS_PER_TX as u32;
+    const LOADED_ACCOUNT_DATA_SIZE: u32 = (Blober::DISCRIMINATOR.len()
+        + Blober::INIT_SPACE
+        + (Blob::DISCRIMINATOR.len() + Blob::INIT_SPACE) * Self::MAX_BLOBS_PER_TX)
+        as u32;
+
+    fn mutable_accounts(args: &MessageArguments<Self::Input>) -> Vec<Pubkey> {
+        args.input
+            .blobs
+            .iter()
+            .copied()
+            .chain([args.blober, args.payer])
+            .collect()
+    }
+
+    fn generate_instructions(args: &MessageArguments<Self::Input>) -> Vec<Instruction> {
+        let mut accounts = data_anchor_blober::accounts::FinalizeBlobs {
+            blober: args.blober,
+            payer: args.payer,
+        }
+        .to_account_metas(None);
+        accounts.extend(
+            args.input
+                .blobs
+                .iter()
+                .map(|blob| AccountMeta::new(*blob, false)),
+        );
+
+        vec![Instruction {
+            program_id: args.program_id,
+            accounts,
+            data: FinalizeBlobsInstruction {}.data(),
+        }]
+    }
+
+    #[cfg(test)]
+    fn generate_arbitrary_input(
+        u: &mut arbitrary::Unstructured,
+        payer: Pubkey,
+        blober: Pubkey,
+    ) -> arbitrary::Result<Self::Input> {
+        let blobs = (0..Self::MAX_BLOBS_PER_TX)
+            .map(|_| {
+                let timestamp: u64 = u.arbitrary()?;
+                let blob_size: usize = u.arbitrary()?;
+                Ok(data_anchor_blober::find_blob_address(
+                    data_anchor_blober::id(),
+                    payer,
+                    blober,
+                    timestamp,
+                    blob_size,
+                ))
+            })
+            .collect::<arbitrary::Result<Vec<_>>>()?;
+
+        Ok(Self::new(blobs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tx::{FinalizeBlobs, MessageBuilder};
+
+    #[test]
+    #[ignore]
+    fn test_compute_unit_limit() {
+        FinalizeBlobs::test_compute_unit_limit();
+    }
+}
@@ -19,15 +19,21 @@ pub mod compound;
 pub mod compound_declare;
 pub mod compound_finalize;
 pub mod configure_checkpoint;
+pub mod create_checkpoint;
 pub mod declare_blob;
 pub mod discard_blob;
+pub mod discard_blobs;
 pub mod finalize_blob;
+pub mod finalize_blobs;
 pub mod initialize_blober;
 pub mod insert_chunk;
+pub mod insert_chunk_with_crc;
 
 pub use compound::Compound;
 pub use compound_declare::CompoundDeclare;
 pub use compound_finalize::CompoundFinalize;
+pub use discard_blobs::DiscardBlobs;
+pub use finalize_blobs::FinalizeBlobs;
 
 pub struct MessageArguments<Input>
 where
@@ -37,7 +43,10 @@ where
     pub program_id: Pubkey,
     /// The address of the blober account to insert the chunk into.
     pub blober: Pubkey,
+    /// The owner of the blober, used to derive its PDAs and to sign for it.
     pub payer: Pubkey,
+    /// The account paying the transaction fees, which is the `payer` unless set otherwise.
+    pub fee_payer: Pubkey,
     pub client: Arc<RpcClient>,
     pub fee: Fee,
     pub input: Input,
@@ -62,9 +71,15 @@ where
             fee,
             input,
             payer: payer.pubkey(),
+            fee_payer: payer.pubkey(),
         }
     }
 
+    /// Sets a separate account to pay the transaction fees.
+    pub fn with_fee_payer(self, fee_payer: Pubkey) -> Self {
+        Self { fee_payer, ..self }
+    }
+
     pub fn to_other<'a, T>(&'a self) -> MessageArguments<T>
     where
         T: From<&'a Input> + Send,
@@ -73,6 +88,7 @@ where
             program_id: self.program_id,
             blober: self.blober,
             payer: self.payer,
+            fee_payer: self.fee_payer,
             client: self.client.clone(),
             fee: self.fee,
             input: T::from(&self.input),
@@ -113,7 +129,6 @@ pub trait MessageBuilder {
     const TX_TYPE: TransactionType;
     const COMPUTE_UNIT_LIMIT: u32;
     const LOADED_ACCOUNT_DATA_SIZE: u32;
-    const NUM_SIGNATURES: u16 = 1;
     #[cfg(test)]
     const INITIALIZE_BLOBER: bool = true;
 
@@ -154,12 +169,12 @@ pub trait MessageBuilder {
                 + (address_lookup_tables_count as u32 * ADDRESS_LOOKUP_TABLE_BASE_SIZE),
         );
 
-        let payer = Some(args.payer);
+        let fee_payer = Some(args.fee_payer);
 
         let mut all_instructions = vec![set_price, set_limit, set_account_data_size];
         all_instructions.extend(Self::generate_instructions(&args));
 
-        Message::new(&all_instructions, payer.as_ref())
+        Message::new(&all_instructions, fee_payer.as_ref())
     }
 
     #[cfg(test)]
@@ -213,6 +228,7 @@ pub trait MessageBuilder {
                         &rpc_client,
                         &[blober, payer.pubkey()],
                         Self::TX_TYPE,
+                        1,
                     )
                     .await
                     .unwrap();
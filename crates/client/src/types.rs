@@ -1,18 +1,36 @@
-use std::fmt::Display;
+use std::{fmt::Display, path::Path};
 
-use data_anchor_api::LedgerDataBlobError;
-use data_anchor_blober::instruction::{
-    Close, ConfigureCheckpoint, DeclareBlob, DiscardBlob, FinalizeBlob, Initialize, InsertChunk,
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{clock::Slot, hash::HASH_BYTES, message::Message},
 };
-use data_anchor_utils::DataAnchorUtilsError;
-use nitro_sender::TransactionOutcome;
+use bon::Builder;
+use data_anchor_api::{CustomerElf, LedgerDataBlobError};
+use data_anchor_blober::{
+    GROTH16_PROOF_SIZE,
+    instruction::{
+        Close, ConfigureCheckpoint, CreateCheckpoint, DeclareBlob, DiscardBlob, FinalizeBlob,
+        Initialize, InsertChunk,
+    },
+    state::blob::Blob,
+};
+use data_anchor_utils::{
+    DataAnchorUtilsError, compression::CompressionType, encoding::EncodingType,
+};
+use nitro_sender::{SuccessfulTransaction, TransactionOutcome};
+use serde::{Deserialize, Serialize};
 use solana_commitment_config::ParseCommitmentLevelError;
 use solana_rpc_client_api::client_error::Error;
+use solana_signature::Signature;
 use thiserror::Error;
+use tokio_util::sync::CancellationToken;
 
 use crate::{
-    client::{ChainError, IndexerError, ProofError},
-    tx::{Compound, CompoundDeclare, CompoundFinalize, MessageBuilder},
+    BloberIdentifier,
+    client::{BloberIdentifierError, ChainError, IndexerError, ProofError},
+    tx::{
+        Compound, CompoundDeclare, CompoundFinalize, DiscardBlobs, FinalizeBlobs, MessageBuilder,
+    },
 };
 
 /// Errors that can occur when interacting with the Blober client.
@@ -39,6 +57,9 @@ pub enum DataAnchorClientError {
     /// Invalid indexer API token: {0}
     #[error("Invalid indexer API token: {0}")]
     InvalidIndexerApiToken(String),
+    /// Invalid blober identifier: {0}
+    #[error(transparent)]
+    BloberIdentifier(#[from] BloberIdentifierError),
     /// Invalid key or namespace for blober
     #[error("Invalid key or namespace for blober")]
     InvalidKeyOrNamespace,
@@ -75,17 +96,31 @@ pub enum OutcomeError {
     Unsuccesful(Vec<TransactionOutcome<TransactionType>>),
 }
 
+impl OutcomeError {
+    /// Whether sending the transactions again may succeed. Transactions that failed to execute
+    /// will fail again, while ones that just didn't land in time, for example because their
+    /// blockhash expired, may land on a retry.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            OutcomeError::Unsuccesful(outcomes) => outcomes.iter().all(|o| o.error().is_none()),
+        }
+    }
+}
+
 /// Transaction types which can be performed by the [`data_anchor_blober::blober`] program.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TransactionType {
     CloseBlober,
     Compound,
     CompoundDeclare,
     CompoundFinalize,
     ConfigureCheckpoint,
+    CreateCheckpoint,
     DeclareBlob,
     DiscardBlob,
+    DiscardBlobs,
     FinalizeBlob,
+    FinalizeBlobs,
     InitializeBlober,
     InsertChunk(u16),
 }
@@ -97,10 +132,13 @@ impl Display for TransactionType {
             TransactionType::Compound => write!(f, "CompoundUpload"),
             TransactionType::CompoundDeclare => write!(f, "CompoundDeclare"),
             TransactionType::CompoundFinalize => write!(f, "CompoundFinalize"),
-            TransactionType::ConfigureCheckpoint => write!(f, "CreateCheckpoint"),
+            TransactionType::ConfigureCheckpoint => write!(f, "ConfigureCheckpoint"),
+            TransactionType::CreateCheckpoint => write!(f, "CreateCheckpoint"),
             TransactionType::DeclareBlob => write!(f, "DeclareBlob"),
             TransactionType::DiscardBlob => write!(f, "DiscardBlob"),
+            TransactionType::DiscardBlobs => write!(f, "DiscardBlobs"),
             TransactionType::FinalizeBlob => write!(f, "FinalizeBlob"),
+            TransactionType::FinalizeBlobs => write!(f, "FinalizeBlobs"),
             TransactionType::InitializeBlober => write!(f, "InitializeBlober"),
             TransactionType::InsertChunk(i) => write!(f, "InsertChunk {i}"),
         }
@@ -108,22 +146,6 @@ impl Display for TransactionType {
 }
 
 impl TransactionType {
-    /// Returns the number of signatures required for the transaction type.
-    pub(crate) fn num_signatures(&self) -> u16 {
-        match self {
-            TransactionType::CloseBlober => Close::NUM_SIGNATURES,
-            TransactionType::Compound => Compound::NUM_SIGNATURES,
-            TransactionType::CompoundDeclare => CompoundDeclare::NUM_SIGNATURES,
-            TransactionType::CompoundFinalize => CompoundFinalize::NUM_SIGNATURES,
-            TransactionType::ConfigureCheckpoint => ConfigureCheckpoint::NUM_SIGNATURES,
-            TransactionType::DeclareBlob => DeclareBlob::NUM_SIGNATURES,
-            TransactionType::DiscardBlob => DiscardBlob::NUM_SIGNATURES,
-            TransactionType::FinalizeBlob => FinalizeBlob::NUM_SIGNATURES,
-            TransactionType::InitializeBlober => Initialize::NUM_SIGNATURES,
-            TransactionType::InsertChunk(_) => InsertChunk::NUM_SIGNATURES,
-        }
-    }
-
     /// Returns the compute unit limit for the transaction type.
     pub(crate) fn compute_unit_limit(&self) -> u32 {
         match self {
@@ -132,11 +154,216 @@ impl TransactionType {
             TransactionType::CompoundDeclare => CompoundDeclare::COMPUTE_UNIT_LIMIT,
             TransactionType::CompoundFinalize => CompoundFinalize::COMPUTE_UNIT_LIMIT,
             TransactionType::ConfigureCheckpoint => ConfigureCheckpoint::COMPUTE_UNIT_LIMIT,
+            TransactionType::CreateCheckpoint => CreateCheckpoint::COMPUTE_UNIT_LIMIT,
             TransactionType::DeclareBlob => DeclareBlob::COMPUTE_UNIT_LIMIT,
             TransactionType::DiscardBlob => DiscardBlob::COMPUTE_UNIT_LIMIT,
+            TransactionType::DiscardBlobs => DiscardBlobs::COMPUTE_UNIT_LIMIT,
             TransactionType::FinalizeBlob => FinalizeBlob::COMPUTE_UNIT_LIMIT,
+            TransactionType::FinalizeBlobs => FinalizeBlobs::COMPUTE_UNIT_LIMIT,
             TransactionType::InitializeBlober => Initialize::COMPUTE_UNIT_LIMIT,
             TransactionType::InsertChunk(_) => InsertChunk::COMPUTE_UNIT_LIMIT,
         }
     }
+
+    /// Returns whether the transaction type finalizes a blob.
+    pub fn finalizes_blob(&self) -> bool {
+        matches!(
+            self,
+            TransactionType::Compound
+                | TransactionType::CompoundFinalize
+                | TransactionType::FinalizeBlob
+                | TransactionType::FinalizeBlobs
+        )
+    }
+}
+
+/// Optional settings for uploading a blob with [`crate::DataAnchorClient::upload_blob`]. The
+/// default uploads and finalizes the blob at a newly derived blob PDA without any extra checks,
+/// using the encoding and compression the client was built with.
+#[derive(Debug, Clone, Default, Builder)]
+pub struct UploadOptions {
+    /// A blob PDA and timestamp from [`crate::DataAnchorClient::derive_blob_address`] to upload
+    /// the blob at, instead of deriving a new one. The upload fails with
+    /// [`ChainError::BlobAddressMismatch`] if the blob doesn't derive to the same address.
+    pub address: Option<(Pubkey, u64)>,
+    /// Check the digest of the blob PDA against the blob data before finalizing. On a mismatch
+    /// the blob PDA is discarded and the upload fails with [`ChainError::BlobDigestMismatch`]. The
+    /// chunks are inserted one at a time when verifying, which makes the upload slower.
+    #[builder(default)]
+    pub verify_before_finalize: bool,
+    /// Ask the indexer for a finalized blob in the namespace with the same content hash, the
+    /// SHA-256 hash of the encoded and compressed blob, and skip the upload if there is one.
+    /// [`UploadOptions::verify_before_finalize`] only applies when the blob does get uploaded.
+    ///
+    /// An existing blob lives at its own address, so uploads with an [`UploadOptions::address`]
+    /// can't be deduplicated and fail with [`ChainError::DedupWithAddress`].
+    #[builder(default)]
+    pub dedup: bool,
+    /// The encoding to use instead of the one the client was built with. The uploaded blob starts
+    /// with a marker for it, so fetching it decodes it correctly regardless of how the fetching
+    /// client is configured.
+    pub encoding: Option<EncodingType>,
+    /// The compression to use instead of the one the client was built with. Like the encoding,
+    /// it is marked in the uploaded blob.
+    pub compression: Option<CompressionType>,
+    /// Stops sending transactions once cancelled. The blob PDA of a cancelled upload is discarded
+    /// the same way as that of a failed one, and the upload fails with
+    /// [`ChainError::UploadCancelled`] if nothing was left to discard, for example when it is
+    /// cancelled before the upload starts.
+    pub cancel: Option<CancellationToken>,
+    /// Stop once all chunks are inserted, leaving the blob PDA complete but not finalized.
+    /// Finalizing many deferred blobs in a burst with
+    /// [`crate::DataAnchorClient::finalize_blobs`] is cheaper than finalizing each one as part of
+    /// its upload. The [`UploadBlobResult::finalize_slot`] is always `None`.
+    #[builder(default)]
+    pub defer_finalize: bool,
+    /// Only build the messages of the upload without sending them, returning them in
+    /// [`UploadBlobResult::recorded`]. The recorded upload can be saved and sent later with
+    /// [`crate::DataAnchorClient::replay_upload`], which is useful for reproducing a failing
+    /// upload.
+    #[builder(default)]
+    pub record: bool,
+}
+
+/// The result of uploading a blob with [`crate::DataAnchorClient::upload_blob`].
+#[derive(Debug)]
+pub struct UploadBlobResult {
+    /// All the transactions sent during the upload, in the order they were sent.
+    pub transactions: Vec<SuccessfulTransaction<TransactionType>>,
+    /// The address of the [`data_anchor_blober::state::blob::Blob`] PDA.
+    pub blob: Pubkey,
+    /// The slot at which the finalize transaction landed, or `None` if the blob was discarded or
+    /// its finalize was deferred.
+    pub finalize_slot: Option<Slot>,
+    /// The messages of the upload if it was only recorded with [`UploadOptions::record`], in
+    /// which case nothing was sent.
+    pub recorded: Option<RecordedUpload>,
+}
+
+impl UploadBlobResult {
+    pub(crate) fn new(
+        transactions: Vec<SuccessfulTransaction<TransactionType>>,
+        blob: Pubkey,
+    ) -> Self {
+        let finalize_slot = transactions
+            .iter()
+            .find(|tx| tx.data.finalizes_blob())
+            .map(|tx| tx.slot);
+        Self {
+            transactions,
+            blob,
+            finalize_slot,
+            recorded: None,
+        }
+    }
+
+    pub(crate) fn recorded(recorded: RecordedUpload) -> Self {
+        Self {
+            transactions: Vec::new(),
+            blob: recorded.blob,
+            finalize_slot: None,
+            recorded: Some(recorded),
+        }
+    }
+}
+
+/// The messages of a blob upload, recorded with [`UploadOptions::record`] so the
+/// exact same upload can be sent later with [`crate::DataAnchorClient::replay_upload`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedUpload {
+    /// The address of the [`data_anchor_blober::state::blob::Blob`] PDA.
+    pub blob: Pubkey,
+    /// The messages in the order they are sent in, tagged with their transaction type.
+    pub messages: Vec<(TransactionType, Message)>,
+}
+
+impl RecordedUpload {
+    /// Writes the recorded upload to a JSON file.
+    pub fn save(&self, path: &Path) -> DataAnchorClientResult {
+        let json = serde_json::to_vec_pretty(self).map_err(std::io::Error::from)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Reads a recorded upload from a JSON file written by [`Self::save`].
+    pub fn load(path: &Path) -> DataAnchorClientResult<Self> {
+        let json = std::fs::read(path)?;
+        Ok(serde_json::from_slice(&json).map_err(std::io::Error::from)?)
+    }
+}
+
+/// The upload state of a blob, as returned by [`crate::DataAnchorClient::blob_status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobStatus {
+    /// Whether all chunks of the blob have been inserted.
+    pub complete: bool,
+    /// Whether the blob has been finalized into the blober hash.
+    pub finalized: bool,
+}
+
+/// The arguments of a verifier program's `verify` instruction, which verifies a Groth16 proof
+/// and creates a checkpoint for the blober with it.
+#[derive(Debug, Clone)]
+pub struct CheckpointArgs {
+    /// The blober to create the checkpoint for.
+    pub blober: BloberIdentifier,
+    /// The verifier program to verify the proof with.
+    pub verifier: CustomerElf,
+    /// The Groth16 proof.
+    pub proof: [u8; GROTH16_PROOF_SIZE],
+    /// The public values committed to by the proof.
+    pub public_values: Vec<u8>,
+    /// The verification key, in hex encoding with a leading "0x".
+    pub verification_key: String,
+    /// The slot the proof was generated for.
+    pub slot: u64,
+}
+
+/// The state of a [`Blob`] PDA account, as returned by
+/// [`crate::DataAnchorClient::get_blob_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobInfo {
+    /// The declared size of the blob in bytes.
+    pub size: u32,
+    /// The timestamp the blob address was derived from.
+    pub timestamp: u64,
+    /// The slot the blob was declared in.
+    pub created_at: u64,
+    /// The slot the last chunk was inserted in.
+    pub last_updated_at: u64,
+    /// Whether all chunks of the blob have been inserted.
+    pub is_complete: bool,
+    /// The digest of the chunks inserted so far.
+    pub digest: [u8; HASH_BYTES],
+}
+
+impl From<&Blob> for BlobInfo {
+    fn from(blob: &Blob) -> Self {
+        Self {
+            size: blob.size(),
+            timestamp: blob.timestamp(),
+            created_at: blob.created_at(),
+            last_updated_at: blob.last_updated_at(),
+            is_complete: blob.is_complete(),
+            digest: *blob.blob_digest(),
+        }
+    }
+}
+
+/// The outcome of discarding a single blob with [`crate::DataAnchorClient::discard_blobs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiscardBlobOutcome {
+    /// The blob was discarded by the transaction with the given signature.
+    Discarded { signature: Signature, slot: Slot },
+    /// The [`data_anchor_blober::state::blob::Blob`] PDA account does not exist.
+    NotFound,
+    /// The transaction carrying the discard instruction did not succeed.
+    Failed(String),
+}
+
+impl DiscardBlobOutcome {
+    /// Returns whether the blob was discarded.
+    pub fn is_discarded(&self) -> bool {
+        matches!(self, DiscardBlobOutcome::Discarded { .. })
+    }
 }
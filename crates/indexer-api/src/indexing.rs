@@ -3,7 +3,8 @@ use anchor_lang::{
     solana_program::instruction::CompiledInstruction,
 };
 use data_anchor_blober::{
-    BLOB_ACCOUNT_INSTRUCTION_IDX, BLOB_BLOBER_INSTRUCTION_IDX, instruction::InsertChunk,
+    BLOB_ACCOUNT_INSTRUCTION_IDX, BLOB_BLOBER_INSTRUCTION_IDX,
+    instruction::{FinalizeBlob, FinalizeBlobs, InsertChunk},
 };
 use itertools::Itertools;
 use serde::Serialize;
@@ -84,6 +85,18 @@ impl RelevantInstruction {
                     .map(RelevantInstruction::InsertChunk)
                     .ok()
             }
+            // The CRC was already checked on-chain, so only the chunk itself is kept.
+            InsertChunkWithCrc::DISCRIMINATOR => {
+                let data = compiled_instruction.data.get(8..).unwrap_or_default();
+                InsertChunkWithCrc::try_from_slice(data)
+                    .map(|insert| {
+                        RelevantInstruction::InsertChunk(InsertChunk {
+                            idx: insert.idx,
+                            data: insert.data,
+                        })
+                    })
+                    .ok()
+            }
             FinalizeBlob::DISCRIMINATOR => {
                 let data = compiled_instruction.data.get(8..).unwrap_or_default();
                 FinalizeBlob::try_from_slice(data)
@@ -107,7 +120,8 @@ pub struct RelevantInstructionWithAccounts {
 }
 
 /// Deserialize relevant instructions from a transaction, given the indices of the blob and blober
-/// accounts in the transaction.
+/// accounts in the transaction. A [`FinalizeBlobs`] instruction is split into one
+/// [`RelevantInstruction::FinalizeBlob`] per blob it finalizes.
 pub fn deserialize_relevant_instructions<'a>(
     program_id: &Pubkey,
     account_keys: &[Pubkey],
@@ -116,25 +130,62 @@ pub fn deserialize_relevant_instructions<'a>(
     blober_pubkey_index: usize,
 ) -> Vec<RelevantInstructionWithAccounts> {
     instructions
-        .filter_map(|compiled_instruction| {
+        .flat_map(|compiled_instruction| {
             let program_id_idx: usize = compiled_instruction.program_id_index.into();
-            let relevant_program_id = account_keys.get(program_id_idx)?;
+            if account_keys.get(program_id_idx) != Some(program_id) {
+                return Vec::new(); // Skip instructions not related to the specified program ID.
+            }
 
-            if program_id != relevant_program_id {
-                return None; // Skip instructions not related to the specified program ID.
+            if compiled_instruction.data.get(..8) == Some(FinalizeBlobs::DISCRIMINATOR) {
+                return split_finalize_blobs(account_keys, compiled_instruction);
             }
 
-            let blob = get_account_at_index(account_keys, compiled_instruction, blob_pubkey_index)?;
-            let blober =
-                get_account_at_index(account_keys, compiled_instruction, blober_pubkey_index)?;
-            let instruction = RelevantInstruction::try_from_slice(compiled_instruction)?;
-            let relevant_instruction = RelevantInstructionWithAccounts {
-                blob,
-                blober,
-                instruction,
-            };
+            deserialize_relevant_instruction(
+                account_keys,
+                compiled_instruction,
+                blob_pubkey_index,
+                blober_pubkey_index,
+            )
+            .into_iter()
+            .collect()
+        })
+        .collect()
+}
+
+fn deserialize_relevant_instruction(
+    account_keys: &[Pubkey],
+    compiled_instruction: &CompiledInstruction,
+    blob_pubkey_index: usize,
+    blober_pubkey_index: usize,
+) -> Option<RelevantInstructionWithAccounts> {
+    let blob = get_account_at_index(account_keys, compiled_instruction, blob_pubkey_index)?;
+    let blober = get_account_at_index(account_keys, compiled_instruction, blober_pubkey_index)?;
+    let instruction = RelevantInstruction::try_from_slice(compiled_instruction)?;
+    let relevant_instruction = RelevantInstructionWithAccounts {
+        blob,
+        blober,
+        instruction,
+    };
+
+    Some(relevant_instruction)
+}
 
-            Some(relevant_instruction)
+/// The blober is the first account of a [`FinalizeBlobs`] instruction and the payer the second,
+/// the blobs it finalizes follow as its remaining accounts.
+fn split_finalize_blobs(
+    account_keys: &[Pubkey],
+    compiled_instruction: &CompiledInstruction,
+) -> Vec<RelevantInstructionWithAccounts> {
+    let Some(blober) = get_account_at_index(account_keys, compiled_instruction, 0) else {
+        return Vec::new();
+    };
+
+    (2..compiled_instruction.accounts.len())
+        .filter_map(|index| get_account_at_index(account_keys, compiled_instruction, index))
+        .map(|blob| RelevantInstructionWithAccounts {
+            blob,
+            blober,
+            instruction: RelevantInstruction::FinalizeBlob(FinalizeBlob {}),
         })
         .collect()
 }
@@ -281,6 +332,16 @@ pub fn get_blob_data_from_instructions(
     blober: Pubkey,
     blob: Pubkey,
 ) -> Result<Vec<u8>, LedgerDataBlobError> {
+    Ok(get_blob_chunks_from_instructions(relevant_instructions, blober, blob)?.concat())
+}
+
+/// Extracts the chunks of blob data from the relevant instructions, in the order they make up the
+/// blob, without copying them into a single buffer.
+pub fn get_blob_chunks_from_instructions<'a>(
+    relevant_instructions: &'a [RelevantInstructionWithAccounts],
+    blober: Pubkey,
+    blob: Pubkey,
+) -> Result<Vec<&'a [u8]>, LedgerDataBlobError> {
     let blob_size = relevant_instructions
         .iter()
         .filter_map(|instruction| {
@@ -307,23 +368,13 @@ pub fn get_blob_data_from_instructions(
                 return None;
             };
 
-            Some(InsertChunk {
-                idx: insert.idx,
-                data: insert.data.clone(),
-            })
+            Some(insert)
         })
-        .collect::<Vec<InsertChunk>>();
-
-    let blob_data =
-        inserts
-            .iter()
-            .sorted_by_key(|insert| insert.idx)
-            .fold(Vec::new(), |mut acc, insert| {
-                acc.extend_from_slice(&insert.data);
-                acc
-            });
-
-    if blob_data.len() != blob_size as usize {
+        .sorted_by_key(|insert| insert.idx)
+        .map(|insert| insert.data.as_slice())
+        .collect::<Vec<&[u8]>>();
+
+    if inserts.iter().map(|data| data.len()).sum::<usize>() != blob_size as usize {
         return Err(LedgerDataBlobError::SizeMismatch);
     }
 
@@ -338,5 +389,82 @@ pub fn get_blob_data_from_instructions(
         return Err(LedgerDataBlobError::FinalizeNotFound);
     }
 
-    Ok(blob_data)
+    Ok(inserts)
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::InstructionData;
+    use data_anchor_blober::{chunk_crc, instruction::InsertChunkWithCrc};
+
+    use super::*;
+
+    fn compiled(data: Vec<u8>) -> CompiledInstruction {
+        CompiledInstruction {
+            program_id_index: 0,
+            accounts: Vec::new(),
+            data,
+        }
+    }
+
+    #[test]
+    fn inserts_with_and_without_crc_are_decoded() {
+        let chunk = vec![1, 2, 3];
+
+        let plain = InsertChunk {
+            idx: 4,
+            data: chunk.clone(),
+        };
+        let with_crc = InsertChunkWithCrc {
+            idx: 4,
+            data: chunk.clone(),
+            crc: chunk_crc(&chunk),
+        };
+
+        for data in [plain.data(), with_crc.data()] {
+            let Some(RelevantInstruction::InsertChunk(insert)) =
+                RelevantInstruction::try_from_slice(&compiled(data))
+            else {
+                panic!("expected an insert chunk instruction");
+            };
+            assert_eq!(insert.idx, 4);
+            assert_eq!(insert.data, chunk);
+        }
+    }
+
+    #[test]
+    fn finalize_blobs_is_split_per_blob() {
+        let program_id = Pubkey::new_unique();
+        let blober = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        let blobs = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let account_keys = [vec![program_id, blober, payer], blobs.to_vec()].concat();
+        let finalize_blobs = CompiledInstruction {
+            program_id_index: 0,
+            accounts: vec![1, 2, 3, 4],
+            data: FinalizeBlobs {}.data(),
+        };
+
+        let relevant = deserialize_relevant_instructions(
+            &program_id,
+            &account_keys,
+            [&finalize_blobs].into_iter(),
+            BLOB_ACCOUNT_INSTRUCTION_IDX,
+            BLOB_BLOBER_INSTRUCTION_IDX,
+        );
+        assert_eq!(
+            relevant
+                .iter()
+                .map(|instruction| instruction.blob)
+                .collect::<Vec<_>>(),
+            blobs
+        );
+        assert!(relevant.iter().all(|instruction| {
+            instruction.blober == blober
+                && matches!(
+                    instruction.instruction,
+                    RelevantInstruction::FinalizeBlob(_)
+                )
+        }));
+    }
 }
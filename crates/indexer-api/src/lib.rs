@@ -1,8 +1,10 @@
 #![doc = include_str!("../README.md")]
 
 mod indexing;
+mod pagination;
 mod rpc;
 
 pub use data_anchor_proofs::compound::CompoundInclusionProof;
 pub use indexing::*;
+pub use pagination::*;
 pub use rpc::*;
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+/// The largest page the indexer returns, regardless of the requested limit.
+pub const MAX_PAGE_LIMIT: u32 = 1000;
+
+/// A request for a single page of results.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PageRequest {
+    /// The maximum number of items to return. Values outside 1 to [`MAX_PAGE_LIMIT`] aren't
+    /// rejected but clamped, so 0 returns a single item, see [`PageRequest::limit`].
+    pub limit: u32,
+    /// The opaque cursor returned with the previous page, or `None` for the first page.
+    pub cursor: Option<String>,
+}
+
+/// A page of results, with the cursor to request the next one if there are more results.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// The items in this page.
+    pub items: Vec<T>,
+    /// The cursor for the next page, or `None` if this is the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// Errors that can occur when resolving a [`PageRequest`].
+#[derive(Debug, thiserror::Error)]
+pub enum PageError {
+    /// The cursor wasn't returned by the indexer
+    #[error("Invalid page cursor: {0}")]
+    InvalidCursor(String),
+}
+
+impl PageRequest {
+    /// Requests the first page with up to `limit` items.
+    pub fn first(limit: u32) -> Self {
+        Self {
+            limit,
+            cursor: None,
+        }
+    }
+
+    /// Requests the page after `page`, or returns `None` if `page` was the last one.
+    pub fn next<T>(&self, page: &Page<T>) -> Option<Self> {
+        page.next_cursor.clone().map(|cursor| Self {
+            limit: self.limit,
+            cursor: Some(cursor),
+        })
+    }
+
+    /// The number of items to return, with the requested limit clamped to a valid value.
+    pub fn limit(&self) -> usize {
+        self.limit.clamp(1, MAX_PAGE_LIMIT) as usize
+    }
+
+    /// The number of items to skip before this page.
+    pub fn offset(&self) -> Result<usize, PageError> {
+        self.cursor.as_deref().map_or(Ok(0), |cursor| {
+            cursor
+                .parse()
+                .map_err(|_| PageError::InvalidCursor(cursor.to_owned()))
+        })
+    }
+
+    /// Cuts this page out of all results. The results must be in the same order for every page.
+    pub fn paginate<T>(&self, items: impl IntoIterator<Item = T>) -> Result<Page<T>, PageError> {
+        let offset = self.offset()?;
+        let limit = self.limit();
+
+        // Take one more item than needed to know whether there is a next page.
+        let mut items: Vec<_> = items.into_iter().skip(offset).take(limit + 1).collect();
+        let next_cursor = (items.len() > limit).then(|| {
+            items.truncate(limit);
+            (offset + limit).to_string()
+        });
+
+        Ok(Page { items, next_cursor })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_page() {
+        let page = PageRequest::first(3).paginate(0..10).unwrap();
+        assert_eq!(page.items, vec![0, 1, 2]);
+        assert!(page.next_cursor.is_some());
+    }
+
+    #[test]
+    fn middle_page() {
+        let request = PageRequest::first(3);
+        let first = request.paginate(0..10).unwrap();
+        let second = request.next(&first).unwrap().paginate(0..10).unwrap();
+        assert_eq!(second.items, vec![3, 4, 5]);
+        assert!(second.next_cursor.is_some());
+    }
+
+    #[test]
+    fn exhaustion() {
+        let mut request = Some(PageRequest::first(4));
+        let mut items = Vec::new();
+        let mut pages = 0;
+        while let Some(current) = request {
+            let page = current.paginate(0..10).unwrap();
+            items.extend(page.items.iter().copied());
+            pages += 1;
+            request = current.next(&page);
+        }
+
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+        assert_eq!(pages, 3);
+
+        // A page that ends exactly at the last item has no next cursor either.
+        let page = PageRequest::first(10).paginate(0..10).unwrap();
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[test]
+    fn limit_is_clamped() {
+        assert_eq!(PageRequest::first(0).limit(), 1);
+        assert_eq!(
+            PageRequest::first(MAX_PAGE_LIMIT + 1).limit(),
+            MAX_PAGE_LIMIT as usize
+        );
+
+        let page = PageRequest::first(0).paginate(0..10).unwrap();
+        assert_eq!(page.items, vec![0]);
+    }
+
+    #[test]
+    fn invalid_cursor() {
+        let request = PageRequest {
+            limit: 3,
+            cursor: Some("not a cursor".to_owned()),
+        };
+        assert!(matches!(
+            request.paginate(0..10),
+            Err(PageError::InvalidCursor(_))
+        ));
+    }
+}
@@ -1,4 +1,8 @@
-use std::collections::HashSet;
+use std::{
+    collections::{BTreeMap, HashSet},
+    ops::RangeInclusive,
+    time::Instant,
+};
 
 use anchor_lang::prelude::Pubkey;
 use chrono::{DateTime, Utc};
@@ -11,6 +15,8 @@ use jsonrpsee::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::{Page, PageRequest};
+
 /// A data structure representing a blober's information, including the blober's pubkey, the
 /// payer's pubkey, and the network of the blober.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -44,6 +50,94 @@ impl TimeRange {
     }
 }
 
+/// The largest number of slots a [`BlobsBySlotRange`] request can span.
+pub const MAX_SLOT_RANGE_SPAN: u64 = 1000;
+
+/// The largest number of slots a single `get_proofs_for_slots` call can request.
+pub const MAX_PROOF_SLOTS_PER_CALL: usize = 100;
+
+/// A request for the blobs of a blober finalized in a range of slots.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BlobsBySlotRange {
+    /// The blober the blobs were uploaded to.
+    #[serde(with = "pubkey_with_str")]
+    pub blober: Pubkey,
+    /// The first slot of the range, inclusive.
+    pub start_slot: u64,
+    /// The last slot of the range, inclusive.
+    pub end_slot: u64,
+}
+
+/// Errors that can occur when validating a [`BlobsBySlotRange`] request.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SlotRangeError {
+    /// The end slot is before the start slot
+    #[error("End slot {end_slot} is before start slot {start_slot}")]
+    Inverted { start_slot: u64, end_slot: u64 },
+    /// The range spans more slots than allowed
+    #[error("Slot range spans {0} slots, the maximum is {MAX_SLOT_RANGE_SPAN}")]
+    TooLarge(u64),
+}
+
+impl BlobsBySlotRange {
+    /// Checks that the range isn't inverted and spans at most [`MAX_SLOT_RANGE_SPAN`] slots.
+    pub fn validate(&self) -> Result<(), SlotRangeError> {
+        if self.end_slot < self.start_slot {
+            return Err(SlotRangeError::Inverted {
+                start_slot: self.start_slot,
+                end_slot: self.end_slot,
+            });
+        }
+        let span = (self.end_slot - self.start_slot).saturating_add(1);
+        if span > MAX_SLOT_RANGE_SPAN {
+            return Err(SlotRangeError::TooLarge(span));
+        }
+        Ok(())
+    }
+
+    /// The slots covered by the request.
+    pub fn slots(&self) -> RangeInclusive<u64> {
+        self.start_slot..=self.end_slot
+    }
+
+    /// Groups blobs by the slot they were finalized in, keeping only slots within the range.
+    pub fn group_by_slot(
+        &self,
+        blobs: impl IntoIterator<Item = (u64, Vec<u8>)>,
+    ) -> BTreeMap<u64, Vec<Vec<u8>>> {
+        let mut grouped = BTreeMap::<_, Vec<_>>::new();
+        for (slot, blob) in blobs {
+            if self.slots().contains(&slot) {
+                grouped.entry(slot).or_default().push(blob);
+            }
+        }
+        grouped
+    }
+}
+
+/// The health of an indexer instance, returned by its `health` method.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct IndexerHealth {
+    /// The version of the indexer API the instance was built with.
+    pub version: String,
+    /// The highest slot the instance has indexed.
+    pub indexed_tip_slot: u64,
+    /// How long the instance has been running, in seconds.
+    pub uptime_secs: u64,
+}
+
+impl IndexerHealth {
+    /// Reports the health of an instance that started at `started_at` and has indexed up to
+    /// `indexed_tip_slot`.
+    pub fn new(indexed_tip_slot: u64, started_at: Instant) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            indexed_tip_slot,
+            uptime_secs: started_at.elapsed().as_secs(),
+        }
+    }
+}
+
 /// A wrapper around a blober's pubkey, used to identify a blober in RPC calls.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct PubkeyFromStr(#[serde(with = "pubkey_with_str")] pub Pubkey);
@@ -60,25 +154,80 @@ impl From<Pubkey> for PubkeyFromStr {
     }
 }
 
+/// The proof system used to wrap the SP1 proof, which determines the verifying key the verifier
+/// must use.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProofSystem {
+    /// Groth16 proofs, which are [`GROTH16_PROOF_SIZE`] bytes long.
+    #[default]
+    Groth16,
+    /// PLONK proofs, which are larger but don't require a trusted setup.
+    Plonk,
+}
+
 /// Data structure to hold the proof data
 #[serde_with::serde_as]
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProofData {
-    /// The Groth16 proof bytes
+    /// The proof bytes, [`GROTH16_PROOF_SIZE`] bytes long for Groth16 proofs
     #[serde_as(as = "serde_with::Bytes")]
-    pub proof: [u8; GROTH16_PROOF_SIZE],
+    pub proof: Vec<u8>,
     /// The public values from the proof
     pub public_values: Vec<u8>,
     /// The verification key bytes in hex encoding with a leading "0x"
     pub verification_key: String,
+    /// The proof system the proof was generated with
+    #[serde(default)]
+    pub proof_system: ProofSystem,
+}
+
+/// Errors that can occur when reading a [`ProofData`] as a Groth16 proof.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Groth16ProofError {
+    /// The proof was generated with a different proof system
+    #[error("Expected a {:?} proof, got {0:?}", ProofSystem::Groth16)]
+    ProofSystem(ProofSystem),
+    /// The proof isn't [`GROTH16_PROOF_SIZE`] bytes long
+    #[error("Expected {GROTH16_PROOF_SIZE} proof bytes, got {0}")]
+    Length(usize),
+}
+
+impl ProofData {
+    /// Creates the proof data for a Groth16 proof.
+    pub fn groth16(
+        proof: [u8; GROTH16_PROOF_SIZE],
+        public_values: Vec<u8>,
+        verification_key: String,
+    ) -> Self {
+        Self {
+            proof: proof.to_vec(),
+            public_values,
+            verification_key,
+            proof_system: ProofSystem::Groth16,
+        }
+    }
+
+    /// Returns the proof bytes, checking that this is a Groth16 proof of [`GROTH16_PROOF_SIZE`]
+    /// bytes.
+    pub fn groth16_proof(&self) -> Result<[u8; GROTH16_PROOF_SIZE], Groth16ProofError> {
+        if self.proof_system != ProofSystem::Groth16 {
+            return Err(Groth16ProofError::ProofSystem(self.proof_system));
+        }
+        self.proof
+            .as_slice()
+            .try_into()
+            .map_err(|_| Groth16ProofError::Length(self.proof.len()))
+    }
 }
 
 /// The Indexer RPC interface.
 #[rpc(server, client)]
 pub trait IndexerRpc {
-    /// Check the health of the RPC server. Returns an error if the server is not healthy.
+    /// Check the health of the RPC server, returning its version, indexed tip and uptime. Returns an
+    /// error if the server is not healthy.
     #[method(name = "health")]
-    async fn health(&self) -> RpcResult<()>;
+    async fn health(&self) -> RpcResult<IndexerHealth>;
 
     /// Retrieve a list of blobs for a given slot and blober pubkey. Returns an error if there was a
     /// database or RPC failure, and None if the slot has not been completed yet. If the slot is
@@ -86,6 +235,20 @@ pub trait IndexerRpc {
     #[method(name = "get_blobs")]
     async fn get_blobs(&self, blober: PubkeyFromStr, slot: u64) -> RpcResult<Option<Vec<Vec<u8>>>>;
 
+    /// Retrieve the highest slot the indexer has processed for a given blober pubkey. Returns an
+    /// error if there was a database or RPC failure, and None if the blober is unknown.
+    #[method(name = "get_latest_indexed_slot")]
+    async fn get_latest_indexed_slot(&self, blober: PubkeyFromStr) -> RpcResult<Option<u64>>;
+
+    /// Retrieve the blobs for a given blober pubkey finalized in a range of slots, grouped by slot.
+    /// Slots without blobs are left out. Returns an error if there was a database or RPC failure,
+    /// or if the range is inverted or spans more than [`MAX_SLOT_RANGE_SPAN`] slots.
+    #[method(name = "get_blobs_by_slot_range")]
+    async fn get_blobs_by_slot_range(
+        &self,
+        request: BlobsBySlotRange,
+    ) -> RpcResult<BTreeMap<u64, Vec<Vec<u8>>>>;
+
     /// Retrieve a list of blobs for a given blober pubkey and time range. Returns an error if there
     /// was a database or RPC failure, and an empty list if no blobs were found.
     #[method(name = "get_blobs_by_blober")]
@@ -105,6 +268,19 @@ pub trait IndexerRpc {
         time_range: Option<TimeRange>,
     ) -> RpcResult<Vec<Vec<u8>>>;
 
+    /// Retrieve a page of blobs for a given payer pubkey, network ID, and time range. The next page
+    /// is requested with the cursor returned in the page. The page limit is clamped to between 1 and
+    /// [`crate::MAX_PAGE_LIMIT`], see [`PageRequest::limit`]. Returns an error if there was a
+    /// database or RPC failure, or if the cursor is invalid.
+    #[method(name = "get_blobs_by_payer_paginated")]
+    async fn get_blobs_by_payer_paginated(
+        &self,
+        payer: PubkeyFromStr,
+        network_name: String,
+        time_range: Option<TimeRange>,
+        page: PageRequest,
+    ) -> RpcResult<Page<Vec<u8>>>;
+
     /// Retrieve a list of blobs for a given network name and time range. Returns an error if there
     /// was a database or RPC failure, and an empty list if no blobs were found.
     #[method(name = "get_blobs_by_network")]
@@ -139,6 +315,17 @@ pub trait IndexerRpc {
         slot: u64,
     ) -> RpcResult<Option<CompoundInclusionProof>>;
 
+    /// Retrieve proofs for several slots of a given blober pubkey. The result has an entry for each
+    /// requested slot, in the same order, with None for slots that have no proof. Returns an error
+    /// if there was a database or RPC failure, or if more than [`MAX_PROOF_SLOTS_PER_CALL`] slots
+    /// were requested.
+    #[method(name = "get_proofs_for_slots")]
+    async fn get_proofs_for_slots(
+        &self,
+        blober: PubkeyFromStr,
+        slots: Vec<u64>,
+    ) -> RpcResult<Vec<(u64, Option<CompoundInclusionProof>)>>;
+
     /// Retrieve a compound proof that covers a particular blob. Returns an error if there was a
     /// database or RPC failure, and None if the blob does not exist.
     #[deprecated(since = "0.4.3", note = "please use `checkpoint_proof` instead")]
@@ -148,6 +335,17 @@ pub trait IndexerRpc {
         blob_address: PubkeyFromStr,
     ) -> RpcResult<Option<CompoundInclusionProof>>;
 
+    /// Retrieve the address of a finalized blob for a given blober pubkey whose content hash, the
+    /// SHA-256 hash of the blob as uploaded (after encoding and compression), matches the given
+    /// one. Returns an error if there was a database or RPC failure, and None if no such blob was
+    /// found.
+    #[method(name = "find_blob_by_content_hash")]
+    async fn find_blob_by_content_hash(
+        &self,
+        blober: PubkeyFromStr,
+        content_hash: [u8; 32],
+    ) -> RpcResult<Option<PubkeyFromStr>>;
+
     /// Listen to blob finalization events from specified blobers. This will return a stream of
     /// slots and blober PDAs that have finalized blobs. The stream will be closed when the RPC server is
     /// shut down.
@@ -187,6 +385,14 @@ impl CustomerElf {
             CustomerElf::PobSla => data_anchor_pob_sla_verifier::id(),
         }
     }
+
+    /// Returns the elf whose verifier program is the given checkpoint authority, if any.
+    pub fn from_authority(authority: Pubkey) -> Option<Self> {
+        Self::value_variants()
+            .iter()
+            .copied()
+            .find(|elf| elf.authority() == authority)
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -310,3 +516,103 @@ pub mod pubkey_with_str {
         serializer.serialize_str(&pubkey.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(start_slot: u64, end_slot: u64) -> BlobsBySlotRange {
+        BlobsBySlotRange {
+            blober: Pubkey::new_unique(),
+            start_slot,
+            end_slot,
+        }
+    }
+
+    #[test]
+    fn health_reports_crate_version() {
+        let health = IndexerHealth::new(42, Instant::now());
+        assert_eq!(health.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(health.indexed_tip_slot, 42);
+    }
+
+    #[test]
+    fn groth16_proof_data_round_trips() {
+        let proof = [7; GROTH16_PROOF_SIZE];
+        let proof_data = ProofData::groth16(proof, vec![1, 2, 3], "0x00".to_owned());
+        assert_eq!(proof_data.groth16_proof(), Ok(proof));
+    }
+
+    #[test]
+    fn groth16_proof_checks_system_and_length() {
+        let proof_data = ProofData::groth16([7; GROTH16_PROOF_SIZE], Vec::new(), String::new());
+
+        let short = ProofData {
+            proof: vec![7; GROTH16_PROOF_SIZE - 1],
+            ..proof_data.clone()
+        };
+        assert_eq!(
+            short.groth16_proof(),
+            Err(Groth16ProofError::Length(GROTH16_PROOF_SIZE - 1))
+        );
+
+        let plonk = ProofData {
+            proof_system: ProofSystem::Plonk,
+            ..proof_data
+        };
+        assert_eq!(
+            plonk.groth16_proof(),
+            Err(Groth16ProofError::ProofSystem(ProofSystem::Plonk))
+        );
+    }
+
+    #[test]
+    fn empty_slot_range() {
+        let request = request(10, 20);
+        request.validate().unwrap();
+
+        let grouped = request.group_by_slot([(9, vec![1]), (21, vec![2])]);
+        assert!(grouped.is_empty());
+    }
+
+    #[test]
+    fn populated_slot_range() {
+        let request = request(10, 20);
+        request.validate().unwrap();
+
+        let grouped =
+            request.group_by_slot([(10, vec![1]), (15, vec![2]), (10, vec![3]), (20, vec![4])]);
+        assert_eq!(
+            grouped,
+            BTreeMap::from([
+                (10, vec![vec![1], vec![3]]),
+                (15, vec![vec![2]]),
+                (20, vec![vec![4]]),
+            ])
+        );
+    }
+
+    #[test]
+    fn inverted_slot_range() {
+        assert_eq!(
+            request(20, 10).validate(),
+            Err(SlotRangeError::Inverted {
+                start_slot: 20,
+                end_slot: 10
+            })
+        );
+    }
+
+    #[test]
+    fn slot_range_span_is_capped() {
+        request(0, MAX_SLOT_RANGE_SPAN - 1).validate().unwrap();
+        assert_eq!(
+            request(0, MAX_SLOT_RANGE_SPAN).validate(),
+            Err(SlotRangeError::TooLarge(MAX_SLOT_RANGE_SPAN + 1))
+        );
+        assert_eq!(
+            request(0, u64::MAX).validate(),
+            Err(SlotRangeError::TooLarge(u64::MAX))
+        );
+    }
+}
@@ -3,7 +3,7 @@
 use std::{cmp::min, fmt::Debug};
 
 use anchor_lang::solana_program::hash::{self, HASH_BYTES, Hash};
-use data_anchor_blober::{CHUNK_SIZE, compute_blob_digest};
+use data_anchor_blober::{CHUNK_SIZE, compute_blob_digest, hash_leaf, initial_hash};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -37,6 +37,8 @@ pub enum BlobProofError {
         expected: [u8; HASH_BYTES],
         found: [u8; HASH_BYTES],
     },
+    #[error("Chunks were not inserted in sequential index order.")]
+    ChunksOutOfOrder,
 }
 
 pub type BlobProofResult<T = ()> = Result<T, BlobProofError>;
@@ -62,6 +64,10 @@ impl BlobProof {
     }
 
     /// Verifies that the given blob matches the proof.
+    ///
+    /// Chunks may have been inserted in any order, the order recorded in the proof is used to
+    /// recompute the digest. Use [`Self::verify_strict`] to additionally require that the chunks
+    /// were inserted sequentially.
     pub fn verify(&self, blob: &[u8]) -> BlobProofResult {
         let chunks = self
             .chunk_order
@@ -88,6 +94,79 @@ impl BlobProof {
             })
         }
     }
+
+    /// Verifies that the given blob matches the proof and that its chunks were inserted in index
+    /// order, i.e. `0, 1, ..., n - 1`.
+    ///
+    /// Unlike [`Self::verify`], which accepts any insertion order, this recomputes the digest
+    /// assuming sequential chunk indices and fails with [`BlobProofError::ChunksOutOfOrder`] if it
+    /// doesn't match the digest in the proof.
+    pub fn verify_strict(&self, blob: &[u8]) -> BlobProofResult {
+        self.verify(blob)?;
+
+        let chunks = blob
+            .chunks(CHUNK_SIZE as usize)
+            .enumerate()
+            .map(|(i, chunk)| (i as u16, chunk))
+            .collect::<Vec<_>>();
+
+        if compute_blob_digest(&chunks) == self.digest {
+            Ok(())
+        } else {
+            Err(BlobProofError::ChunksOutOfOrder)
+        }
+    }
+}
+
+/// Verifies a blob against its expected digest as its chunks arrive, without buffering the whole
+/// blob in memory.
+///
+/// Chunks must be pushed in index order, starting from `0`. Each chunk is folded into a running
+/// digest with [`hash_leaf`], the same way [`compute_blob_digest`] does for a sequentially
+/// uploaded blob, and [`Self::finish`] compares the result against the expected digest.
+#[derive(Debug, Clone)]
+pub struct StreamingBlobVerifier {
+    expected: [u8; HASH_BYTES],
+    digest: [u8; HASH_BYTES],
+    next_index: u16,
+}
+
+impl StreamingBlobVerifier {
+    /// Creates a verifier for a blob with the given expected digest.
+    pub fn new(expected: [u8; HASH_BYTES]) -> Self {
+        Self {
+            expected,
+            digest: initial_hash(),
+            next_index: 0,
+        }
+    }
+
+    /// Adds the next chunk to the running digest. Fails with [`BlobProofError::ChunksOutOfOrder`]
+    /// if `index` isn't the index following the previously pushed chunk.
+    pub fn push_chunk(&mut self, index: u16, data: &[u8]) -> BlobProofResult {
+        if index != self.next_index {
+            return Err(BlobProofError::ChunksOutOfOrder);
+        }
+
+        self.digest = hash_leaf(self.digest, index, data);
+        self.next_index = self
+            .next_index
+            .checked_add(1)
+            .ok_or(BlobProofError::InvalidStructure)?;
+        Ok(())
+    }
+
+    /// Compares the digest of all pushed chunks against the expected digest.
+    pub fn finish(self) -> BlobProofResult {
+        if self.digest == self.expected {
+            Ok(())
+        } else {
+            Err(BlobProofError::DigestMismatch {
+                expected: self.expected,
+                found: self.digest,
+            })
+        }
+    }
 }
 
 #[cfg(test)]
@@ -127,6 +206,53 @@ mod tests {
         .size_max(100_000_000);
     }
 
+    #[test]
+    fn strict_proof_in_order() {
+        arbtest(|u| {
+            let data = u.arbitrary::<Vec<u8>>()?;
+            if data.is_empty() {
+                // Empty blob, invalid test.
+                return Ok(());
+            }
+            let chunks = data
+                .chunks(CHUNK_SIZE as usize)
+                .enumerate()
+                .map(|(i, c)| (i as u16, c))
+                .collect::<Vec<_>>();
+            let proof = BlobProof::new(&chunks);
+            proof.verify(&data).unwrap();
+            proof.verify_strict(&data).unwrap();
+            Ok(())
+        })
+        .size_max(100_000_000);
+    }
+
+    #[test]
+    fn strict_proof_out_of_order() {
+        arbtest(|u| {
+            let data = u.arbitrary::<Vec<u8>>()?;
+            if data.len() <= CHUNK_SIZE as usize {
+                // At least two chunks are needed to reorder them.
+                return Ok(());
+            }
+            let mut chunks = data
+                .chunks(CHUNK_SIZE as usize)
+                .enumerate()
+                .map(|(i, c)| (i as u16, c))
+                .collect::<Vec<_>>();
+            let other = 1 + u.choose_index(chunks.len() - 1)?;
+            chunks.swap(0, other);
+            let proof = BlobProof::new(&chunks);
+            proof.verify(&data).unwrap();
+            assert_eq!(
+                proof.verify_strict(&data),
+                Err(BlobProofError::ChunksOutOfOrder)
+            );
+            Ok(())
+        })
+        .size_max(100_000_000);
+    }
+
     #[test]
     fn false_proof() {
         arbtest(|u| {
@@ -155,4 +281,62 @@ mod tests {
         })
         .size_max(100_000_000);
     }
+
+    #[test]
+    fn streaming_verifier_matches_digest() {
+        arbtest(|u| {
+            let data = u.arbitrary::<Vec<u8>>()?;
+            let chunks = data
+                .chunks(CHUNK_SIZE as usize)
+                .enumerate()
+                .map(|(i, c)| (i as u16, c))
+                .collect::<Vec<_>>();
+            let proof = BlobProof::new(&chunks);
+
+            let mut verifier = StreamingBlobVerifier::new(proof.digest);
+            for (i, chunk) in &chunks {
+                verifier.push_chunk(*i, chunk).unwrap();
+            }
+            verifier.finish().unwrap();
+            Ok(())
+        })
+        .size_max(100_000_000);
+    }
+
+    #[test]
+    fn streaming_verifier_rejects_out_of_order_chunk() {
+        let data = vec![7u8; CHUNK_SIZE as usize * 3];
+        let chunks = data.chunks(CHUNK_SIZE as usize).collect::<Vec<_>>();
+        let proof = BlobProof::new(&[(0, chunks[0]), (1, chunks[1]), (2, chunks[2])]);
+
+        let mut verifier = StreamingBlobVerifier::new(proof.digest);
+        verifier.push_chunk(0, chunks[0]).unwrap();
+        assert_eq!(
+            verifier.push_chunk(2, chunks[2]),
+            Err(BlobProofError::ChunksOutOfOrder)
+        );
+        // The rejected chunk isn't folded in, so the stream can continue in order.
+        verifier.push_chunk(1, chunks[1]).unwrap();
+        verifier.push_chunk(2, chunks[2]).unwrap();
+        verifier.finish().unwrap();
+    }
+
+    #[test]
+    fn streaming_verifier_detects_tampered_chunk() {
+        let data = vec![7u8; CHUNK_SIZE as usize * 2];
+        let chunks = data
+            .chunks(CHUNK_SIZE as usize)
+            .enumerate()
+            .map(|(i, c)| (i as u16, c))
+            .collect::<Vec<_>>();
+        let proof = BlobProof::new(&chunks);
+
+        let mut verifier = StreamingBlobVerifier::new(proof.digest);
+        verifier.push_chunk(0, chunks[0].1).unwrap();
+        verifier.push_chunk(1, &[8u8; 4]).unwrap();
+        assert!(matches!(
+            verifier.finish(),
+            Err(BlobProofError::DigestMismatch { expected, .. }) if expected == proof.digest
+        ));
+    }
 }
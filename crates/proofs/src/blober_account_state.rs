@@ -106,13 +106,28 @@ impl Debug for BlobAccount {
 /// To create this proof, the Blober account's [`data_anchor_blober::blober::finalize_blob`] instruction must
 /// be invoked for each blob whose state should be proven. The starting offset and length of the
 /// "interesting" part of the account data that is to be hashed must also be provided.
-#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct BloberAccountStateProof {
     pub initial_hash: [u8; HASH_BYTES],
     pub initial_slot: Slot,
-    pub uploads: BTreeMap<Slot, Vec<BlobAccount>>,
+    uploads: BTreeMap<Slot, Vec<BlobAccount>>,
+    /// The digest over all uploads so far, maintained by [`Self::append_slot`] and cleared by
+    /// [`Self::uploads_mut`]. Not part of the proof itself, so it's neither serialized nor
+    /// compared.
+    #[serde(skip)]
+    running_hash: Option<[u8; HASH_BYTES]>,
 }
 
+impl PartialEq for BloberAccountStateProof {
+    fn eq(&self, other: &Self) -> bool {
+        self.initial_hash == other.initial_hash
+            && self.initial_slot == other.initial_slot
+            && self.uploads == other.uploads
+    }
+}
+
+impl Eq for BloberAccountStateProof {}
+
 impl Debug for BloberAccountStateProof {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Proof")
@@ -140,13 +155,58 @@ impl BloberAccountStateProof {
             initial_hash,
             initial_slot,
             uploads,
+            running_hash: None,
         }
     }
 
+    /// Appends the blob accounts uploaded in `slot`, which must be after every slot already
+    /// covered by the proof.
+    ///
+    /// The digest is extended from the previous running state instead of being recomputed from
+    /// the initial hash, so a live indexer can keep a proof up to date cheaply. The result is
+    /// identical to constructing the proof from scratch with all the uploads.
+    pub fn append_slot(&mut self, slot: Slot, accounts: Vec<BlobAccount>) {
+        assert!(
+            slot > self.target_slot(),
+            "Appended uploads must be in a slot after the last covered slot"
+        );
+        let running_hash = self.running_hash();
+        self.running_hash = Some(accounts.iter().fold(running_hash, |acc, blob| {
+            merge_hashes(&acc, &blob.hash_blob())
+        }));
+        self.uploads.insert(slot, accounts);
+    }
+
+    /// Returns the digest over all uploads, using the state maintained by [`Self::append_slot`]
+    /// if available.
+    pub fn running_hash(&self) -> [u8; HASH_BYTES] {
+        self.running_hash.unwrap_or_else(|| self.calculate_hash())
+    }
+
+    /// The blob accounts covered by the proof, by the slot they were uploaded in.
+    pub fn uploads(&self) -> &BTreeMap<Slot, Vec<BlobAccount>> {
+        &self.uploads
+    }
+
+    /// Returns the uploads for modification. This discards the state maintained by
+    /// [`Self::append_slot`], so the next [`Self::running_hash`] is calculated from scratch.
+    pub fn uploads_mut(&mut self) -> &mut BTreeMap<Slot, Vec<BlobAccount>> {
+        self.running_hash = None;
+        &mut self.uploads
+    }
+
     pub fn blobs(&self) -> impl Iterator<Item = &BlobAccount> {
         self.uploads.values().flat_map(|blobs| blobs.iter())
     }
 
+    /// Returns the slot and address of every blob account covered by the proof, in the order
+    /// they were hashed.
+    pub fn blob_keys(&self) -> impl Iterator<Item = (Slot, Pubkey)> {
+        self.uploads
+            .iter()
+            .flat_map(|(slot, blobs)| blobs.iter().map(|blob| (*slot, blob.address)))
+    }
+
     pub fn target_slot(&self) -> Slot {
         self.uploads
             .last_key_value()
@@ -165,17 +225,7 @@ impl BloberAccountStateProof {
     }
 
     pub fn verify(&self, blober_account_data: &[u8]) -> BloberAccountStateResult {
-        let mut data = blober_account_data;
-
-        let state = Blober::try_deserialize(&mut data).map_err(|e| match e {
-            Error::AnchorError(anchor_error)
-                if anchor_error.error_code_number
-                    == ErrorCode::AccountDiscriminatorMismatch as u32 =>
-            {
-                BloberAccountStateError::DiscriminatorMismatch
-            }
-            _ => BloberAccountStateError::InvalidStateData,
-        })?;
+        let state = deserialize_blober(blober_account_data)?;
 
         if let Some((&slot, _)) = self.uploads.last_key_value() {
             if slot != state.slot {
@@ -204,6 +254,20 @@ impl BloberAccountStateProof {
     }
 }
 
+/// Deserializes the account data of a [`Blober`], including its discriminator.
+pub fn deserialize_blober(blober_account_data: &[u8]) -> BloberAccountStateResult<Blober> {
+    let mut data = blober_account_data;
+
+    Blober::try_deserialize(&mut data).map_err(|e| match e {
+        Error::AnchorError(anchor_error)
+            if anchor_error.error_code_number == ErrorCode::AccountDiscriminatorMismatch as u32 =>
+        {
+            BloberAccountStateError::DiscriminatorMismatch
+        }
+        _ => BloberAccountStateError::InvalidStateData,
+    })
+}
+
 pub fn get_blober_hash(blober_account_data: &[u8]) -> BloberAccountStateResult<[u8; HASH_BYTES]> {
     if &blober_account_data[..8] != Blober::DISCRIMINATOR {
         return Err(BloberAccountStateError::DiscriminatorMismatch);
@@ -220,6 +284,15 @@ pub fn merge_all_hashes(hashes: impl Iterator<Item = [u8; HASH_BYTES]>) -> [u8;
         .expect("account list to not be empty")
 }
 
+/// Same as [`merge_all_hashes`], but folds over references so callers holding the hashes in a
+/// collection don't need to copy them first.
+pub fn merge_all_hashes_ref<'a>(
+    mut hashes: impl Iterator<Item = &'a [u8; HASH_BYTES]>,
+) -> [u8; HASH_BYTES] {
+    let first = *hashes.next().expect("account list to not be empty");
+    hashes.fold(first, |acc, hash| merge_hashes(&acc, hash))
+}
+
 #[cfg(test)]
 mod tests {
     use anchor_lang::AnchorSerialize;
@@ -243,6 +316,21 @@ mod tests {
         });
     }
 
+    #[test]
+    fn merge_all_hashes_ref_matches_owned() {
+        arbtest(|u| {
+            let mut hashes: Vec<[u8; HASH_BYTES]> = u.arbitrary()?;
+            hashes.push(u.arbitrary()?);
+
+            assert_eq!(
+                merge_all_hashes_ref(hashes.iter()),
+                merge_all_hashes(hashes.iter().cloned())
+            );
+
+            Ok(())
+        });
+    }
+
     #[test]
     #[should_panic]
     fn blobs_before_initial_slot_panics() {
@@ -253,6 +341,100 @@ mod tests {
         );
     }
 
+    #[test]
+    fn blob_keys() {
+        arbtest(|u| {
+            let initial_slot = u.arbitrary::<u32>()? as Slot;
+            let mut expected = Vec::new();
+            let mut uploads = BTreeMap::new();
+            for offset in 1..=u.int_in_range(0..=10)? {
+                let slot = initial_slot + offset;
+                let blob_accounts = (0..u.int_in_range(1..=5)?)
+                    .map(|_| {
+                        let address = u.arbitrary::<ArbKeypair>()?.pubkey();
+                        expected.push((slot, address));
+                        Ok(BlobAccount::new(address, u.arbitrary()?))
+                    })
+                    .collect::<arbitrary::Result<Vec<_>>>()?;
+                uploads.insert(slot, blob_accounts);
+            }
+
+            let proof = BloberAccountStateProof::new(initial_hash(), initial_slot, uploads);
+
+            assert_eq!(proof.blob_keys().collect::<Vec<_>>(), expected);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn append_slot_matches_from_scratch() {
+        arbtest(|u| {
+            let initial_slot = u.arbitrary::<u32>()? as Slot;
+            let mut incremental =
+                BloberAccountStateProof::new(initial_hash(), initial_slot, BTreeMap::new());
+            let mut uploads = BTreeMap::new();
+            let mut slot = initial_slot;
+            for _ in 0..u.int_in_range(1..=10)? {
+                slot += u.int_in_range(1..=5)?;
+                let blob_accounts = (0..u.int_in_range(1..=5)?)
+                    .map(|_| {
+                        Ok(BlobAccount::new(
+                            u.arbitrary::<ArbKeypair>()?.pubkey(),
+                            u.arbitrary()?,
+                        ))
+                    })
+                    .collect::<arbitrary::Result<Vec<_>>>()?;
+                uploads.insert(slot, blob_accounts.clone());
+                incremental.append_slot(slot, blob_accounts);
+            }
+
+            let from_scratch = BloberAccountStateProof::new(initial_hash(), initial_slot, uploads);
+
+            assert_eq!(incremental, from_scratch);
+            assert_eq!(incremental.running_hash(), from_scratch.calculate_hash());
+
+            // Modifying the uploads directly doesn't leave a stale running hash behind.
+            let mut modified = incremental.clone();
+            modified.uploads_mut().remove(&slot);
+            assert_eq!(modified.running_hash(), modified.calculate_hash());
+            assert_ne!(modified.running_hash(), incremental.running_hash());
+            assert_eq!(
+                bincode::serialize(&incremental).unwrap(),
+                bincode::serialize(&from_scratch).unwrap()
+            );
+
+            let blober_account_data: Vec<u8> = [
+                Blober::DISCRIMINATOR.to_vec(),
+                Blober {
+                    slot,
+                    hash: from_scratch.calculate_hash(),
+                    caller: u.arbitrary::<ArbKeypair>()?.pubkey().to_bytes().into(),
+                    namespace: u.arbitrary()?,
+                }
+                .try_to_vec()
+                .unwrap(),
+            ]
+            .concat();
+
+            incremental.verify(&blober_account_data).unwrap();
+            from_scratch.verify(&blober_account_data).unwrap();
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn append_slot_before_target_slot_panics() {
+        let mut proof = BloberAccountStateProof::new(
+            initial_hash(),
+            1,
+            BTreeMap::from([(3, vec![BlobAccount::new(Pubkey::default(), vec![0; 10])])]),
+        );
+        proof.append_slot(2, vec![BlobAccount::new(Pubkey::default(), vec![0; 10])]);
+    }
+
     #[test]
     fn single_account() {
         arbtest(|u| {
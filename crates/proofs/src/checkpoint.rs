@@ -0,0 +1,128 @@
+//! Off-chain verification of checkpoint proofs, reproducing the checks the on-chain verifier
+//! programs and the blober program's `create_checkpoint` instruction perform.
+
+use anchor_lang::prelude::Pubkey;
+use data_anchor_blober::{GROTH16_PROOF_SIZE, checkpoint::Checkpoint};
+use thiserror::Error;
+
+/// Failures that can occur when verifying a checkpoint off-chain.
+#[derive(Debug, Error)]
+pub enum CheckpointVerificationError {
+    #[error("Invalid checkpoint: {0}")]
+    InvalidCheckpoint(anchor_lang::error::Error),
+    #[error("Checkpoint is for blober {found}, expected {expected}")]
+    BloberMismatch { expected: Pubkey, found: Pubkey },
+    #[error("Groth16 proof verification failed")]
+    ProofVerificationFailed,
+}
+
+pub type CheckpointVerificationResult<T = ()> = Result<T, CheckpointVerificationError>;
+
+/// Verifies a checkpoint proof the same way it would be verified on-chain.
+///
+/// The public values and verification key must have valid sizes, the blober committed to in the
+/// public values must be `expected_blober`, and the Groth16 proof must verify against the SP1
+/// verification key. Checks against the previously stored checkpoint (slot and hash continuity)
+/// require on-chain state and are not performed.
+pub fn verify_checkpoint_offchain(
+    proof: [u8; GROTH16_PROOF_SIZE],
+    public_values: Vec<u8>,
+    verification_key: String,
+    expected_blober: Pubkey,
+) -> CheckpointVerificationResult {
+    // The slot isn't part of the proof, so it doesn't affect verification.
+    let checkpoint = Checkpoint::new(proof, public_values, verification_key, 0)
+        .map_err(CheckpointVerificationError::InvalidCheckpoint)?;
+
+    let blober = checkpoint
+        .blober()
+        .map_err(CheckpointVerificationError::InvalidCheckpoint)?;
+    if blober != expected_blober {
+        return Err(CheckpointVerificationError::BloberMismatch {
+            expected: expected_blober,
+            found: blober,
+        });
+    }
+
+    checkpoint
+        .verify_zk_proof()
+        .map_err(|_| CheckpointVerificationError::ProofVerificationFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::solana_program::hash::HASH_BYTES;
+    use data_anchor_blober::PROOF_VERIFICATION_KEY_SIZE;
+
+    use super::*;
+
+    fn public_values(blober: Pubkey) -> Vec<u8> {
+        [
+            blober.to_bytes().as_slice(),
+            &[1; HASH_BYTES],
+            &[2; HASH_BYTES],
+        ]
+        .concat()
+    }
+
+    fn verification_key() -> String {
+        format!("0x{}", "0".repeat(PROOF_VERIFICATION_KEY_SIZE - 2))
+    }
+
+    #[test]
+    fn matching_blober_reaches_proof_verification() {
+        let blober = Pubkey::new_unique();
+
+        // Without a real Groth16 proof the blober check passes and only the proof is rejected.
+        let err = verify_checkpoint_offchain(
+            [0; GROTH16_PROOF_SIZE],
+            public_values(blober),
+            verification_key(),
+            blober,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, CheckpointVerificationError::ProofVerificationFailed),
+            "Expected ProofVerificationFailed, got {err:?}"
+        );
+    }
+
+    #[test]
+    fn blober_mismatch() {
+        let blober = Pubkey::new_unique();
+        let expected = Pubkey::new_unique();
+
+        let err = verify_checkpoint_offchain(
+            [0; GROTH16_PROOF_SIZE],
+            public_values(blober),
+            verification_key(),
+            expected,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                CheckpointVerificationError::BloberMismatch { expected: e, found }
+                    if e == expected && found == blober
+            ),
+            "Expected BloberMismatch, got {err:?}"
+        );
+    }
+
+    #[test]
+    fn invalid_verification_key_size() {
+        let blober = Pubkey::new_unique();
+
+        let err = verify_checkpoint_offchain(
+            [0; GROTH16_PROOF_SIZE],
+            public_values(blober),
+            "0x00".to_owned(),
+            blober,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, CheckpointVerificationError::InvalidCheckpoint(_)),
+            "Expected InvalidCheckpoint, got {err:?}"
+        );
+    }
+}
@@ -1,13 +1,17 @@
 //! This proof module contains the logic for verifying "inclusion" in the sense that a specific
 //! Solana block contains blobs, and that there are no other blobs in the block.
 
-use std::fmt::Debug;
+use std::{collections::BTreeMap, fmt::Debug};
 
 use anchor_lang::{
     prelude::Pubkey,
-    solana_program::hash::{HASH_BYTES, Hash},
+    solana_program::{
+        clock::Slot,
+        hash::{HASH_BYTES, Hash, hashv},
+        pubkey::PUBKEY_BYTES,
+    },
 };
-use data_anchor_blober::hash_blob;
+use data_anchor_blober::{BLOB_ACCOUNT_DATA_LEN, find_blober_address, hash_blob};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -15,9 +19,10 @@ use thiserror::Error;
 use crate::{
     blob::{BlobProof, BlobProofError},
     blober_account_state::{
-        self, BloberAccountStateError, BloberAccountStateProof, BloberAccountStateResult,
-        get_blober_hash, merge_all_hashes,
+        self, BlobAccount, BloberAccountStateError, BloberAccountStateProof,
+        BloberAccountStateResult, get_blober_hash, merge_all_hashes,
     },
+    debug::BloberStateDiff,
 };
 
 /// A proof that a specific Solana block contains blobs, and that there are no other blobs in the block.
@@ -83,6 +88,8 @@ pub enum CompoundInclusionProofError {
     MissingBlobs,
     #[error("The inclusion proof is not for the blober account")]
     IncludedAccountNotBlober,
+    #[error("The proof is for blober {found}, but the namespace derives blober {expected}")]
+    BloberNamespaceMismatch { expected: Pubkey, found: Pubkey },
     #[error(
         "The proof is for a different blockhash than the one provided, expected {expected:?}, found {found:?}"
     )]
@@ -111,6 +118,28 @@ pub enum CompoundInclusionProofError {
     BloberAccountState(#[from] blober_account_state::BloberAccountStateError),
     #[error(transparent)]
     Blob(#[from] BlobProofError),
+    #[error("{source} (blober state differs: {diff})")]
+    WithBloberStateDiff {
+        source: Box<CompoundInclusionProofError>,
+        diff: BloberStateDiff,
+    },
+}
+
+impl CompoundInclusionProofError {
+    /// Attaches the differences between the expected and actual blober states to the error, to
+    /// make mismatches easier to debug.
+    ///
+    /// The error is returned unchanged if either state can't be deserialized, or if the states are
+    /// identical.
+    pub fn with_blober_state_diff(self, expected: &[u8], actual: &[u8]) -> Self {
+        match BloberStateDiff::new(expected, actual) {
+            Ok(diff) if !diff.is_empty() => Self::WithBloberStateDiff {
+                source: Box::new(self),
+                diff,
+            },
+            _ => self,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -204,6 +233,28 @@ impl CompoundInclusionProof {
         merge_all_hashes(self.blob_proofs.iter().map(BlobProof::hash_proof))
     }
 
+    /// Estimates the [`bincode`] serialized size of a proof covering `blob_count` blobs and
+    /// `account_count` blob accounts, without having to construct the proof.
+    ///
+    /// Every blob is assumed to fit in a single chunk and every blob account is assumed to have
+    /// been finalized in its own slot, so the estimate is exact for small blobs and errs on the
+    /// large side when multiple blobs share a slot. Blobs spanning multiple chunks add two bytes
+    /// per extra chunk to the actual size.
+    pub fn estimated_serialized_size(blob_count: usize, account_count: usize) -> usize {
+        // Bincode prefixes every sequence and map with its length as a `u64`.
+        const LEN_PREFIX: usize = size_of::<u64>();
+        const BLOB_PROOF_SIZE: usize = HASH_BYTES + LEN_PREFIX + size_of::<u16>();
+        const BLOB_ACCOUNT_SIZE: usize = PUBKEY_BYTES + LEN_PREFIX + BLOB_ACCOUNT_DATA_LEN as usize;
+        const SLOT_ENTRY_SIZE: usize = size_of::<Slot>() + LEN_PREFIX + BLOB_ACCOUNT_SIZE;
+        const BLOBER_ACCOUNT_STATE_PROOF_SIZE: usize = HASH_BYTES + size_of::<Slot>() + LEN_PREFIX;
+
+        LEN_PREFIX
+            + blob_count * BLOB_PROOF_SIZE
+            + PUBKEY_BYTES
+            + BLOBER_ACCOUNT_STATE_PROOF_SIZE
+            + account_count * SLOT_ENTRY_SIZE
+    }
+
     /// Verifies that a specific Solana block contains the provided blobs, and that no blobs have been excluded.
     #[tracing::instrument(skip_all, err(Debug), fields(blober = %blober))]
     pub fn verify(
@@ -250,16 +301,325 @@ impl CompoundInclusionProof {
 
         Ok(())
     }
+
+    /// Verifies the proof like [`Self::verify`], but derives the blober address from the given
+    /// program ID, payer and namespace instead of taking the raw PDA.
+    pub fn verify_for_namespace(
+        &self,
+        program_id: Pubkey,
+        payer: Pubkey,
+        namespace: &str,
+        blober_state: &[u8],
+        blobs: &[ProofBlob<impl AsRef<[u8]>>],
+    ) -> Result<(), CompoundInclusionProofError> {
+        let blober = find_blober_address(program_id, payer, namespace);
+        if blober != self.blober_pubkey {
+            return Err(CompoundInclusionProofError::BloberNamespaceMismatch {
+                expected: blober,
+                found: self.blober_pubkey,
+            });
+        }
+
+        self.verify(blober, blober_state, blobs)
+    }
+}
+
+/// A proof covering several blobers at once, such as when data spans multiple namespaces, made up
+/// of one independent [`CompoundInclusionProof`] per blober.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AggregateInclusionProof {
+    pub proofs: Vec<CompoundInclusionProof>,
+}
+
+/// Failures that can occur when verifying an [`AggregateInclusionProof`].
+#[derive(Debug, Clone, Error)]
+pub enum AggregateInclusionProofError {
+    #[error(
+        "The number of verify arguments ({args}) does not match the number of proofs ({proofs})"
+    )]
+    InvalidNumberOfArgs { proofs: usize, args: usize },
+    #[error("Blober {0} is covered by more than one proof")]
+    DuplicateBlober(Pubkey),
+    #[error("Proof {index} for blober {blober} is invalid: {source}")]
+    InvalidProof {
+        index: usize,
+        blober: Pubkey,
+        source: CompoundInclusionProofError,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggregateInclusionProofCommitment {
+    /// The initial blober hash of every proof, bound to its blober and folded in proof order.
+    pub blober_initial_hashes: [u8; HASH_BYTES],
+}
+
+impl From<&AggregateInclusionProof> for AggregateInclusionProofCommitment {
+    fn from(aggregate: &AggregateInclusionProof) -> Self {
+        let blober_initial_hashes =
+            aggregate
+                .proofs
+                .iter()
+                .fold([0; HASH_BYTES], |folded, proof| {
+                    hashv(&[
+                        &folded,
+                        proof.blober_pubkey.as_ref(),
+                        &proof.into_commitment().blober_initial_hash,
+                    ])
+                    .to_bytes()
+                });
+
+        Self {
+            blober_initial_hashes,
+        }
+    }
+}
+
+impl AggregateInclusionProof {
+    /// Creates an aggregate proof out of one inclusion proof per blober.
+    pub fn new(proofs: Vec<CompoundInclusionProof>) -> Self {
+        Self { proofs }
+    }
+
+    pub fn into_commitment(&self) -> AggregateInclusionProofCommitment {
+        AggregateInclusionProofCommitment::from(self)
+    }
+
+    /// Verifies every constituent proof against the [`VerifyArgs`] at the same index, and that no
+    /// blober is covered by more than one proof.
+    pub fn verify_all(&self, args: &[VerifyArgs]) -> Result<(), AggregateInclusionProofError> {
+        if args.len() != self.proofs.len() {
+            return Err(AggregateInclusionProofError::InvalidNumberOfArgs {
+                proofs: self.proofs.len(),
+                args: args.len(),
+            });
+        }
+
+        if let Some(blober) = self
+            .proofs
+            .iter()
+            .map(|proof| proof.blober_pubkey)
+            .duplicates()
+            .next()
+        {
+            return Err(AggregateInclusionProofError::DuplicateBlober(blober));
+        }
+
+        for (index, (proof, args)) in self.proofs.iter().zip_eq(args).enumerate() {
+            proof
+                .verify(args.blober, &args.blober_state, &args.blobs)
+                .map_err(|source| AggregateInclusionProofError::InvalidProof {
+                    index,
+                    blober: proof.blober_pubkey,
+                    source,
+                })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The current version of the compact binary format produced by
+/// [`CompoundInclusionProof::to_compact_bytes`].
+pub const COMPACT_FORMAT_VERSION: u8 = 1;
+
+/// Failures that can occur when decoding a [`CompoundInclusionProof`] from its compact binary
+/// representation.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CompactEncodingError {
+    #[error("Unsupported compact format version {0}, expected {COMPACT_FORMAT_VERSION}")]
+    UnsupportedVersion(u8),
+    #[error("Unexpected end of input while decoding the compact format")]
+    UnexpectedEnd,
+    #[error("Variable length integer does not fit into 64 bits")]
+    VarintOverflow,
+    #[error("Found {0} trailing bytes after decoding the compact format")]
+    TrailingBytes(usize),
+    #[error("Slot delta {delta} after slot {previous_slot} does not lead to a later slot")]
+    NonIncreasingSlot { previous_slot: Slot, delta: u64 },
+}
+
+pub type CompactEncodingResult<T = ()> = Result<T, CompactEncodingError>;
+
+impl CompoundInclusionProof {
+    /// Serializes the proof into a compact binary representation.
+    ///
+    /// The format starts with a [version byte][`COMPACT_FORMAT_VERSION`], all lengths are encoded
+    /// as LEB128 variable length integers and the upload slots of the
+    /// [`BloberAccountStateProof`] are delta-encoded relative to the previous slot (starting from
+    /// the initial slot). The blob accounts of each slot are only length-prefixed, their addresses
+    /// and data are stored as-is. This is considerably smaller than the flat [`bincode`] encoding,
+    /// which spends eight bytes on every length and slot.
+    ///
+    /// Every slot must come after the previous one, so each proof has exactly one encoding.
+    pub fn to_compact_bytes(&self) -> Vec<u8> {
+        let mut out = vec![COMPACT_FORMAT_VERSION];
+
+        write_varint(&mut out, self.blob_proofs.len() as u64);
+        for blob_proof in &self.blob_proofs {
+            out.extend_from_slice(&blob_proof.digest);
+            write_varint(&mut out, blob_proof.chunk_order.len() as u64);
+            for chunk_index in &blob_proof.chunk_order {
+                out.extend_from_slice(&chunk_index.to_le_bytes());
+            }
+        }
+
+        out.extend_from_slice(self.blober_pubkey.as_ref());
+
+        let state_proof = &self.blober_account_state_proof;
+        out.extend_from_slice(&state_proof.initial_hash);
+        write_varint(&mut out, state_proof.initial_slot);
+        write_varint(&mut out, state_proof.uploads().len() as u64);
+        let mut previous_slot = state_proof.initial_slot;
+        for (slot, blob_accounts) in state_proof.uploads() {
+            // Proofs built through `BloberAccountStateProof::new` only have uploads after the
+            // initial slot. Any other proof wraps around here and is rejected when decoding.
+            write_varint(&mut out, slot.wrapping_sub(previous_slot));
+            previous_slot = *slot;
+
+            write_varint(&mut out, blob_accounts.len() as u64);
+            for blob_account in blob_accounts {
+                out.extend_from_slice(blob_account.address.as_ref());
+                write_varint(&mut out, blob_account.raw_data.len() as u64);
+                out.extend_from_slice(&blob_account.raw_data);
+            }
+        }
+
+        out
+    }
+
+    /// Deserializes a proof previously serialized with [`Self::to_compact_bytes`].
+    pub fn from_compact_bytes(bytes: &[u8]) -> CompactEncodingResult<Self> {
+        let mut reader = CompactReader { bytes };
+
+        let version = reader.read_u8()?;
+        if version != COMPACT_FORMAT_VERSION {
+            return Err(CompactEncodingError::UnsupportedVersion(version));
+        }
+
+        let blob_proof_count = reader.read_len()?;
+        let mut blob_proofs = Vec::with_capacity(blob_proof_count.min(reader.bytes.len()));
+        for _ in 0..blob_proof_count {
+            let digest = reader.read_array()?;
+            let chunk_count = reader.read_len()?;
+            let chunk_order = (0..chunk_count)
+                .map(|_| reader.read_array().map(u16::from_le_bytes))
+                .collect::<CompactEncodingResult<_>>()?;
+            blob_proofs.push(BlobProof {
+                digest,
+                chunk_order,
+            });
+        }
+
+        let blober_pubkey = Pubkey::new_from_array(reader.read_array()?);
+
+        let initial_hash = reader.read_array()?;
+        let initial_slot = reader.read_varint()?;
+        let slot_count = reader.read_len()?;
+        let mut uploads = BTreeMap::new();
+        let mut previous_slot = initial_slot;
+        for _ in 0..slot_count {
+            let delta = reader.read_varint()?;
+            let slot = previous_slot
+                .checked_add(delta)
+                .filter(|_| delta > 0)
+                .ok_or(CompactEncodingError::NonIncreasingSlot {
+                    previous_slot,
+                    delta,
+                })?;
+            previous_slot = slot;
+
+            let account_count = reader.read_len()?;
+            let blob_accounts = (0..account_count)
+                .map(|_| {
+                    let address = Pubkey::new_from_array(reader.read_array()?);
+                    let raw_data_len = reader.read_len()?;
+                    let raw_data = reader.read_slice(raw_data_len)?.to_vec();
+                    Ok(BlobAccount::new(address, raw_data))
+                })
+                .collect::<CompactEncodingResult<_>>()?;
+            uploads.insert(slot, blob_accounts);
+        }
+
+        if !reader.bytes.is_empty() {
+            return Err(CompactEncodingError::TrailingBytes(reader.bytes.len()));
+        }
+
+        Ok(Self {
+            blob_proofs,
+            blober_pubkey,
+            // Every slot was checked to come after the previous one, starting from the initial slot.
+            blober_account_state_proof: BloberAccountStateProof::new(
+                initial_hash,
+                initial_slot,
+                uploads,
+            ),
+        })
+    }
+}
+
+/// Appends `value` to `out` as an unsigned LEB128 variable length integer.
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// A cursor over the bytes of the compact proof format.
+struct CompactReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> CompactReader<'a> {
+    fn read_slice(&mut self, len: usize) -> CompactEncodingResult<&'a [u8]> {
+        let (head, tail) = self
+            .bytes
+            .split_at_checked(len)
+            .ok_or(CompactEncodingError::UnexpectedEnd)?;
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> CompactEncodingResult<[u8; N]> {
+        Ok(self
+            .read_slice(N)?
+            .try_into()
+            .expect("slice to have the requested length"))
+    }
+
+    fn read_u8(&mut self) -> CompactEncodingResult<u8> {
+        self.read_array::<1>().map(|[byte]| byte)
+    }
+
+    fn read_varint(&mut self) -> CompactEncodingResult<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+            let bits = u64::from(byte & 0x7f);
+            if shift == 63 && bits > 1 {
+                return Err(CompactEncodingError::VarintOverflow);
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(CompactEncodingError::VarintOverflow)
+    }
+
+    fn read_len(&mut self) -> CompactEncodingResult<usize> {
+        usize::try_from(self.read_varint()?).map_err(|_| CompactEncodingError::VarintOverflow)
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
-    use std::collections::BTreeMap;
-
-    use anchor_lang::{AnchorSerialize, Discriminator, solana_program::clock::Slot};
+    use anchor_lang::{AnchorSerialize, Discriminator};
     use arbtest::arbtest;
-    use blober_account_state::{BlobAccount, merge_all_hashes};
+    use blober_account_state::merge_all_hashes;
     use data_anchor_blober::{
         BLOB_DATA_END, BLOB_DATA_START, CHUNK_SIZE, initial_hash,
         state::{blob::Blob, blober::Blober},
@@ -279,6 +639,199 @@ mod tests {
         let deserialized_bincode: CompoundInclusionProof =
             bincode::deserialize(&serialized_bincode).unwrap();
         assert_eq!(proof, deserialized_bincode);
+
+        let serialized_compact = proof.to_compact_bytes();
+        let deserialized_compact =
+            CompoundInclusionProof::from_compact_bytes(&serialized_compact).unwrap();
+        assert_eq!(proof, deserialized_compact);
+    }
+
+    #[test]
+    fn compact_serialization_is_smaller_than_bincode() {
+        arbtest(|u| {
+            let blob_proofs = (0..u.int_in_range(0..=20)?)
+                .map(|_| {
+                    Ok(BlobProof {
+                        digest: u.arbitrary()?,
+                        chunk_order: u.arbitrary()?,
+                    })
+                })
+                .collect::<arbitrary::Result<Vec<_>>>()?;
+
+            let initial_slot = u.arbitrary::<u32>()? as Slot;
+            let mut uploads = BTreeMap::new();
+            for _ in 0..u.int_in_range(0..=20)? {
+                let slot = initial_slot + 1 + u.arbitrary::<u16>()? as Slot;
+                let blob_accounts = (0..u.int_in_range(1..=5)?)
+                    .map(|_| {
+                        Ok(BlobAccount::new(
+                            u.arbitrary::<ArbKeypair>()?.pubkey(),
+                            u.arbitrary()?,
+                        ))
+                    })
+                    .collect::<arbitrary::Result<Vec<_>>>()?;
+                uploads.insert(slot, blob_accounts);
+            }
+
+            let proof = CompoundInclusionProof::new(
+                blob_proofs,
+                u.arbitrary::<ArbKeypair>()?.pubkey(),
+                BloberAccountStateProof::new(u.arbitrary()?, initial_slot, uploads),
+            );
+
+            let compact = proof.to_compact_bytes();
+            let bincode = bincode::serialize(&proof).unwrap();
+            assert!(
+                compact.len() < bincode.len(),
+                "compact encoding ({}) should be smaller than bincode ({})",
+                compact.len(),
+                bincode.len()
+            );
+            assert_eq!(
+                CompoundInclusionProof::from_compact_bytes(&compact).unwrap(),
+                proof
+            );
+
+            Ok(())
+        })
+        .size_max(100_000_000);
+    }
+
+    #[test]
+    fn verify_for_namespace() {
+        let program_id = data_anchor_blober::id();
+        let payer = Pubkey::new_unique();
+        let blober = find_blober_address(program_id, payer, "test");
+        let compound_inclusion_proof = CompoundInclusionProof::new(
+            Vec::new(),
+            blober,
+            BloberAccountStateProof::new(initial_hash(), 1, Default::default()),
+        );
+        let blober_state = [
+            Blober::DISCRIMINATOR,
+            Blober {
+                caller: payer,
+                namespace: "test".to_string(),
+                hash: initial_hash(),
+                slot: 1,
+            }
+            .try_to_vec()
+            .unwrap()
+            .as_ref(),
+        ]
+        .concat();
+        let uploads: Vec<ProofBlob<Vec<u8>>> = Vec::new();
+
+        compound_inclusion_proof
+            .verify_for_namespace(program_id, payer, "test", &blober_state, &uploads)
+            .unwrap();
+
+        let err = compound_inclusion_proof
+            .verify_for_namespace(program_id, payer, "other", &blober_state, &uploads)
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                CompoundInclusionProofError::BloberNamespaceMismatch { expected, found }
+                    if expected == find_blober_address(program_id, payer, "other")
+                        && found == blober
+            ),
+            "Expected a namespace mismatch, got {err:?}"
+        );
+    }
+
+    #[test]
+    fn estimated_serialized_size_matches_bincode() {
+        let blob_count = 5;
+        let blobs = (0..blob_count)
+            .map(|i| vec![i as u8; CHUNK_SIZE as usize])
+            .collect_vec();
+        let blob_proofs = blobs
+            .iter()
+            .map(|blob| BlobProof::new(&[(0, blob)]))
+            .collect_vec();
+
+        // Each blob account in its own slot, matching the assumptions of the estimate.
+        let uploads = (0..blob_count)
+            .map(|i| {
+                (
+                    i as Slot + 2,
+                    vec![BlobAccount::new(
+                        Pubkey::new_unique(),
+                        vec![0; BLOB_ACCOUNT_DATA_LEN as usize],
+                    )],
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+        let proof = CompoundInclusionProof::new(
+            blob_proofs.clone(),
+            Pubkey::new_unique(),
+            BloberAccountStateProof::new(initial_hash(), 1, uploads.clone()),
+        );
+        assert_eq!(
+            CompoundInclusionProof::estimated_serialized_size(blob_count, blob_count),
+            bincode::serialize(&proof).unwrap().len()
+        );
+
+        // All blob accounts in a single slot, the estimate should only overshoot slightly.
+        let uploads = BTreeMap::from([(2, uploads.into_values().flatten().collect_vec())]);
+        let proof = CompoundInclusionProof::new(
+            blob_proofs,
+            Pubkey::new_unique(),
+            BloberAccountStateProof::new(initial_hash(), 1, uploads),
+        );
+        let actual = bincode::serialize(&proof).unwrap().len();
+        let estimate = CompoundInclusionProof::estimated_serialized_size(blob_count, blob_count);
+        assert!(estimate >= actual);
+        assert!(
+            estimate - actual <= blob_count * 2 * size_of::<u64>(),
+            "estimate {estimate} is too far off the actual size {actual}"
+        );
+    }
+
+    #[test]
+    fn compact_serialization_rejects_unknown_version() {
+        let proof = CompoundInclusionProof::new(
+            Vec::new(),
+            Pubkey::new_unique(),
+            BloberAccountStateProof::new(initial_hash(), 1, Default::default()),
+        );
+        let mut bytes = proof.to_compact_bytes();
+        bytes[0] = COMPACT_FORMAT_VERSION + 1;
+
+        assert_eq!(
+            CompoundInclusionProof::from_compact_bytes(&bytes),
+            Err(CompactEncodingError::UnsupportedVersion(
+                COMPACT_FORMAT_VERSION + 1
+            ))
+        );
+    }
+
+    #[test]
+    fn compact_serialization_rejects_duplicate_slots() {
+        let proof = CompoundInclusionProof::new(
+            Vec::new(),
+            Pubkey::new_unique(),
+            BloberAccountStateProof::new(
+                initial_hash(),
+                1,
+                BTreeMap::from([(2, Vec::new()), (3, Vec::new())]),
+            ),
+        );
+        let mut bytes = proof.to_compact_bytes();
+        // The encoding ends with the delta and the empty account list of the last slot. A zero
+        // delta repeats the previous slot instead.
+        let last_delta = bytes.len() - 2;
+        assert_eq!(bytes[last_delta], 1);
+        bytes[last_delta] = 0;
+
+        assert_eq!(
+            CompoundInclusionProof::from_compact_bytes(&bytes),
+            Err(CompactEncodingError::NonIncreasingSlot {
+                previous_slot: 2,
+                delta: 0
+            })
+        );
     }
 
     #[test]
@@ -308,6 +861,225 @@ mod tests {
         );
     }
 
+    /// Builds a proof for a blober with no uploads, along with arguments that verify it unless
+    /// `corrupt` is set, in which case the blober state has a different hash.
+    fn empty_proof_with_args(
+        u: &mut arbitrary::Unstructured,
+        blober: Pubkey,
+        corrupt: bool,
+    ) -> arbitrary::Result<(CompoundInclusionProof, VerifyArgs)> {
+        let slot = u.int_in_range(1..=u64::MAX)?;
+        let blober_account_state_proof =
+            BloberAccountStateProof::new(u.arbitrary()?, slot, Default::default());
+        let mut hash = blober_account_state_proof.calculate_hash();
+        if corrupt {
+            hash[0] ^= 1;
+        }
+        let blober_state = Blober {
+            caller: Pubkey::new_unique(),
+            namespace: "test".to_string(),
+            hash,
+            slot,
+        };
+        let args = VerifyArgs {
+            blober,
+            blober_state: [
+                Blober::DISCRIMINATOR,
+                blober_state.try_to_vec().unwrap().as_ref(),
+            ]
+            .concat(),
+            blobs: Vec::new(),
+        };
+        let proof = CompoundInclusionProof::new(Vec::new(), blober, blober_account_state_proof);
+        Ok((proof, args))
+    }
+
+    #[test]
+    fn verify_never_panics_on_arbitrary_input() {
+        arbtest(|u| {
+            let proof: CompoundInclusionProof = u.arbitrary()?;
+
+            // Mostly use the proof's own blober and blob count, so verification gets past the
+            // initial checks and into the blob and state verification.
+            let blober = if u.ratio(9, 10)? {
+                proof.blober_pubkey
+            } else {
+                Pubkey::new_from_array(u.arbitrary()?)
+            };
+            let blobs: Vec<ProofBlob<Vec<u8>>> = if u.ratio(9, 10)? {
+                (0..proof.blob_proofs.len())
+                    .map(|_| u.arbitrary())
+                    .collect::<arbitrary::Result<_>>()?
+            } else {
+                u.arbitrary()?
+            };
+            let blober_state = if u.ratio(9, 10)? {
+                let state = Blober {
+                    caller: Pubkey::new_from_array(u.arbitrary()?),
+                    namespace: u.arbitrary()?,
+                    hash: u.arbitrary()?,
+                    slot: if u.arbitrary()? {
+                        proof.target_slot()
+                    } else {
+                        u.arbitrary()?
+                    },
+                };
+                [Blober::DISCRIMINATOR, state.try_to_vec().unwrap().as_ref()].concat()
+            } else {
+                u.arbitrary()?
+            };
+
+            let _ = proof.verify(blober, &blober_state, &blobs);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn aggregate_verifies_iff_every_proof_verifies() {
+        arbtest(|u| {
+            let mut proofs = Vec::new();
+            let mut args = Vec::new();
+            for _ in 0..u.int_in_range(0..=8)? {
+                let blober = Pubkey::new_unique();
+                let (proof, arg) = empty_proof_with_args(u, blober, u.ratio(1, 5)?)?;
+                proofs.push(proof);
+                args.push(arg);
+            }
+
+            let all_valid = proofs.iter().zip_eq(&args).all(|(proof, arg)| {
+                proof
+                    .verify(arg.blober, &arg.blober_state, &arg.blobs)
+                    .is_ok()
+            });
+
+            let aggregate = AggregateInclusionProof::new(proofs);
+            let verification = aggregate.verify_all(&args);
+            assert_eq!(
+                verification.is_ok(),
+                all_valid,
+                "Unexpected aggregate verification result: {verification:?}"
+            );
+            if let Err(err) = verification {
+                let AggregateInclusionProofError::InvalidProof { index, blober, .. } = err else {
+                    panic!("Expected an invalid proof error, got {err:?}");
+                };
+                assert_eq!(blober, aggregate.proofs[index].blober_pubkey);
+            }
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn aggregate_rejects_duplicate_blobers() {
+        arbtest(|u| {
+            let blober = Pubkey::new_unique();
+            let (first, first_args) = empty_proof_with_args(u, blober, false)?;
+            let (second, second_args) = empty_proof_with_args(u, blober, false)?;
+            let aggregate = AggregateInclusionProof::new(vec![first, second]);
+
+            let verification = aggregate.verify_all(&[first_args, second_args]);
+            assert!(
+                matches!(verification, Err(AggregateInclusionProofError::DuplicateBlober(duplicate)) if duplicate == blober),
+                "Expected a duplicate blober error, got {verification:?}"
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn aggregate_rejects_mismatched_number_of_args() {
+        arbtest(|u| {
+            let (proof, args) = empty_proof_with_args(u, Pubkey::new_unique(), false)?;
+            let aggregate = AggregateInclusionProof::new(vec![proof]);
+
+            assert!(aggregate.verify_all(&[args.clone()]).is_ok());
+            assert!(matches!(
+                aggregate.verify_all(&[args.clone(), args]),
+                Err(AggregateInclusionProofError::InvalidNumberOfArgs { proofs: 1, args: 2 })
+            ));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn aggregate_commitment_depends_on_every_proof() {
+        arbtest(|u| {
+            let (first, _) = empty_proof_with_args(u, Pubkey::new_unique(), false)?;
+            let (second, _) = empty_proof_with_args(u, Pubkey::new_unique(), false)?;
+
+            let aggregate = AggregateInclusionProof::new(vec![first.clone(), second.clone()]);
+            let reordered = AggregateInclusionProof::new(vec![second, first.clone()]);
+            let single = AggregateInclusionProof::new(vec![first]);
+
+            let commitment = aggregate.into_commitment();
+            assert_eq!(commitment, aggregate.clone().into_commitment());
+            assert_ne!(commitment, reordered.into_commitment());
+            assert_ne!(commitment, single.into_commitment());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn blober_state_for_different_slot() {
+        let blober = Pubkey::new_unique();
+        let compound_inclusion_proof = CompoundInclusionProof::new(
+            Vec::new(),
+            blober,
+            BloberAccountStateProof::new(initial_hash(), 1, Default::default()),
+        );
+        let blober_state = Blober {
+            caller: Pubkey::new_unique(),
+            namespace: "test".to_string(),
+            hash: initial_hash(),
+            slot: 2,
+        };
+        let state_bytes = [
+            Blober::DISCRIMINATOR,
+            blober_state.try_to_vec().unwrap().as_ref(),
+        ]
+        .concat();
+        let uploads: Vec<ProofBlob<Vec<u8>>> = Vec::new();
+
+        let verification = compound_inclusion_proof.verify(blober, &state_bytes, &uploads);
+        assert!(
+            matches!(
+                verification,
+                Err(CompoundInclusionProofError::BloberAccountState(
+                    BloberAccountStateError::SlotMismatch {
+                        expected: 1,
+                        found: 2
+                    }
+                ))
+            ),
+            "Expected SlotMismatch, got {verification:?}",
+        );
+
+        let expected_state_bytes = [
+            Blober::DISCRIMINATOR,
+            Blober {
+                slot: 1,
+                ..blober_state
+            }
+            .try_to_vec()
+            .unwrap()
+            .as_ref(),
+        ]
+        .concat();
+        let err = verification
+            .unwrap_err()
+            .with_blober_state_diff(&expected_state_bytes, &state_bytes);
+        assert_eq!(
+            err.to_string(),
+            "The proof targets slot 1, but the blober state is for slot 2 \
+             (blober state differs: slot: expected 1, found 2)"
+        );
+    }
+
     #[test]
     fn inclusion_construction_single_blob() {
         arbtest(|u| {
@@ -1,8 +1,15 @@
 use std::{
     fmt,
-    fmt::{Debug, Formatter},
+    fmt::{Debug, Display, Formatter},
 };
 
+use anchor_lang::{
+    prelude::Pubkey,
+    solana_program::{clock::Slot, hash::HASH_BYTES},
+};
+
+use crate::blober_account_state::{BloberAccountStateResult, deserialize_blober};
+
 pub struct NoPrettyPrint<T: Debug>(pub T);
 
 impl<T: Debug> Debug for NoPrettyPrint<T> {
@@ -11,3 +18,108 @@ impl<T: Debug> Debug for NoPrettyPrint<T> {
         write!(f, "{:?}", self.0)
     }
 }
+
+/// The fields that differ between an expected and an actual blober state, as `(expected, actual)`
+/// pairs. Fields that are equal in both states are `None`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BloberStateDiff {
+    pub caller: Option<(Pubkey, Pubkey)>,
+    pub namespace: Option<(String, String)>,
+    pub hash: Option<([u8; HASH_BYTES], [u8; HASH_BYTES])>,
+    pub slot: Option<(Slot, Slot)>,
+}
+
+fn diff<T: PartialEq>(expected: T, actual: T) -> Option<(T, T)> {
+    (expected != actual).then_some((expected, actual))
+}
+
+impl BloberStateDiff {
+    /// Deserializes both serialized blober states, including their discriminators, and compares
+    /// their fields.
+    pub fn new(expected: &[u8], actual: &[u8]) -> BloberAccountStateResult<Self> {
+        let expected = deserialize_blober(expected)?;
+        let actual = deserialize_blober(actual)?;
+
+        Ok(Self {
+            caller: diff(expected.caller, actual.caller),
+            namespace: diff(expected.namespace, actual.namespace),
+            hash: diff(expected.hash, actual.hash),
+            slot: diff(expected.slot, actual.slot),
+        })
+    }
+
+    /// Whether the two states are identical.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Display for BloberStateDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut fields = Vec::new();
+        if let Some((expected, actual)) = &self.caller {
+            fields.push(format!("caller: expected {expected}, found {actual}"));
+        }
+        if let Some((expected, actual)) = &self.namespace {
+            fields.push(format!(
+                "namespace: expected {expected:?}, found {actual:?}"
+            ));
+        }
+        if let Some((expected, actual)) = &self.hash {
+            fields.push(format!(
+                "hash: expected {}, found {}",
+                hex::encode(expected),
+                hex::encode(actual)
+            ));
+        }
+        if let Some((expected, actual)) = &self.slot {
+            fields.push(format!("slot: expected {expected}, found {actual}"));
+        }
+
+        if fields.is_empty() {
+            write!(f, "no differences")
+        } else {
+            write!(f, "{}", fields.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{AnchorSerialize, Discriminator};
+    use data_anchor_blober::{initial_hash, state::blober::Blober};
+
+    use super::*;
+
+    fn serialize(blober: &Blober) -> Vec<u8> {
+        [Blober::DISCRIMINATOR, blober.try_to_vec().unwrap().as_ref()].concat()
+    }
+
+    #[test]
+    fn diff_reports_only_slot() {
+        let expected = Blober {
+            caller: Pubkey::new_unique(),
+            namespace: "test".to_string(),
+            hash: initial_hash(),
+            slot: 1,
+        };
+        let actual = Blober {
+            slot: 2,
+            ..expected.clone()
+        };
+
+        let diff = BloberStateDiff::new(&serialize(&expected), &serialize(&actual)).unwrap();
+        assert_eq!(
+            diff,
+            BloberStateDiff {
+                slot: Some((1, 2)),
+                ..Default::default()
+            }
+        );
+        assert_eq!(diff.to_string(), "slot: expected 1, found 2");
+
+        let same = BloberStateDiff::new(&serialize(&expected), &serialize(&expected)).unwrap();
+        assert!(same.is_empty());
+        assert_eq!(same.to_string(), "no differences");
+    }
+}
@@ -6,21 +6,30 @@
 
 pub mod blob;
 pub mod blober_account_state;
+#[cfg(feature = "sp1")]
+pub mod checkpoint;
 pub mod compound;
-mod debug;
+pub mod debug;
 
 #[doc(hidden)]
 #[cfg(test)]
 pub(crate) mod testing {
-    use std::{cmp::max, hash::Hash, ops::Deref};
+    use std::{cmp::max, collections::BTreeMap, hash::Hash, ops::Deref};
 
-    use anchor_lang::solana_program::clock::Epoch;
+    use anchor_lang::{prelude::Pubkey, solana_program::clock::Epoch};
     use arbitrary::{Arbitrary, Unstructured};
+    use data_anchor_blober::BLOB_ACCOUNT_DATA_LEN;
     use solana_account::Account;
     use solana_keypair::Keypair;
     use solana_seed_derivable::SeedDerivable;
     use solana_signer::Signer;
 
+    use crate::{
+        blob::BlobProof,
+        blober_account_state::{BlobAccount, BloberAccountStateProof},
+        compound::{CompoundInclusionProof, ProofBlob},
+    };
+
     /// An arbitrary keypair, since we can't implement [`arbitrary::Arbitrary`] for
     /// [`solana_keypair::Keypair`] or [`anchor_lang::Pubkey`].
     ///
@@ -91,4 +100,70 @@ pub(crate) mod testing {
             }
         }
     }
+
+    // The proof impls below don't try to produce valid proofs, they're meant for fuzzing the
+    // verifiers' error paths. Pubkeys are taken from raw bytes since they don't need to be on the
+    // curve.
+
+    fn arbitrary_pubkey(u: &mut Unstructured) -> arbitrary::Result<Pubkey> {
+        Ok(Pubkey::new_from_array(u.arbitrary()?))
+    }
+
+    impl<'a> Arbitrary<'a> for BlobProof {
+        fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+            Ok(BlobProof {
+                digest: u.arbitrary()?,
+                chunk_order: u.arbitrary()?,
+            })
+        }
+    }
+
+    impl<'a, A: AsRef<[u8]> + Arbitrary<'a>> Arbitrary<'a> for ProofBlob<A> {
+        fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+            Ok(ProofBlob {
+                blob: arbitrary_pubkey(u)?,
+                data: u.arbitrary()?,
+            })
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for BlobAccount {
+        fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+            // Favor correctly sized account data, so verification gets past the length checks.
+            let raw_data = if u.arbitrary()? {
+                u.bytes(BLOB_ACCOUNT_DATA_LEN as usize)?.to_vec()
+            } else {
+                u.arbitrary()?
+            };
+            Ok(BlobAccount::new(arbitrary_pubkey(u)?, raw_data))
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for BloberAccountStateProof {
+        fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+            let initial_hash = u.arbitrary()?;
+            let initial_slot = u.arbitrary()?;
+            // Only uploads after the initial slot make up a valid proof.
+            let uploads = u
+                .arbitrary::<BTreeMap<_, _>>()?
+                .into_iter()
+                .filter(|(slot, _)| *slot > initial_slot)
+                .collect();
+            Ok(BloberAccountStateProof::new(
+                initial_hash,
+                initial_slot,
+                uploads,
+            ))
+        }
+    }
+
+    impl<'a> Arbitrary<'a> for CompoundInclusionProof {
+        fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+            Ok(CompoundInclusionProof::new(
+                u.arbitrary()?,
+                arbitrary_pubkey(u)?,
+                u.arbitrary()?,
+            ))
+        }
+    }
 }
@@ -1,8 +1,10 @@
-use std::collections::BTreeMap;
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
 
 use anchor_lang::{AnchorSerialize, Discriminator, prelude::Pubkey};
-use bytesize::ByteSize;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use data_anchor_blober::{
     BLOB_DATA_END, BLOB_DATA_START, CHUNK_SIZE, COMPOUND_TX_SIZE, blob::Blob, initial_hash,
     state::blober::Blober,
@@ -13,16 +15,18 @@ use data_anchor_proofs::{
     compound::{CompoundInclusionProof, ProofBlob, VerifyArgs},
 };
 use data_anchor_prover::{
-    DATA_CORRECTNESS_ELF, ENCODING_COMPRESSION_TEST_ELF, POB_ELF, run_client, setup_prover_input,
+    DATA_CORRECTNESS_ELF, ENCODING_COMPRESSION_TEST_ELF, POB_ELF, ProofSystem, SlaScorePrecision,
+    run_client_with_input, setup_pob_sla_prover_input, setup_prover_input,
 };
 use data_anchor_utils::{
     compression::{CompressionType, ZstdCompression},
     encode_and_compress,
     encoding::EncodingType,
 };
-use itertools::iproduct;
+use itertools::{Itertools, iproduct};
 use rand::{RngCore, rngs::OsRng};
-use sp1_sdk::{ProverClient, utils};
+use serde::Serialize;
+use sp1_sdk::{ExecutionReport, ProverClient, SP1PublicValues, utils};
 
 #[derive(Debug, Clone, Parser)]
 struct Config {
@@ -30,6 +34,112 @@ struct Config {
     pub prove: bool,
     #[arg(short, long, env = "DATA_ANCHOR_VERIFY", default_value_t = true)]
     pub verify: bool,
+    #[arg(
+        long,
+        env = "DATA_ANCHOR_PROOF_SYSTEM",
+        value_enum,
+        default_value_t = ProofSystem::default()
+    )]
+    pub proof_system: ProofSystem,
+    #[arg(long, env = "DATA_ANCHOR_OUTPUT_FORMAT", value_enum, default_value_t = OutputFormat::default())]
+    pub format: OutputFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// One CSV row per program, with the cycle trackers joined into a single column.
+    #[default]
+    Csv,
+    /// A JSON array with one object per program.
+    Json,
+}
+
+/// The measurements of a single program run.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct ProverBenchmarkRow {
+    slots: u64,
+    blob_proof_count: usize,
+    program: String,
+    public_values_bytes: usize,
+    cycle_tracker: BTreeMap<String, u64>,
+    total_cycles: u64,
+    gas: u64,
+    /// Wall-clock time of the run in seconds, including proving if enabled.
+    elapsed: f64,
+}
+
+/// A [`ProverBenchmarkRow`] with the cycle trackers flattened, since CSV can't hold nested maps.
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    slots: u64,
+    blob_proof_count: usize,
+    program: &'a str,
+    public_values_bytes: usize,
+    cycle_tracker: String,
+    total_cycles: u64,
+    gas: u64,
+    elapsed: f64,
+}
+
+impl ProverBenchmarkRow {
+    fn new(
+        slots: u64,
+        blob_proof_count: usize,
+        program: String,
+        public_values: &SP1PublicValues,
+        report: &ExecutionReport,
+        elapsed: Duration,
+    ) -> Self {
+        let cycle_tracker: BTreeMap<_, _> = report
+            .cycle_tracker
+            .iter()
+            .map(|(k, v)| (k.clone(), *v))
+            .collect();
+        Self {
+            slots,
+            blob_proof_count,
+            program,
+            public_values_bytes: public_values.as_slice().len(),
+            total_cycles: cycle_tracker.values().sum(),
+            cycle_tracker,
+            gas: report.gas.unwrap_or_default(),
+            elapsed: elapsed.as_secs_f64(),
+        }
+    }
+
+    fn as_csv_row(&self) -> CsvRow<'_> {
+        CsvRow {
+            slots: self.slots,
+            blob_proof_count: self.blob_proof_count,
+            program: &self.program,
+            public_values_bytes: self.public_values_bytes,
+            cycle_tracker: self
+                .cycle_tracker
+                .iter()
+                .map(|(k, v)| format!("{k}:{v}"))
+                .join(","),
+            total_cycles: self.total_cycles,
+            gas: self.gas,
+            elapsed: self.elapsed,
+        }
+    }
+}
+
+/// Serializes the benchmark rows in the given format.
+fn write_rows(
+    rows: &[ProverBenchmarkRow],
+    format: OutputFormat,
+) -> Result<String, Box<dyn std::error::Error>> {
+    match format {
+        OutputFormat::Csv => {
+            let mut writer = csv::WriterBuilder::new().from_writer(Vec::new());
+            for row in rows {
+                writer.serialize(row.as_csv_row())?;
+            }
+            Ok(String::from_utf8(writer.into_inner()?)?)
+        }
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(rows)?),
+    }
 }
 
 fn generate_inputs(
@@ -191,30 +301,41 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let config = Config::parse();
 
-    let mut output = "slot,blob_proof_count,program,cycles,cycle_tracker,gases,elf".to_owned();
-    for (elf, name) in [
-        (DATA_CORRECTNESS_ELF, "DATA_CORRECTNESS"),
-        (POB_ELF, "POB_SLA"),
+    let mut rows = Vec::new();
+    for (elf, inputs, name) in [
+        (
+            DATA_CORRECTNESS_ELF,
+            setup_prover_input(&compound_inclusion_proof, &args),
+            "DATA_CORRECTNESS",
+        ),
+        (
+            POB_ELF,
+            setup_pob_sla_prover_input(&compound_inclusion_proof, &args, SlaScorePrecision::F64),
+            "POB_SLA",
+        ),
+        (
+            POB_ELF,
+            setup_pob_sla_prover_input(&compound_inclusion_proof, &args, SlaScorePrecision::F32),
+            "POB_SLA_F32",
+        ),
     ] {
-        let (public_values, report) = run_client(
-            &compound_inclusion_proof,
-            &args,
+        let start = Instant::now();
+        let (public_values, report) = run_client_with_input(
+            inputs,
             elf,
             config.prove,
             config.verify,
+            config.proof_system,
         )?;
-
-        let size = ByteSize(public_values.as_slice().len() as u64);
-        output.push_str(&format!(
-            "\n{slots},{blob_proof_count},{},{size},{},{},{name}",
-            report
-                .cycle_tracker
-                .iter()
-                .map(|(k, v)| format!("{k}:{v}"))
-                .collect::<Vec<_>>()
-                .join(","),
-            report.cycle_tracker.values().sum::<u64>(),
-            report.gas.unwrap_or_default(),
+        let elapsed = start.elapsed();
+
+        rows.push(ProverBenchmarkRow::new(
+            slots,
+            blob_proof_count,
+            name.to_owned(),
+            &public_values,
+            &report,
+            elapsed,
         ));
     }
 
@@ -225,6 +346,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             CompressionType::NoCompression,
             CompressionType::Flate2Compression,
             CompressionType::Lz4Compression,
+            CompressionType::SnappyCompression,
         ],
         [
             EncodingType::default(),
@@ -244,25 +366,79 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         let client = ProverClient::from_env();
 
+        let start = Instant::now();
         let (public_values, report) = client
             .execute(ENCODING_COMPRESSION_TEST_ELF, &inputs)
             .run()?;
-
-        let size = ByteSize(public_values.as_slice().len() as u64);
-        output.push_str(&format!(
-            "\n{slots},{blob_proof_count},{},{size},{},{},ENCODING_COMPRESSION_TEST_{compression}_{encoding}",
-            report
-                .cycle_tracker
-                .iter()
-                .map(|(k, v)| format!("{k}:{v}"))
-                .collect::<Vec<_>>()
-                .join(","),
-            report.cycle_tracker.values().sum::<u64>(),
-            report.gas.unwrap_or_default(),
+        let elapsed = start.elapsed();
+
+        rows.push(ProverBenchmarkRow::new(
+            slots,
+            blob_proof_count,
+            format!("ENCODING_COMPRESSION_TEST_{compression}_{encoding}"),
+            &public_values,
+            &report,
+            elapsed,
         ));
     }
 
-    println!("{output}");
+    println!("{}", write_rows(&rows, config.format)?);
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row() -> ProverBenchmarkRow {
+        ProverBenchmarkRow {
+            slots: 93,
+            blob_proof_count: 93,
+            program: "DATA_CORRECTNESS".to_owned(),
+            public_values_bytes: 96,
+            cycle_tracker: BTreeMap::from([("hash".to_owned(), 10), ("verify".to_owned(), 32)]),
+            total_cycles: 42,
+            gas: 1000,
+            elapsed: 1.5,
+        }
+    }
+
+    #[test]
+    fn rows_serialize_to_csv() {
+        let csv = write_rows(&[row(), row()], OutputFormat::Csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "slots,blob_proof_count,program,public_values_bytes,cycle_tracker,total_cycles,gas,elapsed"
+            )
+        );
+        for _ in 0..2 {
+            assert_eq!(
+                lines.next(),
+                Some("93,93,DATA_CORRECTNESS,96,\"hash:10,verify:32\",42,1000,1.5")
+            );
+        }
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn rows_serialize_to_json() {
+        let json = write_rows(&[row()], OutputFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!([{
+                "slots": 93,
+                "blob_proof_count": 93,
+                "program": "DATA_CORRECTNESS",
+                "public_values_bytes": 96,
+                "cycle_tracker": { "hash": 10, "verify": 32 },
+                "total_cycles": 42,
+                "gas": 1000,
+                "elapsed": 1.5,
+            }])
+        );
+    }
+}
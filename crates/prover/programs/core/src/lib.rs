@@ -1,5 +1,7 @@
 use data_anchor_proofs::compound::{CompoundInclusionProof, VerifyArgs};
 
+pub mod sla;
+
 #[sp1_derive::cycle_tracker]
 fn read_data_correctness_inputs_inner() -> (CompoundInclusionProof, VerifyArgs) {
     let proof: CompoundInclusionProof = sp1_zkvm::io::read();
@@ -22,6 +24,32 @@ fn verify_data_correctness_inner(proof: &CompoundInclusionProof, args: &VerifyAr
         .unwrap();
 }
 
+#[sp1_derive::cycle_tracker]
+fn batch_data_correctness_proof_inner() -> Vec<(CompoundInclusionProof, VerifyArgs)> {
+    let count: u32 = sp1_zkvm::io::read();
+
+    let mut batch: Vec<(CompoundInclusionProof, VerifyArgs)> = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let (proof, args) = read_data_correctness_inputs();
+        if let Some((previous_proof, previous_args)) = batch.last() {
+            assert_eq!(
+                proof.blober_pubkey, previous_proof.blober_pubkey,
+                "all slots in a batch must be for the same blober"
+            );
+            assert_eq!(
+                proof.into_commitment().blober_initial_hash,
+                previous_args.into_commitment().unwrap().blober_hash,
+                "slots in a batch must be contiguous"
+            );
+        }
+        data_correctness_commitment(&proof, &args);
+        verify_data_correctness(&proof, &args);
+        batch.push((proof, args));
+    }
+
+    batch
+}
+
 #[sp1_derive::cycle_tracker]
 fn data_correctness_proof_inner() -> (CompoundInclusionProof, VerifyArgs) {
     let (proof, args) = read_data_correctness_inputs();
@@ -80,3 +108,29 @@ pub fn data_correctness_proof() -> (CompoundInclusionProof, VerifyArgs) {
     println!("cycle-tracker-report-end: data_correctness_proof_inner");
     inputs
 }
+
+/// Run the data correctness proof for a contiguous batch of slots of the same blober, amortizing
+/// the fixed proving overhead over all of them.
+///
+/// The number of slots is read first as a `u32`, followed by the inputs of every slot in order.
+/// Every slot is committed to exactly like in [`data_correctness_proof`], so the public values are
+/// the concatenation of one record per slot:
+///
+/// | Offset in record | Size | Value                                |
+/// |------------------|------|--------------------------------------|
+/// | 0                | 32   | Blober public key                    |
+/// | 32               | 32   | Blober hash before the slot          |
+/// | 64               | 32   | Blober hash after the slot           |
+///
+/// The first record is laid out exactly like the public values of a single slot proof.
+///
+/// # Panics
+///
+/// This function will panic if any slot fails to verify, if the slots are for different blobers
+/// or if a slot doesn't start from the hash the previous slot ended with.
+pub fn batch_data_correctness_proof() -> Vec<(CompoundInclusionProof, VerifyArgs)> {
+    println!("cycle-tracker-report-start: batch_data_correctness_proof_inner");
+    let batch = batch_data_correctness_proof_inner();
+    println!("cycle-tracker-report-end: batch_data_correctness_proof_inner");
+    batch
+}
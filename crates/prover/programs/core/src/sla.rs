@@ -0,0 +1,99 @@
+//! Helpers for reading the SLA values that the PoB SLA program scores blobs by.
+
+use anchor_lang::prelude::Pubkey;
+use data_anchor_proofs::compound::ProofBlob;
+
+/// The size in bytes of the little-endian SLA value at the start of each blob's data.
+pub const SLA_VALUE_SIZE: usize = 8;
+
+/// Reads the SLA value stored in the first [`SLA_VALUE_SIZE`] bytes of a blob's data.
+///
+/// # Panics
+///
+/// This function will panic if the data is shorter than [`SLA_VALUE_SIZE`] bytes.
+pub fn get_sla_from_blob_data(data: &[u8]) -> u64 {
+    let sla_bytes: [u8; SLA_VALUE_SIZE] = data[..SLA_VALUE_SIZE].try_into().unwrap();
+    u64::from_le_bytes(sla_bytes)
+}
+
+/// Same as [`get_sla_from_blob_data`], but returns `None` instead of panicking if the data is
+/// shorter than [`SLA_VALUE_SIZE`] bytes.
+pub fn checked_sla_from_blob_data(data: &[u8]) -> Option<u64> {
+    data.first_chunk::<SLA_VALUE_SIZE>()
+        .copied()
+        .map(u64::from_le_bytes)
+}
+
+/// Averages the SLA values of the given blobs, skipping blobs too short to carry one. Returns
+/// `None` if none of the blobs carry an SLA value.
+pub fn sla_score(blobs: &[&[u8]]) -> Option<f64> {
+    let (sla_sum, count) = blobs
+        .iter()
+        .filter_map(|data| checked_sla_from_blob_data(data))
+        .fold((0u64, 0u64), |(sum, count), sla| (sum + sla, count + 1));
+
+    (count > 0).then(|| sla_sum as f64 / count as f64)
+}
+
+/// Returns the SLA value of every blob that carries one, labeled by the blob's address, so the
+/// individual contributions to the averaged SLA score can be audited.
+pub fn per_blob_sla<A: AsRef<[u8]>>(blobs: &[ProofBlob<A>]) -> Vec<(Pubkey, u64)> {
+    blobs
+        .iter()
+        .filter_map(|blob| {
+            let data = blob.data.as_ref()?;
+            Some((blob.blob, checked_sla_from_blob_data(data.as_ref())?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blob_with_sla(sla: u64, trailing: &[u8]) -> ProofBlob<Vec<u8>> {
+        ProofBlob {
+            blob: Pubkey::new_unique(),
+            data: Some([sla.to_le_bytes().as_slice(), trailing].concat()),
+        }
+    }
+
+    #[test]
+    fn per_blob_sla_values() {
+        let blobs = vec![
+            blob_with_sla(100, &[]),
+            blob_with_sla(0, &[1, 2, 3]),
+            ProofBlob::empty(Pubkey::new_unique()),
+            blob_with_sla(u64::MAX, &[0xff; 32]),
+        ];
+
+        assert_eq!(
+            per_blob_sla(&blobs),
+            vec![
+                (blobs[0].blob, 100),
+                (blobs[1].blob, 0),
+                (blobs[3].blob, u64::MAX),
+            ]
+        );
+    }
+
+    #[test]
+    fn short_blob_has_no_sla() {
+        let short = [1, 2, 3, 4];
+
+        assert_eq!(checked_sla_from_blob_data(&short), None);
+        assert_eq!(sla_score(&[&short]), None);
+
+        let valid = 42u64.to_le_bytes();
+        assert_eq!(sla_score(&[&short, &valid]), Some(42.0));
+
+        let blobs = vec![
+            ProofBlob {
+                blob: Pubkey::new_unique(),
+                data: Some(short.to_vec()),
+            },
+            blob_with_sla(7, &[]),
+        ];
+        assert_eq!(per_blob_sla(&blobs), vec![(blobs[1].blob, 7)]);
+    }
+}
@@ -0,0 +1,8 @@
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use data_anchor_prover_core::batch_data_correctness_proof;
+
+fn main() {
+    batch_data_correctness_proof();
+}
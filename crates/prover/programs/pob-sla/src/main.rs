@@ -1,23 +1,16 @@
 #![no_main]
 sp1_zkvm::entrypoint!(main);
 
-use data_anchor_prover_core::data_correctness_proof;
+use data_anchor_prover_core::{data_correctness_proof, sla::sla_score};
 
-fn get_sla_from_blob_data(data: &[u8]) -> u64 {
-    let sla_bytes: [u8; 8] = data[..8].try_into().unwrap();
-    u64::from_le_bytes(sla_bytes)
-}
-
-fn get_sla_score(blobs: &[&[u8]]) -> f64 {
-    let sla_sum = blobs
-        .iter()
-        .map(|&data| get_sla_from_blob_data(data))
-        .sum::<u64>();
-    sla_sum as f64 / blobs.len() as f64
-}
+/// Tags for the precision of the committed SLA score, matching
+/// `data_anchor_pob_sla_verifier::SlaScorePrecision`.
+const SLA_SCORE_PRECISION_F64: u8 = 0;
+const SLA_SCORE_PRECISION_F32: u8 = 1;
 
 fn main() {
     let (_proof, args) = data_correctness_proof();
+    let precision: u8 = sp1_zkvm::io::read();
 
     println!("cycle-tracker-report-start: pob_sla");
     let blob_data = args
@@ -25,8 +18,14 @@ fn main() {
         .iter()
         .map(|b| b.data.as_ref().unwrap().as_slice())
         .collect::<Vec<_>>();
-    let sla_score = get_sla_score(&blob_data);
+    // Blobs too short to carry an SLA value are skipped rather than aborting the proof.
+    let sla_score = sla_score(&blob_data).expect("no blob carries an SLA value");
 
-    sp1_zkvm::io::commit(&sla_score);
+    sp1_zkvm::io::commit(&precision);
+    match precision {
+        SLA_SCORE_PRECISION_F64 => sp1_zkvm::io::commit(&sla_score),
+        SLA_SCORE_PRECISION_F32 => sp1_zkvm::io::commit(&(sla_score as f32)),
+        _ => panic!("unknown SLA score precision tag {precision}"),
+    }
     println!("cycle-tracker-report-end: pob_sla");
 }
@@ -0,0 +1,170 @@
+//! An on-disk cache for generated proofs. The inputs for a given slot never change, so a proof
+//! generated once can be reused, for example after the prover restarts.
+
+use std::{io::ErrorKind, path::PathBuf};
+
+use anchor_lang::solana_program::hash::hashv;
+use data_anchor_api::ProofData;
+use data_anchor_proofs::compound::{CompoundInclusionProof, VerifyArgs};
+use tracing::debug;
+
+use crate::{ProofGenerationError, ProofGenerationResult, ProofSystem};
+
+/// Stores generated [`ProofData`] as JSON files in a directory, keyed by the commitments of the
+/// prover inputs, the prover ELF and the proof system.
+#[derive(Debug, Clone)]
+pub struct ProofCache {
+    dir: PathBuf,
+}
+
+impl ProofCache {
+    /// Creates a cache storing its entries in `dir`. The directory is created on the first store.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Computes the cache key for the given prover inputs.
+    pub fn key(
+        compound_inclusion_proof: &CompoundInclusionProof,
+        args: &VerifyArgs,
+        prover_elf: &[u8],
+        proof_system: ProofSystem,
+    ) -> ProofGenerationResult<String> {
+        let args_commitment = args
+            .into_commitment()
+            .map_err(|e| ProofGenerationError::Generate(e.to_string()))?;
+        let elf_hash = hashv(&[prover_elf]).to_bytes();
+
+        Ok(hex::encode(
+            hashv(&[
+                &compound_inclusion_proof
+                    .into_commitment()
+                    .blober_initial_hash,
+                &args_commitment.blober_hash,
+                &elf_hash,
+                &[proof_system as u8],
+            ])
+            .to_bytes(),
+        ))
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    /// Returns the cached proof for the key, if there is one.
+    pub fn get(&self, key: &str) -> ProofGenerationResult<Option<ProofData>> {
+        match std::fs::read(self.path(key)) {
+            Ok(contents) => Ok(Some(serde_json::from_slice(&contents)?)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Stores the proof under the key, replacing any previous entry.
+    pub fn put(&self, key: &str, proof_data: &ProofData) -> ProofGenerationResult {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.path(key), serde_json::to_vec(proof_data)?)?;
+        Ok(())
+    }
+
+    /// Returns the cached proof for the prover inputs, or runs `generate` and caches its result.
+    pub async fn get_or_generate<F, Fut>(
+        &self,
+        compound_inclusion_proof: &CompoundInclusionProof,
+        args: &VerifyArgs,
+        prover_elf: &[u8],
+        proof_system: ProofSystem,
+        generate: F,
+    ) -> ProofGenerationResult<ProofData>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ProofGenerationResult<ProofData>>,
+    {
+        let key = Self::key(compound_inclusion_proof, args, prover_elf, proof_system)?;
+        if let Some(proof_data) = self.get(&key)? {
+            debug!("Proof cache hit for {key}");
+            return Ok(proof_data);
+        }
+
+        debug!("Proof cache miss for {key}");
+        let proof_data = generate().await?;
+        self.put(&key, &proof_data)?;
+        Ok(proof_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use anchor_lang::prelude::Pubkey;
+
+    use super::*;
+    use crate::{
+        DATA_CORRECTNESS_ELF, POB_ELF,
+        mock::{MockProver, test_inputs},
+    };
+
+    fn cache() -> ProofCache {
+        ProofCache::new(std::env::temp_dir().join(format!("proof-cache-{}", Pubkey::new_unique())))
+    }
+
+    #[tokio::test]
+    async fn second_call_hits_cache() {
+        let (proof, args) = test_inputs();
+        let cache = cache();
+        let generations = AtomicUsize::new(0);
+
+        let generate = || async {
+            generations.fetch_add(1, Ordering::SeqCst);
+            MockProver::generate_proof(&proof, &args, DATA_CORRECTNESS_ELF, ProofSystem::Groth16)
+                .await
+        };
+
+        let first = cache
+            .get_or_generate(
+                &proof,
+                &args,
+                DATA_CORRECTNESS_ELF,
+                ProofSystem::Groth16,
+                generate,
+            )
+            .await
+            .unwrap();
+        let second = cache
+            .get_or_generate(
+                &proof,
+                &args,
+                DATA_CORRECTNESS_ELF,
+                ProofSystem::Groth16,
+                generate,
+            )
+            .await
+            .unwrap();
+        std::fs::remove_dir_all(&cache.dir).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(generations.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn key_depends_on_elf_and_proof_system() {
+        let (proof, args) = test_inputs();
+
+        let key =
+            ProofCache::key(&proof, &args, DATA_CORRECTNESS_ELF, ProofSystem::Groth16).unwrap();
+        assert_eq!(
+            key,
+            ProofCache::key(&proof, &args, DATA_CORRECTNESS_ELF, ProofSystem::Groth16).unwrap()
+        );
+        assert_ne!(
+            key,
+            ProofCache::key(&proof, &args, POB_ELF, ProofSystem::Groth16).unwrap()
+        );
+        assert_ne!(
+            key,
+            ProofCache::key(&proof, &args, DATA_CORRECTNESS_ELF, ProofSystem::Plonk).unwrap()
+        );
+    }
+}
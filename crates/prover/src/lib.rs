@@ -1,15 +1,39 @@
-use data_anchor_api::ProofData;
+use anchor_lang::solana_program::{hash::HASH_BYTES, pubkey::PUBKEY_BYTES};
+pub use data_anchor_api::ProofSystem;
+use data_anchor_api::{Groth16ProofError, ProofData};
+use data_anchor_blober::checkpoint::Checkpoint;
+pub use data_anchor_pob_sla_verifier::SlaScorePrecision;
 use data_anchor_proofs::compound::{CompoundInclusionProof, VerifyArgs};
 use sp1_sdk::{
-    ExecutionReport, HashableKey, ProverClient, SP1PublicValues, SP1Stdin, SP1VerificationError,
-    include_elf,
+    ExecutionReport, HashableKey, ProverClient, SP1ProofMode, SP1PublicValues, SP1Stdin,
+    SP1VerificationError, include_elf,
+};
+use tokio::{
+    sync::mpsc::{self, Sender},
+    task::spawn_blocking,
 };
-use tokio::task::spawn_blocking;
 use tracing::{debug, info};
 
+pub mod cache;
+#[cfg(feature = "mock")]
+pub mod mock;
+
+pub use cache::ProofCache;
+
 /// Prover ELF binary for data correctness proof generation.
 pub const DATA_CORRECTNESS_ELF: &[u8] = include_elf!("data-anchor-data-correctness");
 
+/// Prover ELF binary for proving the data correctness of a contiguous batch of slots at once. See
+/// [`setup_batch_prover_input`] for the inputs and [`BATCH_PUBLIC_VALUES_RECORD_SIZE`] for the
+/// public values layout.
+pub const DATA_CORRECTNESS_BATCH_ELF: &[u8] = include_elf!("data-anchor-data-correctness-batch");
+
+/// The size of the public values committed per slot by [`DATA_CORRECTNESS_BATCH_ELF`]. The public
+/// values of a batch are one record per slot, in the order the slots were passed in, each
+/// consisting of the blober public key, the blober hash before the slot and the blober hash after
+/// the slot. The first record matches the public values of a single slot proof.
+pub const BATCH_PUBLIC_VALUES_RECORD_SIZE: usize = PUBKEY_BYTES + 2 * HASH_BYTES;
+
 /// Prover ELF binary for PoB SLA proof generation.
 pub const POB_ELF: &[u8] = include_elf!("data-anchor-pob-sla");
 
@@ -27,6 +51,16 @@ pub enum ProofGenerationError {
     Verify(#[from] SP1VerificationError),
     #[error("Failed to put Groth16 proof bytes into array")]
     Groth16ProofBytes,
+    #[error("Failed to access proof cache: {0}")]
+    Cache(#[from] std::io::Error),
+    #[error("Failed to (de)serialize cached proof: {0}")]
+    CacheEntry(#[from] serde_json::Error),
+    #[error("Simulated public values don't match the commitments of the prover inputs")]
+    SimulationMismatch,
+    #[error("Invalid proof data: {0}")]
+    InvalidProofData(String),
+    #[error("Proof data verification failed")]
+    ProofDataVerification,
 }
 
 pub type ProofGenerationResult<T = ()> = Result<T, ProofGenerationError>;
@@ -54,13 +88,53 @@ pub fn setup_prover_input(
     sp1_stdin
 }
 
+/// Read the prover inputs needed for the PoB SLA proof and return the [`SP1Stdin`] instance. On
+/// top of the data correctness inputs, the PoB SLA program reads the precision it should commit
+/// the SLA score in.
+pub fn setup_pob_sla_prover_input(
+    compound_inclusion_proof: &CompoundInclusionProof,
+    args: &VerifyArgs,
+    precision: SlaScorePrecision,
+) -> SP1Stdin {
+    let mut sp1_stdin = setup_prover_input(compound_inclusion_proof, args);
+    sp1_stdin.write(&(precision as u8));
+    sp1_stdin
+}
+
+/// Read the prover inputs needed for the batch data correctness proof and return the [`SP1Stdin`]
+/// instance. The slots must be for the same blober, ordered and contiguous, so that every slot
+/// starts from the blober hash the previous one ended with.
+pub fn setup_batch_prover_input(batch: &[(CompoundInclusionProof, VerifyArgs)]) -> SP1Stdin {
+    let mut sp1_stdin = SP1Stdin::new();
+    sp1_stdin.write(&(batch.len() as u32));
+    for (compound_inclusion_proof, args) in batch {
+        sp1_stdin.write(compound_inclusion_proof);
+        sp1_stdin.write(args);
+    }
+    sp1_stdin
+}
+
+/// Prepares the inputs the given prover ELF reads, using the default SLA score precision for the
+/// PoB SLA program.
+fn setup_elf_input(
+    compound_inclusion_proof: &CompoundInclusionProof,
+    args: &VerifyArgs,
+    prover_elf: &[u8],
+) -> SP1Stdin {
+    if prover_elf == POB_ELF {
+        setup_pob_sla_prover_input(compound_inclusion_proof, args, SlaScorePrecision::default())
+    } else {
+        setup_prover_input(compound_inclusion_proof, args)
+    }
+}
+
 #[tracing::instrument(level = "info", skip_all, fields(slot = compound_inclusion_proof.target_slot(), blober = %args.blober))]
 pub fn simulate_proof_generation(
     compound_inclusion_proof: &CompoundInclusionProof,
     args: &VerifyArgs,
     prover_elf: &[u8],
 ) -> ProofGenerationResult<(SP1PublicValues, ExecutionReport)> {
-    let sp1_stdin = setup_prover_input(compound_inclusion_proof, args);
+    let sp1_stdin = setup_elf_input(compound_inclusion_proof, args, prover_elf);
 
     let client = ProverClient::from_env();
 
@@ -73,28 +147,93 @@ pub fn simulate_proof_generation(
     Ok((public_values, report))
 }
 
+/// Computes the public values the data correctness program commits to for the given inputs. The
+/// other programs commit to the same values first, followed by their own.
+pub fn expected_public_values(
+    compound_inclusion_proof: &CompoundInclusionProof,
+    args: &VerifyArgs,
+) -> ProofGenerationResult<SP1PublicValues> {
+    let mut public_values = SP1PublicValues::new();
+    public_values.write(&compound_inclusion_proof.blober_pubkey);
+    public_values.write(&compound_inclusion_proof.into_commitment());
+    public_values.write(
+        &args
+            .into_commitment()
+            .map_err(|e| ProofGenerationError::Generate(e.to_string()))?,
+    );
+    Ok(public_values)
+}
+
+/// Runs `simulate` and only runs `prove` if the simulated public values start with the
+/// [`expected_public_values`], so no time is spent on a proof that would not verify.
+pub async fn simulate_then_prove<S, P, Fut>(
+    compound_inclusion_proof: &CompoundInclusionProof,
+    args: &VerifyArgs,
+    simulate: S,
+    prove: P,
+) -> ProofGenerationResult<ProofData>
+where
+    S: FnOnce() -> ProofGenerationResult<(SP1PublicValues, ExecutionReport)>,
+    P: FnOnce() -> Fut,
+    Fut: Future<Output = ProofGenerationResult<ProofData>>,
+{
+    let expected = expected_public_values(compound_inclusion_proof, args)?;
+    let (simulated, _) = simulate()?;
+
+    if !simulated.as_slice().starts_with(expected.as_slice()) {
+        return Err(ProofGenerationError::SimulationMismatch);
+    }
+
+    prove().await
+}
+
+/// Returns the SP1 proof mode that wraps the proof in the given [`ProofSystem`].
+pub fn proof_mode(proof_system: ProofSystem) -> SP1ProofMode {
+    match proof_system {
+        ProofSystem::Groth16 => SP1ProofMode::Groth16,
+        ProofSystem::Plonk => SP1ProofMode::Plonk,
+    }
+}
+
 pub fn run_client(
     compound_inclusion_proof: &CompoundInclusionProof,
     args: &VerifyArgs,
     prover_elf: &[u8],
     prove: bool,
     verify: bool,
+    proof_system: ProofSystem,
 ) -> ProofGenerationResult<(SP1PublicValues, ExecutionReport)> {
-    let sp1_stdin = setup_prover_input(compound_inclusion_proof, args);
+    run_client_with_input(
+        setup_elf_input(compound_inclusion_proof, args, prover_elf),
+        prover_elf,
+        prove,
+        verify,
+        proof_system,
+    )
+}
 
+/// Same as [`run_client`], but with already prepared prover inputs, for programs that read more
+/// than the data correctness inputs.
+pub fn run_client_with_input(
+    sp1_stdin: SP1Stdin,
+    prover_elf: &[u8],
+    prove: bool,
+    verify: bool,
+    proof_system: ProofSystem,
+) -> ProofGenerationResult<(SP1PublicValues, ExecutionReport)> {
     let client = ProverClient::from_env();
 
     if prove {
-        debug!("Generating Groth16 proof");
+        debug!("Generating {proof_system:?} proof");
         let (pk, vk) = client.setup(prover_elf);
         let proof = client
             .prove(&pk, &sp1_stdin)
-            .groth16()
+            .mode(proof_mode(proof_system))
             .run()
             .map_err(|e| ProofGenerationError::Generate(e.to_string()))?;
 
         if verify {
-            debug!("Verifying Groth16 proof");
+            debug!("Verifying {proof_system:?} proof");
             client.verify(&proof, &vk)?;
         }
     }
@@ -108,35 +247,384 @@ pub fn run_client(
     Ok((public_values, report))
 }
 
-#[tracing::instrument(level = "info", skip_all, fields(slot = compound_inclusion_proof.target_slot(), blober = %args.blober))]
+/// Stages of proof generation reported by [`generate_proof_with_progress`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofProgress {
+    /// Setting up the proving and verification keys for the prover ELF.
+    Setup,
+    /// Generating the proof.
+    Proving,
+    /// Verifying the generated proof.
+    Verifying,
+    /// The proof is ready.
+    Done,
+}
+
+/// Generates a proof for the given inputs. If a [`ProofCache`] is passed, a previously generated
+/// proof for the same inputs is returned from it instead, and newly generated proofs are stored.
 pub async fn generate_proof(
     compound_inclusion_proof: &CompoundInclusionProof,
     args: &VerifyArgs,
     prover_elf: &[u8],
+    proof_system: ProofSystem,
+    cache: Option<&ProofCache>,
+) -> ProofGenerationResult<ProofData> {
+    let (progress, _) = mpsc::channel(1);
+    generate_proof_with_progress(
+        compound_inclusion_proof,
+        args,
+        prover_elf,
+        proof_system,
+        cache,
+        progress,
+    )
+    .await
+}
+
+/// Same as [`generate_proof`], but sends a [`ProofProgress`] whenever proof generation moves to the
+/// next stage. Progress is best effort, a closed channel doesn't stop proof generation. A cached
+/// proof only reports [`ProofProgress::Done`].
+#[tracing::instrument(level = "info", skip_all, fields(slot = compound_inclusion_proof.target_slot(), blober = %args.blober))]
+pub async fn generate_proof_with_progress(
+    compound_inclusion_proof: &CompoundInclusionProof,
+    args: &VerifyArgs,
+    prover_elf: &[u8],
+    proof_system: ProofSystem,
+    cache: Option<&ProofCache>,
+    progress: Sender<ProofProgress>,
+) -> ProofGenerationResult<ProofData> {
+    let proof_data = match cache {
+        Some(cache) => {
+            cache
+                .get_or_generate(
+                    compound_inclusion_proof,
+                    args,
+                    prover_elf,
+                    proof_system,
+                    || {
+                        prove(
+                            compound_inclusion_proof,
+                            args,
+                            prover_elf,
+                            proof_system,
+                            &progress,
+                        )
+                    },
+                )
+                .await?
+        }
+        None => {
+            prove(
+                compound_inclusion_proof,
+                args,
+                prover_elf,
+                proof_system,
+                &progress,
+            )
+            .await?
+        }
+    };
+
+    let _ = progress.send(ProofProgress::Done).await;
+    Ok(proof_data)
+}
+
+/// Simulates the batch data correctness proof for the given slots, see
+/// [`setup_batch_prover_input`].
+#[tracing::instrument(level = "info", skip_all, fields(slots = batch.len()))]
+pub fn simulate_batch_proof_generation(
+    batch: &[(CompoundInclusionProof, VerifyArgs)],
+) -> ProofGenerationResult<(SP1PublicValues, ExecutionReport)> {
+    let client = ProverClient::from_env();
+
+    debug!("Simulating batch proof generation");
+    client
+        .execute(DATA_CORRECTNESS_BATCH_ELF, &setup_batch_prover_input(batch))
+        .run()
+        .map_err(|e| ProofGenerationError::Generate(e.to_string()))
+}
+
+/// Generates a single proof covering the data correctness of all given slots, see
+/// [`setup_batch_prover_input`].
+#[tracing::instrument(level = "info", skip_all, fields(slots = batch.len()))]
+pub async fn generate_batch_proof(
+    batch: &[(CompoundInclusionProof, VerifyArgs)],
+    proof_system: ProofSystem,
+) -> ProofGenerationResult<ProofData> {
+    let (progress, _) = mpsc::channel(1);
+    prove_with_input(
+        setup_batch_prover_input(batch),
+        DATA_CORRECTNESS_BATCH_ELF,
+        proof_system,
+        &progress,
+    )
+    .await
+}
+
+async fn prove(
+    compound_inclusion_proof: &CompoundInclusionProof,
+    args: &VerifyArgs,
+    prover_elf: &[u8],
+    proof_system: ProofSystem,
+    progress: &Sender<ProofProgress>,
 ) -> ProofGenerationResult<ProofData> {
-    let sp1_stdin = setup_prover_input(compound_inclusion_proof, args);
+    prove_with_input(
+        setup_elf_input(compound_inclusion_proof, args, prover_elf),
+        prover_elf,
+        proof_system,
+        progress,
+    )
+    .await
+}
 
+async fn prove_with_input(
+    sp1_stdin: SP1Stdin,
+    prover_elf: &[u8],
+    proof_system: ProofSystem,
+    progress: &Sender<ProofProgress>,
+) -> ProofGenerationResult<ProofData> {
+    let _ = progress.send(ProofProgress::Setup).await;
     let client = ProverClient::from_env();
     let (pk, vk) = client.setup(prover_elf);
 
-    info!("Generating Groth16 proof");
-    let proof = spawn_blocking(move || {
+    info!("Generating {proof_system:?} proof");
+    let _ = progress.send(ProofProgress::Proving).await;
+    let (client, proof) = spawn_blocking(move || {
         client
             .prove(&pk, &sp1_stdin)
-            .groth16()
+            .mode(proof_mode(proof_system))
             .run()
+            .map(|proof| (client, proof))
             .map_err(|e| ProofGenerationError::Generate(e.to_string()))
     })
     .await??;
 
-    let proof_bytes = proof
-        .bytes()
-        .try_into()
-        .map_err(|_| ProofGenerationError::Groth16ProofBytes)?;
+    debug!("Verifying {proof_system:?} proof");
+    let _ = progress.send(ProofProgress::Verifying).await;
+    client.verify(&proof, &vk)?;
 
-    Ok(ProofData {
-        proof: proof_bytes,
-        public_values: proof.public_values.to_vec(),
-        verification_key: vk.bytes32(),
-    })
+    let public_values = proof.public_values.to_vec();
+    match proof_system {
+        ProofSystem::Groth16 => {
+            let proof_bytes = proof
+                .bytes()
+                .try_into()
+                .map_err(|_| ProofGenerationError::Groth16ProofBytes)?;
+            Ok(ProofData::groth16(proof_bytes, public_values, vk.bytes32()))
+        }
+        ProofSystem::Plonk => Ok(ProofData {
+            proof: proof.bytes(),
+            public_values,
+            verification_key: vk.bytes32(),
+            proof_system,
+        }),
+    }
+}
+
+/// Verifies a [`ProofData`] against the verification key of the given prover ELF, the same way the
+/// blober program verifies checkpoint proofs on-chain, without re-running the prover.
+///
+/// Only Groth16 proofs are supported, as those are the only ones the on-chain verifier accepts.
+pub fn verify_proof_data(proof: &ProofData, prover_elf: &[u8]) -> ProofGenerationResult {
+    let proof_bytes = proof.groth16_proof().map_err(|e| match e {
+        Groth16ProofError::Length(_) => ProofGenerationError::Groth16ProofBytes,
+        Groth16ProofError::ProofSystem(_) => ProofGenerationError::InvalidProofData(e.to_string()),
+    })?;
+
+    let (_, vk) = ProverClient::from_env().setup(prover_elf);
+
+    Checkpoint::new(proof_bytes, proof.public_values.clone(), vk.bytes32(), 0)
+        .map_err(|e| ProofGenerationError::InvalidProofData(e.to_string()))?
+        .verify_zk_proof()
+        .map_err(|_| ProofGenerationError::ProofDataVerification)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use anchor_lang::prelude::Pubkey;
+    use data_anchor_blober::GROTH16_PROOF_SIZE;
+
+    use super::*;
+    use crate::mock::{MockProver, test_inputs};
+
+    /// Simulates with the proof claiming to be for `simulated_blober`, if given, and proves with
+    /// the original inputs. Returns the result and whether proving was attempted.
+    async fn simulate_then_mock_prove(
+        simulated_blober: Option<Pubkey>,
+    ) -> (ProofGenerationResult<ProofData>, bool) {
+        let (proof, args) = test_inputs();
+        let mut simulated_proof = proof.clone();
+        if let Some(blober) = simulated_blober {
+            simulated_proof.blober_pubkey = blober;
+        }
+        let proved = AtomicBool::new(false);
+
+        let result = simulate_then_prove(
+            &proof,
+            &args,
+            || MockProver::simulate_proof_generation(&simulated_proof, &args, DATA_CORRECTNESS_ELF),
+            || async {
+                proved.store(true, Ordering::SeqCst);
+                MockProver::generate_proof(
+                    &proof,
+                    &args,
+                    DATA_CORRECTNESS_ELF,
+                    ProofSystem::Groth16,
+                )
+                .await
+            },
+        )
+        .await;
+
+        (result, proved.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn matching_simulation_proves() {
+        let (result, proved) = simulate_then_mock_prove(None).await;
+
+        result.unwrap();
+        assert!(proved);
+    }
+
+    #[tokio::test]
+    async fn mismatched_simulation_aborts_before_proving() {
+        let (result, proved) = simulate_then_mock_prove(Some(Pubkey::new_unique())).await;
+
+        assert!(
+            matches!(result, Err(ProofGenerationError::SimulationMismatch)),
+            "Expected SimulationMismatch, got {result:?}"
+        );
+        assert!(!proved);
+    }
+
+    #[test]
+    fn groth16_is_the_default_proof_system() {
+        assert_eq!(ProofSystem::default(), ProofSystem::Groth16);
+    }
+
+    #[test]
+    fn proof_system_selects_proof_mode() {
+        assert!(matches!(
+            proof_mode(ProofSystem::Groth16),
+            SP1ProofMode::Groth16
+        ));
+        assert!(matches!(
+            proof_mode(ProofSystem::Plonk),
+            SP1ProofMode::Plonk
+        ));
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn generated_proof_verifies_and_tampered_proof_fails() {
+        let (proof, args) = test_inputs();
+        let mut proof_data = generate_proof(
+            &proof,
+            &args,
+            DATA_CORRECTNESS_ELF,
+            ProofSystem::Groth16,
+            None,
+        )
+        .await
+        .unwrap();
+
+        verify_proof_data(&proof_data, DATA_CORRECTNESS_ELF).unwrap();
+
+        proof_data.proof[GROTH16_PROOF_SIZE / 2] ^= 1;
+        let err = verify_proof_data(&proof_data, DATA_CORRECTNESS_ELF).unwrap_err();
+        assert!(
+            matches!(err, ProofGenerationError::ProofDataVerification),
+            "Expected ProofDataVerification, got {err:?}"
+        );
+    }
+
+    #[test]
+    fn plonk_proof_data_is_rejected() {
+        let proof_data = ProofData {
+            proof: Vec::new(),
+            public_values: Vec::new(),
+            verification_key: MockProver::verification_key(),
+            proof_system: ProofSystem::Plonk,
+        };
+
+        let err = verify_proof_data(&proof_data, DATA_CORRECTNESS_ELF).unwrap_err();
+        assert!(
+            matches!(err, ProofGenerationError::InvalidProofData(_)),
+            "Expected InvalidProofData, got {err:?}"
+        );
+    }
+
+    /// Reads the batch inputs back the same way the batch program does.
+    fn read_batch_input(sp1_stdin: &SP1Stdin) -> Vec<(CompoundInclusionProof, VerifyArgs)> {
+        let mut buffer = sp1_stdin.buffer.iter();
+        let mut read = || buffer.next().expect("stdin to not be exhausted");
+
+        let count: u32 = bincode::deserialize(read()).unwrap();
+        let batch = (0..count)
+            .map(|_| {
+                (
+                    bincode::deserialize(read()).unwrap(),
+                    bincode::deserialize(read()).unwrap(),
+                )
+            })
+            .collect();
+        assert!(buffer.next().is_none(), "stdin has trailing inputs");
+        batch
+    }
+
+    #[test]
+    fn batch_input_round_trips() {
+        let (proof, args) = test_inputs();
+        let mut other_proof = proof.clone();
+        other_proof.blober_pubkey = Pubkey::new_unique();
+        let batch = vec![(proof.clone(), args.clone()), (other_proof, args.clone())];
+
+        let sp1_stdin = setup_batch_prover_input(&batch);
+        assert_eq!(read_batch_input(&sp1_stdin), batch);
+
+        // Every slot is written exactly like the inputs of a single slot proof.
+        assert_eq!(
+            sp1_stdin.buffer[1..3],
+            setup_prover_input(&proof, &args).buffer
+        );
+        assert!(read_batch_input(&setup_batch_prover_input(&[])).is_empty());
+    }
+
+    #[test]
+    fn batching_uses_fewer_cycles_than_separate_proofs() {
+        const SLOTS: usize = 4;
+        let (proof, args) = test_inputs();
+        let batch = vec![(proof.clone(), args.clone()); SLOTS];
+
+        let (public_values, report) = simulate_batch_proof_generation(&batch).unwrap();
+        let separate_cycles = batch
+            .iter()
+            .map(|(proof, args)| {
+                simulate_proof_generation(proof, args, DATA_CORRECTNESS_ELF)
+                    .unwrap()
+                    .1
+                    .total_instruction_count()
+            })
+            .sum::<u64>();
+
+        assert!(
+            report.total_instruction_count() < separate_cycles,
+            "Batched proof took {} cycles, separate proofs {separate_cycles}",
+            report.total_instruction_count()
+        );
+        assert_eq!(
+            public_values.as_slice().len(),
+            SLOTS * BATCH_PUBLIC_VALUES_RECORD_SIZE
+        );
+        let single_slot = expected_public_values(&proof, &args).unwrap();
+        for record in public_values
+            .as_slice()
+            .chunks_exact(BATCH_PUBLIC_VALUES_RECORD_SIZE)
+        {
+            assert_eq!(record, single_slot.as_slice());
+        }
+    }
 }
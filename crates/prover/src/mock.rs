@@ -0,0 +1,187 @@
+//! A mock prover that returns deterministic dummy proofs without invoking the zkVM, so the proof
+//! request/response flow can be tested without the SP1 toolchain.
+
+use data_anchor_api::ProofData;
+use data_anchor_blober::{GROTH16_PROOF_SIZE, PROOF_VERIFICATION_KEY_SIZE};
+use data_anchor_proofs::compound::{CompoundInclusionProof, VerifyArgs};
+use sp1_sdk::{ExecutionReport, SP1PublicValues};
+use tokio::sync::mpsc::{self, Sender};
+use tracing::debug;
+
+use crate::{ProofGenerationResult, ProofProgress, ProofSystem, expected_public_values};
+
+/// Drop-in replacement for [`crate::generate_proof`], [`crate::generate_proof_with_progress`] and
+/// [`crate::simulate_proof_generation`], without proof caching.
+///
+/// The public values are the same ones the data correctness program commits to, the proof bytes
+/// are zeroed and the verification key is a constant, so mock proofs never verify.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MockProver;
+
+impl MockProver {
+    /// The verification key returned for every mock proof.
+    pub fn verification_key() -> String {
+        format!("0x{}", "0".repeat(PROOF_VERIFICATION_KEY_SIZE - 2))
+    }
+
+    pub fn simulate_proof_generation(
+        compound_inclusion_proof: &CompoundInclusionProof,
+        args: &VerifyArgs,
+        _prover_elf: &[u8],
+    ) -> ProofGenerationResult<(SP1PublicValues, ExecutionReport)> {
+        debug!("Simulating mock proof generation");
+        Ok((
+            expected_public_values(compound_inclusion_proof, args)?,
+            ExecutionReport::default(),
+        ))
+    }
+
+    pub async fn generate_proof(
+        compound_inclusion_proof: &CompoundInclusionProof,
+        args: &VerifyArgs,
+        prover_elf: &[u8],
+        proof_system: ProofSystem,
+    ) -> ProofGenerationResult<ProofData> {
+        let (progress, _) = mpsc::channel(1);
+        Self::generate_proof_with_progress(
+            compound_inclusion_proof,
+            args,
+            prover_elf,
+            proof_system,
+            progress,
+        )
+        .await
+    }
+
+    /// Reports the same stages as [`crate::generate_proof_with_progress`].
+    pub async fn generate_proof_with_progress(
+        compound_inclusion_proof: &CompoundInclusionProof,
+        args: &VerifyArgs,
+        _prover_elf: &[u8],
+        proof_system: ProofSystem,
+        progress: Sender<ProofProgress>,
+    ) -> ProofGenerationResult<ProofData> {
+        let _ = progress.send(ProofProgress::Setup).await;
+
+        debug!("Generating mock {proof_system:?} proof");
+        let _ = progress.send(ProofProgress::Proving).await;
+        let public_values = expected_public_values(compound_inclusion_proof, args)?;
+
+        let _ = progress.send(ProofProgress::Verifying).await;
+        let proof_data = ProofData {
+            proof: vec![0; GROTH16_PROOF_SIZE],
+            public_values: public_values.to_vec(),
+            verification_key: Self::verification_key(),
+            proof_system,
+        };
+
+        let _ = progress.send(ProofProgress::Done).await;
+        Ok(proof_data)
+    }
+}
+
+/// Prover inputs for an empty blober, shared by the tests of this crate.
+#[cfg(test)]
+pub(crate) fn test_inputs() -> (CompoundInclusionProof, VerifyArgs) {
+    use std::collections::BTreeMap;
+
+    use anchor_lang::{AnchorSerialize, Discriminator, prelude::Pubkey};
+    use data_anchor_blober::{initial_hash, state::blober::Blober};
+    use data_anchor_proofs::blober_account_state::BloberAccountStateProof;
+
+    let blober = Pubkey::new_unique();
+    let blober_state = Blober {
+        hash: initial_hash(),
+        slot: 1,
+        caller: Pubkey::new_unique(),
+        namespace: "test".to_owned(),
+    };
+
+    (
+        CompoundInclusionProof::new(
+            Vec::new(),
+            blober,
+            BloberAccountStateProof::new(initial_hash(), 1, BTreeMap::new()),
+        ),
+        VerifyArgs {
+            blober,
+            blober_state: [
+                Blober::DISCRIMINATOR,
+                blober_state.try_to_vec().unwrap().as_ref(),
+            ]
+            .concat(),
+            blobs: Vec::new(),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DATA_CORRECTNESS_ELF;
+
+    #[tokio::test]
+    async fn mock_proof_has_groth16_size() {
+        let (proof, args) = test_inputs();
+
+        let proof_data =
+            MockProver::generate_proof(&proof, &args, DATA_CORRECTNESS_ELF, ProofSystem::default())
+                .await
+                .unwrap();
+
+        assert_eq!(proof_data.proof.len(), GROTH16_PROOF_SIZE);
+        assert_eq!(
+            proof_data.verification_key.len(),
+            PROOF_VERIFICATION_KEY_SIZE
+        );
+        assert_eq!(proof_data.proof_system, ProofSystem::Groth16);
+    }
+
+    #[tokio::test]
+    async fn mock_public_values_match_simulation() {
+        let (proof, args) = test_inputs();
+
+        let (public_values, _) =
+            MockProver::simulate_proof_generation(&proof, &args, DATA_CORRECTNESS_ELF).unwrap();
+        let proof_data =
+            MockProver::generate_proof(&proof, &args, DATA_CORRECTNESS_ELF, ProofSystem::default())
+                .await
+                .unwrap();
+
+        assert_eq!(public_values.to_vec(), proof_data.public_values);
+        assert_eq!(
+            &proof_data.public_values[..32],
+            proof.blober_pubkey.as_ref()
+        );
+    }
+
+    #[tokio::test]
+    async fn mock_reports_progress_stages() {
+        let (proof, args) = test_inputs();
+        let (progress, mut stages) = mpsc::channel(4);
+
+        MockProver::generate_proof_with_progress(
+            &proof,
+            &args,
+            DATA_CORRECTNESS_ELF,
+            ProofSystem::default(),
+            progress,
+        )
+        .await
+        .unwrap();
+
+        let mut received = Vec::new();
+        while let Some(stage) = stages.recv().await {
+            received.push(stage);
+        }
+        assert_eq!(
+            received,
+            [
+                ProofProgress::Setup,
+                ProofProgress::Proving,
+                ProofProgress::Verifying,
+                ProofProgress::Done,
+            ]
+        );
+    }
+}
@@ -42,6 +42,7 @@ mod tests {
     )]
     #[case::lz4_compression(Lz4Compression, true)]
     #[case::flate2_compression(Flate2Compression, true)]
+    #[case::snappy_compression(SnappyCompression, true)]
     #[case::compression_type(CompressionType::default(), true)]
     #[tokio::test]
     async fn test_compression_decompression<C>(
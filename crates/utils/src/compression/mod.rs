@@ -20,6 +20,9 @@ pub enum DataAnchorCompressionError {
     #[error("Flate2 compression error: {0}")]
     Flate2CompressionError(std::io::Error),
 
+    #[error("Snappy compression error: {0}")]
+    SnappyCompressionError(#[from] snap::Error),
+
     #[error("Unknown compression type")]
     UnknownCompressionType,
 
@@ -48,6 +51,7 @@ pub enum CompressionType {
     Lz4Compression,
     Flate2Compression,
     ZstdCompression(ruzstd::encoding::CompressionLevel),
+    SnappyCompression,
 }
 
 impl serde::Serialize for CompressionType {
@@ -93,6 +97,7 @@ impl std::fmt::Debug for CompressionType {
             Self::Lz4Compression => write!(f, "Lz4Compression"),
             Self::Flate2Compression => write!(f, "Flate2Compression"),
             Self::ZstdCompression(level) => write!(f, "{:?}", ZstdCompression(*level)),
+            Self::SnappyCompression => write!(f, "SnappyCompression"),
         }
     }
 }
@@ -103,7 +108,8 @@ impl PartialEq for CompressionType {
         match (self, other) {
             (NoCompression, NoCompression)
             | (Lz4Compression, Lz4Compression)
-            | (Flate2Compression, Flate2Compression) => true,
+            | (Flate2Compression, Flate2Compression)
+            | (SnappyCompression, SnappyCompression) => true,
             (ZstdCompression(l), ZstdCompression(r)) => {
                 use ruzstd::encoding::CompressionLevel::*;
                 matches!(
@@ -129,6 +135,7 @@ impl std::fmt::Display for CompressionType {
             CompressionType::Lz4Compression => write!(f, "lz4_compression"),
             CompressionType::Flate2Compression => write!(f, "flate2_compression"),
             CompressionType::ZstdCompression(level) => write!(f, "{}", ZstdCompression(*level)),
+            CompressionType::SnappyCompression => write!(f, "snappy_compression"),
         }
     }
 }
@@ -141,6 +148,7 @@ const ZSTD_FASTEST_BYTE: u8 = 4;
 const ZSTD_DEFAULT_BYTE: u8 = 5;
 const ZSTD_BETTER_BYTE: u8 = 6;
 const ZSTD_BEST_BYTE: u8 = 7;
+const SNAPPY_COMPRESSION_BYTE: u8 = 8;
 
 impl From<CompressionType> for u8 {
     fn from(value: CompressionType) -> Self {
@@ -159,6 +167,7 @@ impl From<CompressionType> for u8 {
                     Best => ZSTD_BEST_BYTE,
                 }
             }
+            SnappyCompression => SNAPPY_COMPRESSION_BYTE,
         }
     }
 }
@@ -178,6 +187,7 @@ impl TryFrom<u8> for CompressionType {
             ZSTD_DEFAULT_BYTE => Ok(ZstdCompression(Default)),
             ZSTD_BETTER_BYTE => Ok(ZstdCompression(Better)),
             ZSTD_BEST_BYTE => Ok(ZstdCompression(Best)),
+            SNAPPY_COMPRESSION_BYTE => Ok(SnappyCompression),
             _ => Err(DataAnchorCompressionError::UnknownCompressionType),
         }
     }
@@ -224,6 +234,46 @@ impl CompressionType {
 
         Ok(data)
     }
+
+    /// Wrap a reader of compressed data, including the marker byte, in a reader of the
+    /// decompressed data.
+    ///
+    /// Flate2 and Zstd data is decompressed incrementally as it is read. Lz4 and Snappy use block
+    /// formats that can only be decompressed as a whole, so their data is read and decompressed
+    /// up front.
+    pub fn decompress_reader<'a, R>(
+        mut reader: R,
+    ) -> DataAnchorCompressionResult<Box<dyn Read + 'a>>
+    where
+        R: Read + 'a,
+    {
+        let mut compression_type_byte = [0u8; 1];
+        if reader.read(&mut compression_type_byte)? == 0 {
+            return Err(DataAnchorCompressionError::NoDataToDecompress);
+        }
+
+        Ok(match CompressionType::try_from(compression_type_byte[0])? {
+            CompressionType::NoCompression => Box::new(reader),
+            CompressionType::Flate2Compression => Box::new(flate2::read::GzDecoder::new(reader)),
+            CompressionType::ZstdCompression(_) => {
+                Box::new(ruzstd::decoding::StreamingDecoder::new(reader)?)
+            }
+            CompressionType::Lz4Compression => {
+                let mut data = Vec::new();
+                reader.read_to_end(&mut data)?;
+                Box::new(std::io::Cursor::new(lz4_flex::decompress_size_prepended(
+                    &data,
+                )?))
+            }
+            CompressionType::SnappyCompression => {
+                let mut data = Vec::new();
+                reader.read_to_end(&mut data)?;
+                Box::new(std::io::Cursor::new(
+                    snap::raw::Decoder::new().decompress_vec(&data)?,
+                ))
+            }
+        })
+    }
 }
 
 impl DataAnchorCompression for CompressionType {
@@ -233,6 +283,7 @@ impl DataAnchorCompression for CompressionType {
             CompressionType::Lz4Compression => Lz4Compression.compress(data),
             CompressionType::Flate2Compression => Flate2Compression.compress(data),
             CompressionType::ZstdCompression(level) => ZstdCompression(*level).compress(data),
+            CompressionType::SnappyCompression => SnappyCompression.compress(data),
         }
     }
 
@@ -244,6 +295,7 @@ impl DataAnchorCompression for CompressionType {
             CompressionType::Lz4Compression => Lz4Compression.decompress(data),
             CompressionType::Flate2Compression => Flate2Compression.decompress(data),
             CompressionType::ZstdCompression(level) => ZstdCompression(level).decompress(data),
+            CompressionType::SnappyCompression => SnappyCompression.decompress(data),
         }
     }
 }
@@ -366,6 +418,20 @@ impl DataAnchorCompression for Flate2Compression {
     }
 }
 
+#[derive(Debug, Clone, Copy, std::default::Default)]
+pub struct SnappyCompression;
+
+impl DataAnchorCompression for SnappyCompression {
+    fn compress(&self, data: &[u8]) -> DataAnchorCompressionResult<Vec<u8>> {
+        Ok(CompressionType::SnappyCompression.mark(snap::raw::Encoder::new().compress_vec(data)?))
+    }
+
+    fn decompress(&self, data: &[u8]) -> DataAnchorCompressionResult<Vec<u8>> {
+        Ok(snap::raw::Decoder::new()
+            .decompress_vec(CompressionType::SnappyCompression.assert_compression_type(data)?)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rstest::rstest;
@@ -382,6 +448,7 @@ mod tests {
     )]
     #[case::lz4_compression(Lz4Compression, true)]
     #[case::flate2_compression(Flate2Compression, true)]
+    #[case::snappy_compression(SnappyCompression, true)]
     #[case::compression_type(CompressionType::default(), true)]
     fn test_compression_decompression<C>(
         #[case] compression: C,
@@ -405,5 +472,12 @@ mod tests {
         }
         let decompressed_data = compression.decompress(&compressed_data).unwrap();
         assert_eq!(decompressed_data, data);
+
+        let mut streamed_data = Vec::new();
+        CompressionType::decompress_reader(compressed_data.as_slice())
+            .unwrap()
+            .read_to_end(&mut streamed_data)
+            .unwrap();
+        assert_eq!(streamed_data, data);
     }
 }
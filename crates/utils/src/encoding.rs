@@ -1,8 +1,11 @@
+use alloc::vec::Vec;
+
 #[derive(Debug, thiserror::Error)]
 pub enum DataAnchorEncodingError {
     #[error("Postcard encoding error: {0}")]
     Postcard(#[from] postcard::Error),
 
+    #[cfg(feature = "std")]
     #[error("Bincode encoding error: {0}")]
     Bincode(#[from] bincode::Error),
 
@@ -12,15 +15,26 @@ pub enum DataAnchorEncodingError {
     #[error("Unknown encoding type")]
     UnknownEncodingType,
 
+    #[error("Encoding type {0} is not available without the std feature")]
+    UnsupportedEncodingType(EncodingType),
+
     #[error("Encoding type mismatch expected: {0:?}, found: {1:?}")]
     EncodingTypeMismatch(EncodingType, EncodingType),
 
     #[error("No data to decode")]
     NoDataToDecode,
 
+    // Not a `#[from]` source, since borsh's `no_std` I/O error doesn't implement `Error`.
     #[cfg(feature = "borsh")]
     #[error("Borsh encoding error: {0}")]
-    Borsh(#[from] borsh::io::Error),
+    Borsh(borsh::io::Error),
+}
+
+#[cfg(feature = "borsh")]
+impl From<borsh::io::Error> for DataAnchorEncodingError {
+    fn from(error: borsh::io::Error) -> Self {
+        Self::Borsh(error)
+    }
 }
 
 pub type DataAnchorEncodingResult<T = ()> = Result<T, DataAnchorEncodingError>;
@@ -59,8 +73,12 @@ pub trait DataAnchorEncoding {
     fn decode<T: Decodable>(&self, data: &[u8]) -> DataAnchorEncodingResult<T>;
 }
 
+/// The encoding used for a blob, stored as a marker byte in front of the encoded data.
+///
+/// Every variant is always present so that the marker bytes and serialized representation are the
+/// same with and without `std`, but [`EncodingType::Bincode`] can only be used with `std`.
 #[derive(
-    Debug, Clone, Copy, PartialEq, Eq, std::default::Default, serde::Serialize, serde::Deserialize,
+    Debug, Clone, Copy, PartialEq, Eq, core::default::Default, serde::Serialize, serde::Deserialize,
 )]
 #[cfg_attr(
     feature = "borsh",
@@ -76,8 +94,8 @@ pub enum EncodingType {
     Borsh,
 }
 
-impl std::fmt::Display for EncodingType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for EncodingType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             EncodingType::Postcard => write!(f, "postcard"),
             EncodingType::Bincode => write!(f, "bincode"),
@@ -146,7 +164,10 @@ impl DataAnchorEncoding for EncodingType {
     fn encode<T: Encodable>(&self, data: &T) -> DataAnchorEncodingResult<Vec<u8>> {
         match self {
             EncodingType::Postcard => Postcard.encode(data),
+            #[cfg(feature = "std")]
             EncodingType::Bincode => Bincode.encode(data),
+            #[cfg(not(feature = "std"))]
+            EncodingType::Bincode => Err(DataAnchorEncodingError::UnsupportedEncodingType(*self)),
             EncodingType::Json => Json.encode(data),
             #[cfg(feature = "borsh")]
             EncodingType::Borsh => Borsh.encode(data),
@@ -158,7 +179,12 @@ impl DataAnchorEncoding for EncodingType {
 
         match encoding_type {
             EncodingType::Postcard => Postcard.decode(data),
+            #[cfg(feature = "std")]
             EncodingType::Bincode => Bincode.decode(data),
+            #[cfg(not(feature = "std"))]
+            EncodingType::Bincode => Err(DataAnchorEncodingError::UnsupportedEncodingType(
+                encoding_type,
+            )),
             EncodingType::Json => Json.decode(data),
             #[cfg(feature = "borsh")]
             EncodingType::Borsh => Borsh.decode(data),
@@ -166,7 +192,7 @@ impl DataAnchorEncoding for EncodingType {
     }
 }
 
-#[derive(Debug, Clone, Copy, std::default::Default)]
+#[derive(Debug, Clone, Copy, core::default::Default)]
 pub struct Postcard;
 
 pub use Postcard as Default;
@@ -183,9 +209,11 @@ impl DataAnchorEncoding for Postcard {
     }
 }
 
-#[derive(Debug, Clone, Copy, std::default::Default)]
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, core::default::Default)]
 pub struct Bincode;
 
+#[cfg(feature = "std")]
 impl DataAnchorEncoding for Bincode {
     fn encode<T: Encodable>(&self, data: &T) -> DataAnchorEncodingResult<Vec<u8>> {
         Ok(EncodingType::Bincode.mark(bincode::serialize(data)?))
@@ -198,7 +226,7 @@ impl DataAnchorEncoding for Bincode {
     }
 }
 
-#[derive(Debug, Clone, Copy, std::default::Default)]
+#[derive(Debug, Clone, Copy, core::default::Default)]
 pub struct Json;
 
 impl DataAnchorEncoding for Json {
@@ -214,7 +242,7 @@ impl DataAnchorEncoding for Json {
 }
 
 #[cfg(feature = "borsh")]
-#[derive(Debug, Clone, Copy, std::default::Default)]
+#[derive(Debug, Clone, Copy, core::default::Default)]
 pub struct Borsh;
 
 #[cfg(feature = "borsh")]
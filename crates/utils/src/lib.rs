@@ -1,3 +1,12 @@
+//! Encoding and compression utilities for Data Anchor.
+//!
+//! Without the default `std` feature the crate is `no_std` and only requires `alloc`, so the
+//! [`encoding`] module can be used inside the zkVM. Postcard, JSON and Borsh (with the `borsh`
+//! feature) are available without `std`. Bincode and the `compression` module require `std`.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 #[cfg(feature = "compression")]
 pub mod compression;
 pub mod encoding;
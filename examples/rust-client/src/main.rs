@@ -1,7 +1,7 @@
 use std::{path::PathBuf, sync::Arc, time::Duration};
 
 use clap::Parser;
-use data_anchor_client::{DataAnchorClient, FeeStrategy};
+use data_anchor_client::{DataAnchorClient, FeeStrategy, UploadOptions};
 use serde_json::json;
 use solana_cli_config::Config;
 use solana_keypair::Keypair;
@@ -64,23 +64,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // ─── 1. Initialize blober ─────────────────────────────────────────────────────
     // Create the on-chain storage container (PDA) for our namespace
     println!("\n1. Initializing Data Anchor blober");
-    match client
-        .initialize_blober(FeeStrategy::default(), args.namespace.clone().into(), None)
-        .await
+    if client
+        .initialize_blober_if_absent(FeeStrategy::default(), args.namespace.clone().into(), None)
+        .await?
     {
-        Ok(_) => println!("Blober initialized for namespace '{}'", args.namespace),
-        Err(e) => {
-            if e.to_string().contains("AccountExists")
-                || e.to_string().contains("Account already exists")
-            {
-                println!(
-                    "Blober already exists for namespace '{}', continuing...",
-                    args.namespace
-                );
-            } else {
-                return Err(e.into());
-            }
-        }
+        println!("Blober initialized for namespace '{}'", args.namespace);
+    } else {
+        println!(
+            "Blober already exists for namespace '{}', continuing...",
+            args.namespace
+        );
     }
 
     // ─── 2. Write dynamic JSON blob (can be skipped if you want to upload a static blob) ───────────────────────────────
@@ -106,19 +99,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // ─── 3. Upload blob ───────────────────────────────────────────────────────────
     // Send the payload on-chain, capture its signature and ledger slot
     println!("\n3. Uploading blob");
-    let (outcomes, _blob_addr) = client
+    let upload = client
         .upload_blob(
             &payload,
             FeeStrategy::default(),
             &args.namespace,
             Some(Duration::from_secs(10)),
+            UploadOptions::default(),
         )
         .await?;
-    if outcomes.is_empty() {
-        panic!("No upload outcomes returned");
-    }
-    let slot = outcomes[0].slot;
-    let sigs: Vec<_> = outcomes.iter().map(|o| o.signature).collect();
+    let Some(slot) = upload.finalize_slot else {
+        panic!("Blob upload was not finalized");
+    };
+    let sigs: Vec<_> = upload.transactions.iter().map(|o| o.signature).collect();
     println!("  signature: {:?}", sigs[0]);
     println!("  slot:      {}", slot);
 
@@ -30,9 +30,10 @@ pub const MAX_BLOB_SIZE: u32 = MAX_CHUNKS as u32 * CHUNK_SIZE as u32;
 #[constant]
 pub const CHUNK_SIZE: u16 = 915;
 
-/// The maximum length of a namespace string.
+/// The maximum length of a namespace string, in bytes. The namespace is a seed of the blober PDA,
+/// so it can't be longer than a single seed.
 #[constant]
-pub const MAX_NAMESPACE_LENGTH: u8 = 100;
+pub const MAX_NAMESPACE_LENGTH: u8 = 32;
 
 /// The max size of data for a compound transaction containing all three (declare, insert and finalize) instructions.
 pub const COMPOUND_TX_SIZE: u16 = 848;
@@ -28,4 +28,22 @@ pub enum ErrorCode {
     CheckpointWithoutConfig,
     #[msg("Blob is missing chunks, can't be completed in this state")]
     BlobNotComplete,
+    #[msg("Chunk data does not match its CRC")]
+    ChunkCrcMismatch,
+    #[msg("Only the current trusted caller can set a new trusted caller")]
+    NotTrustedCaller,
+    #[msg("No blobs were passed to finalize")]
+    NoBlobsToFinalize,
+    #[msg("Account is not a blob of this blober")]
+    BlobNotInBlober,
+    #[msg("Blob has not expired yet")]
+    BlobNotExpired,
+    #[msg("Namespace must be 1 to 32 bytes of printable ASCII without whitespace")]
+    InvalidNamespace,
+    #[msg("Chunk length doesn't match the blob's declared size")]
+    ChunkSizeMismatch,
+    #[msg("Public values are too short to hold the blober address and hashes")]
+    PublicValuesTooShort,
+    #[msg("Chunk index is out of bounds for the blob's declared size")]
+    ChunkIndexOutOfBounds,
 }
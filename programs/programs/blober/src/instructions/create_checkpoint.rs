@@ -61,7 +61,8 @@ pub fn create_checkpoint_handler(
 ) -> Result<()> {
     let new_checkpoint = Checkpoint::new(proof, public_values, verification_key, slot)?;
 
-    let public_value_blober = new_checkpoint.blober()?;
+    // The accessors only check their own field, so the whole layout is validated up front.
+    let public_value_blober = new_checkpoint.parsed_public_values()?.blober();
 
     if public_value_blober != blober {
         return Err(error!(ErrorCode::BloberMismatch));
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{blob::Blob, error::ErrorCode, state::blober::Blober, SEED};
+
+/// Closes an abandoned blob. Anyone can call this once the blob has expired, the rent always goes
+/// back to the payer that declared it.
+#[derive(Accounts)]
+pub struct DiscardExpired<'info> {
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            SEED,
+            payer.key().as_ref(),
+            blober.key().as_ref(),
+            blob.timestamp.to_le_bytes().as_ref(),
+            blob.size.to_le_bytes().as_ref(),
+        ],
+        bump = blob.bump,
+    )]
+    pub blob: Account<'info, Blob>,
+
+    pub blober: Account<'info, Blober>,
+
+    /// CHECK: The payer that declared the blob, checked by the blob's seeds.
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+pub fn discard_expired_handler(ctx: Context<DiscardExpired>) -> Result<()> {
+    require!(
+        ctx.accounts.blob.is_expired(Clock::get()?.slot),
+        ErrorCode::BlobNotExpired
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{
+        prelude::{AccountMeta, Pubkey},
+        ToAccountMetas,
+    };
+
+    use crate::accounts::DiscardExpired;
+
+    #[test]
+    fn test_first_account_is_the_blob() {
+        let blob = Pubkey::new_unique();
+        let blober = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+
+        let account = DiscardExpired {
+            blob,
+            blober,
+            payer,
+        };
+
+        let expected = AccountMeta {
+            pubkey: blob,
+            is_signer: false,
+            is_writable: true,
+        };
+
+        let is_signer = None;
+        let actual = &account.to_account_metas(is_signer)[0];
+        assert_eq!(actual, &expected);
+    }
+}
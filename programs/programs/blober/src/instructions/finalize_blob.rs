@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    blob::Blob, error::ErrorCode, hash_blob, state::blober::Blober, BLOB_DATA_END, BLOB_DATA_START,
-    SEED,
+    blob::Blob,
+    hash_blob,
+    state::{blober::Blober, events::BlobFinalized},
+    BLOB_DATA_END, BLOB_DATA_START, SEED,
 };
 
 #[derive(Accounts)]
@@ -32,7 +34,7 @@ pub struct FinalizeBlob<'info> {
 }
 
 pub fn finalize_blob_handler(ctx: Context<FinalizeBlob>) -> Result<()> {
-    require!(ctx.accounts.blob.is_complete(), ErrorCode::BlobNotComplete);
+    ctx.accounts.blob.check_finalizable()?;
 
     let blob_info = ctx.accounts.blob.to_account_info();
 
@@ -40,9 +42,16 @@ pub fn finalize_blob_handler(ctx: Context<FinalizeBlob>) -> Result<()> {
 
     let blob_hash = hash_blob(blob_info.key, blob_digest_and_size);
 
-    ctx.accounts
-        .blober
-        .store_hash(&blob_hash, Clock::get()?.slot);
+    let slot = Clock::get()?.slot;
+    ctx.accounts.blober.store_hash(&blob_hash, slot);
+
+    emit!(BlobFinalized {
+        blober: ctx.accounts.blober.key(),
+        blob: ctx.accounts.blob.key(),
+        digest: *ctx.accounts.blob.blob_digest(),
+        size: ctx.accounts.blob.size(),
+        slot,
+    });
 
     Ok(())
 }
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    blob::Blob,
+    error::ErrorCode,
+    hash_blob,
+    state::{blober::Blober, events::BlobFinalized},
+    BLOB_DATA_END, BLOB_DATA_START, SEED,
+};
+
+#[derive(Accounts)]
+pub struct FinalizeBlobs<'info> {
+    #[account(
+        mut,
+        constraint = blober.caller == payer.key(),
+    )]
+    pub blober: Account<'info, Blober>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+/// Finalizes every blob passed in the remaining accounts, in order. The result is the same as
+/// finalizing them one by one in the same slot.
+pub fn finalize_blobs_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizeBlobs<'info>>,
+) -> Result<()> {
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        ErrorCode::NoBlobsToFinalize
+    );
+
+    let blober_key = ctx.accounts.blober.key();
+    let payer = ctx.accounts.payer.to_account_info();
+    let slot = Clock::get()?.slot;
+
+    for blob_info in ctx.remaining_accounts {
+        // The blober is owned by this program too, so it must never be hashed into itself.
+        require_keys_neq!(blob_info.key(), blober_key, ErrorCode::BlobNotInBlober);
+        require!(blob_info.is_writable, ErrorCode::BlobNotInBlober);
+
+        let blob = Account::<Blob>::try_from(blob_info)?;
+        let expected = Pubkey::create_program_address(
+            &[
+                SEED,
+                payer.key().as_ref(),
+                blober_key.as_ref(),
+                blob.timestamp.to_le_bytes().as_ref(),
+                blob.size.to_le_bytes().as_ref(),
+                &[blob.bump],
+            ],
+            ctx.program_id,
+        )
+        .map_err(|_| error!(ErrorCode::BlobNotInBlober))?;
+        require_keys_eq!(blob_info.key(), expected, ErrorCode::BlobNotInBlober);
+        blob.check_finalizable()?;
+
+        let blob_hash = hash_blob(
+            blob_info.key,
+            &blob_info.data.borrow()[BLOB_DATA_START..BLOB_DATA_END],
+        );
+        ctx.accounts.blober.store_hash(&blob_hash, slot);
+
+        emit!(BlobFinalized {
+            blober: blober_key,
+            blob: blob_info.key(),
+            digest: *blob.blob_digest(),
+            size: blob.size(),
+            slot,
+        });
+
+        // Closing hands the account back to the system program, so passing the same blob twice
+        // fails on the second deserialization.
+        blob.close(payer.clone())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{
+        prelude::{AccountMeta, Pubkey},
+        ToAccountMetas,
+    };
+
+    use crate::accounts::FinalizeBlobs;
+
+    #[test]
+    fn test_first_account_is_the_blober() {
+        let blober = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+
+        let account = FinalizeBlobs { blober, payer };
+
+        let expected = AccountMeta {
+            pubkey: blober,
+            is_signer: false,
+            is_writable: true,
+        };
+
+        let is_signer = None;
+        let actual = &account.to_account_metas(is_signer)[0];
+        assert_eq!(actual, &expected);
+    }
+}
@@ -1,6 +1,6 @@
 use anchor_lang::{prelude::*, Discriminator};
 
-use crate::{initial_hash, state::blober::Blober, SEED};
+use crate::{error::ErrorCode, initial_hash, is_valid_namespace, state::blober::Blober, SEED};
 
 #[derive(Accounts)]
 #[instruction(namespace: String)]
@@ -29,6 +29,8 @@ pub fn initialize_handler(
     namespace: String,
     trusted: Pubkey,
 ) -> Result<()> {
+    require!(is_valid_namespace(&namespace), ErrorCode::InvalidNamespace);
+
     ctx.accounts.blober.caller = trusted;
     ctx.accounts.blober.namespace = namespace;
     ctx.accounts.blober.hash = initial_hash();
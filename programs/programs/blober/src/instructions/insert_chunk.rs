@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{blob::Blob, state::blober::Blober, SEED};
+use crate::{blob::Blob, chunk_crc, error::ErrorCode, state::blober::Blober, SEED};
 
 #[derive(Accounts)]
 pub struct InsertChunk<'info> {
@@ -26,7 +26,25 @@ pub struct InsertChunk<'info> {
     pub payer: Signer<'info>,
 }
 
-pub fn insert_chunk_handler(ctx: Context<InsertChunk>, idx: u16, data: Vec<u8>) -> Result<()> {
+pub fn insert_chunk_handler(
+    ctx: Context<InsertChunk>,
+    idx: u16,
+    data: Vec<u8>,
+    crc: Option<u32>,
+) -> Result<()> {
+    require_gt!(
+        ctx.accounts.blob.num_chunks(),
+        idx,
+        ErrorCode::ChunkIndexOutOfBounds
+    );
+    if let Some(crc) = crc {
+        require_eq!(chunk_crc(&data), crc, ErrorCode::ChunkCrcMismatch);
+    }
+    require_eq!(
+        data.len() as u32,
+        ctx.accounts.blob.expected_chunk_len(idx),
+        ErrorCode::ChunkSizeMismatch
+    );
     ctx.accounts.blob.insert(Clock::get()?.slot, idx, &data);
     Ok(())
 }
@@ -3,15 +3,21 @@ pub mod close_blob;
 pub mod configure_checkpoint;
 pub mod create_checkpoint;
 pub mod declare_blob;
+pub mod discard_expired;
 pub mod finalize_blob;
+pub mod finalize_blobs;
 pub mod initialize;
 pub mod insert_chunk;
+pub mod set_trusted;
 
 pub use close::*;
 pub use close_blob::*;
 pub use configure_checkpoint::*;
 pub use create_checkpoint::*;
 pub use declare_blob::*;
+pub use discard_expired::*;
 pub use finalize_blob::*;
+pub use finalize_blobs::*;
 pub use initialize::*;
 pub use insert_chunk::*;
+pub use set_trusted::*;
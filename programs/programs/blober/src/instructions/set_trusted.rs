@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::blober::Blober};
+
+#[derive(Accounts)]
+pub struct SetTrusted<'info> {
+    #[account(
+        mut,
+        constraint = blober.caller == caller.key() @ ErrorCode::NotTrustedCaller,
+    )]
+    pub blober: Account<'info, Blober>,
+
+    pub caller: Signer<'info>,
+}
+
+pub fn set_trusted_handler(ctx: Context<SetTrusted>, new_trusted: Pubkey) -> Result<()> {
+    ctx.accounts.blober.caller = new_trusted;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{
+        prelude::{AccountMeta, Pubkey},
+        ToAccountMetas,
+    };
+
+    use crate::accounts::SetTrusted;
+
+    #[test]
+    fn test_first_account_is_the_blober() {
+        let blober = Pubkey::new_unique();
+        let caller = Pubkey::new_unique();
+
+        let account = SetTrusted { blober, caller };
+
+        let expected = AccountMeta {
+            pubkey: blober,
+            is_signer: false,
+            is_writable: true,
+        };
+
+        let is_signer = None;
+        let actual = &account.to_account_metas(is_signer)[0];
+        assert_eq!(actual, &expected);
+    }
+}
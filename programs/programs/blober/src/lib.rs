@@ -32,21 +32,44 @@ pub mod blober {
     }
 
     pub fn insert_chunk(ctx: Context<InsertChunk>, idx: u16, data: Vec<u8>) -> Result<()> {
-        insert_chunk_handler(ctx, idx, data)
+        insert_chunk_handler(ctx, idx, data, None)
+    }
+
+    pub fn insert_chunk_with_crc(
+        ctx: Context<InsertChunk>,
+        idx: u16,
+        data: Vec<u8>,
+        crc: u32,
+    ) -> Result<()> {
+        insert_chunk_handler(ctx, idx, data, Some(crc))
     }
 
     pub fn finalize_blob(ctx: Context<FinalizeBlob>) -> Result<()> {
         finalize_blob_handler(ctx)
     }
 
+    pub fn finalize_blobs<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeBlobs<'info>>,
+    ) -> Result<()> {
+        finalize_blobs_handler(ctx)
+    }
+
     pub fn discard_blob(ctx: Context<DiscardBlob>) -> Result<()> {
         discard_blob_handler(ctx)
     }
 
+    pub fn discard_expired(ctx: Context<DiscardExpired>) -> Result<()> {
+        discard_expired_handler(ctx)
+    }
+
     pub fn close(ctx: Context<Close>) -> Result<()> {
         close_handler(ctx)
     }
 
+    pub fn set_trusted(ctx: Context<SetTrusted>, new_trusted: Pubkey) -> Result<()> {
+        set_trusted_handler(ctx, new_trusted)
+    }
+
     pub fn configure_checkpoint(
         ctx: Context<ConfigureCheckpoint>,
         authority: Pubkey,
@@ -75,6 +98,35 @@ pub fn hash_leaf(
     hash::hashv(&[&previous_hash, &chunk_index.to_le_bytes(), chunk_data]).to_bytes()
 }
 
+/// The lookup table for [`chunk_crc`], using the reflected CRC-32 (IEEE) polynomial.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Computes the CRC-32 (IEEE) checksum of a chunk, which can be sent along with the chunk to have
+/// corrupted chunks rejected before they are hashed into the blob digest.
+pub fn chunk_crc(chunk_data: &[u8]) -> u32 {
+    !chunk_data.iter().fold(!0u32, |crc, byte| {
+        CRC32_TABLE[((crc ^ *byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
 /// Computes a blob digest of all the chunks of a blob.
 pub fn compute_blob_digest<A: AsRef<[u8]>>(chunks: &[(u16, A)]) -> [u8; HASH_BYTES] {
     chunks
@@ -110,6 +162,15 @@ pub fn find_blober_address(program_id: Pubkey, payer: Pubkey, namespace: &str) -
     Pubkey::find_program_address(&[SEED, payer.as_ref(), namespace.as_bytes()], &program_id).0
 }
 
+/// Checks that a namespace can be used to initialize a blober: it must be non-empty, at most
+/// [`MAX_NAMESPACE_LENGTH`] bytes long and only contain printable ASCII characters without
+/// whitespace.
+pub fn is_valid_namespace(namespace: &str) -> bool {
+    !namespace.is_empty()
+        && namespace.len() <= MAX_NAMESPACE_LENGTH as usize
+        && namespace.bytes().all(|byte| byte.is_ascii_graphic())
+}
+
 /// Retrieves the PDA address of a checkpoint account to store proofs and public values.
 pub fn find_checkpoint_address(program_id: Pubkey, blober: Pubkey) -> Pubkey {
     Pubkey::find_program_address(&[SEED, CHECKPOINT_SEED, blober.as_ref()], &program_id).0
@@ -154,3 +215,18 @@ pub fn hash_blob(key: &Pubkey, data: &[u8]) -> [u8; HASH_BYTES] {
 pub fn merge_hashes(current: &[u8; HASH_BYTES], new: &[u8; HASH_BYTES]) -> [u8; HASH_BYTES] {
     hash::hashv(&[current, new]).to_bytes()
 }
+
+/// Predicts the blober hash after finalizing the given blobs, in order, on top of the `current`
+/// hash. Each blob is given as its digest, size and address, and is hashed the same way
+/// `finalize_blob` hashes the blob account.
+pub fn predict_blober_hash(
+    current: [u8; HASH_BYTES],
+    finalized: &[([u8; HASH_BYTES], u32, Pubkey)],
+) -> [u8; HASH_BYTES] {
+    finalized
+        .iter()
+        .fold(current, |hash, (digest, size, blob)| {
+            let blob_hash = hash_blob(blob, &[digest.as_ref(), &size.to_le_bytes()].concat());
+            merge_hashes(&hash, &blob_hash)
+        })
+}
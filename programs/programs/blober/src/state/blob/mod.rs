@@ -57,10 +57,51 @@ impl Blob {
         &self.digest
     }
 
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    pub fn last_updated_at(&self) -> u64 {
+        self.last_updated_at
+    }
+
+    pub fn num_chunks(&self) -> u16 {
+        self.bitmap.num_chunks
+    }
+
     pub fn is_complete(&self) -> bool {
         self.bitmap.is_complete()
     }
 
+    /// Checks that the blob can be finalized, meaning every chunk has been inserted. Chunk lengths
+    /// are checked against [`Blob::expected_chunk_len`] as they are inserted, so a complete blob
+    /// holds exactly its declared size.
+    pub fn check_finalizable(&self) -> Result<()> {
+        require!(self.is_complete(), ErrorCode::BlobNotComplete);
+        Ok(())
+    }
+
+    /// The length the chunk at `chunk_index` must have for the chunks to add up to the declared
+    /// size: [`CHUNK_SIZE`] for every chunk but the last, which holds the remainder.
+    pub fn expected_chunk_len(&self, chunk_index: u16) -> u32 {
+        let offset = chunk_index as u32 * CHUNK_SIZE as u32;
+        self.size.saturating_sub(offset).min(CHUNK_SIZE as u32)
+    }
+
+    /// Whether the blob was created more than [`BLOB_SLOT_TOTAL_DELAY_LIMIT`] slots before `slot`,
+    /// after which no more chunks can be inserted into it.
+    pub fn is_expired(&self, slot: u64) -> bool {
+        slot.saturating_sub(self.created_at) > BLOB_SLOT_TOTAL_DELAY_LIMIT
+    }
+
     pub fn insert(&mut self, slot: u64, chunk_index: u16, chunk_data: &[u8]) {
         if self.check_preconditions(slot, chunk_index).is_err() {
             return;
@@ -106,3 +106,14 @@ fn test_blob() {
     assert_eq!(current_digest, acc.digest);
     assert_eq!(acc.blob_digest(), &current_digest);
 }
+
+#[test]
+fn expected_chunk_len_covers_declared_size() {
+    let blob = Blob::new(0, 0, 2 * CHUNK_SIZE as u32 + 1, 0);
+    assert_eq!(blob.expected_chunk_len(0), CHUNK_SIZE as u32);
+    assert_eq!(blob.expected_chunk_len(1), CHUNK_SIZE as u32);
+    assert_eq!(blob.expected_chunk_len(2), 1);
+
+    let blob = Blob::new(0, 0, CHUNK_SIZE as u32, 0);
+    assert_eq!(blob.expected_chunk_len(0), CHUNK_SIZE as u32);
+}
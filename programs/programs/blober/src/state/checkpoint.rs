@@ -73,35 +73,39 @@ impl Checkpoint {
         .map_err(|_| error!(ErrorCode::ProofVerificationFailed))
     }
 
+    /// Parses the public values of the checkpoint proof.
+    pub fn parsed_public_values(&self) -> Result<CheckpointPublicValues<'_>> {
+        CheckpointPublicValues::new(&self.public_values)
+    }
+
+    /// The blober address, which only needs the public values to be long enough to hold it.
     pub fn blober(&self) -> Result<Pubkey> {
-        bincode::deserialize::<Pubkey>(
-            self.public_values
-                .get(..PUBKEY_BYTES)
-                .ok_or_else(|| error!(ErrorCode::InvalidPublicValue))?,
-        )
-        .map_err(|_| error!(ErrorCode::InvalidPublicValue))
+        CheckpointPublicValues::field(&self.public_values, CheckpointPublicValues::BLOBER_OFFSET)
+            .map(Pubkey::new_from_array)
     }
 
+    /// The blober hash before the proven slots, which only needs the public values to be long
+    /// enough to hold it.
     pub fn initial_hash(&self) -> Result<[u8; HASH_BYTES]> {
-        bincode::deserialize::<[u8; HASH_BYTES]>(
-            self.public_values
-                .get(PUBKEY_BYTES..PUBKEY_BYTES + HASH_BYTES)
-                .ok_or_else(|| error!(ErrorCode::InvalidPublicValue))?,
+        CheckpointPublicValues::field(
+            &self.public_values,
+            CheckpointPublicValues::INITIAL_HASH_OFFSET,
         )
-        .map_err(|_| error!(ErrorCode::InvalidPublicValue))
     }
 
+    /// The blober hash after the proven slots, which only needs the public values to be long
+    /// enough to hold it.
     pub fn final_hash(&self) -> Result<[u8; HASH_BYTES]> {
-        bincode::deserialize::<[u8; HASH_BYTES]>(
-            self.public_values
-                .get(PUBKEY_BYTES + HASH_BYTES..)
-                .ok_or_else(|| error!(ErrorCode::InvalidPublicValue))?,
+        CheckpointPublicValues::field(
+            &self.public_values,
+            CheckpointPublicValues::FINAL_HASH_OFFSET,
         )
-        .map_err(|_| error!(ErrorCode::InvalidPublicValue))
     }
 
+    /// The commitments following the base ones, which are specific to the verifier program that
+    /// created the checkpoint.
     pub fn non_base_commitments(&self) -> Option<&[u8]> {
-        self.public_values.get(PUBKEY_BYTES + HASH_BYTES * 2..)
+        self.parsed_public_values().ok()?.extra_commitment()
     }
 
     #[cfg(feature = "cpi")]
@@ -135,6 +139,69 @@ impl Checkpoint {
     }
 }
 
+/// The public values committed by a checkpoint proof, as laid out by the prover programs: the
+/// blober address, the blober hash before and after the proven slots, and then any commitments
+/// specific to the verifier program, such as the SLA score of the pob-sla verifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckpointPublicValues<'a> {
+    public_values: &'a [u8],
+}
+
+impl<'a> CheckpointPublicValues<'a> {
+    /// The offset of the blober address.
+    pub const BLOBER_OFFSET: usize = 0;
+    /// The offset of the blober hash before the proven slots.
+    pub const INITIAL_HASH_OFFSET: usize = Self::BLOBER_OFFSET + PUBKEY_BYTES;
+    /// The offset of the blober hash after the proven slots.
+    pub const FINAL_HASH_OFFSET: usize = Self::INITIAL_HASH_OFFSET + HASH_BYTES;
+    /// The offset of the verifier-specific commitments, which is also the length of the base
+    /// commitments every checkpoint proof has.
+    pub const EXTRA_COMMITMENT_OFFSET: usize = Self::FINAL_HASH_OFFSET + HASH_BYTES;
+
+    /// Parses public values, failing if they are too short to hold the base commitments.
+    pub fn new(public_values: &'a [u8]) -> Result<Self> {
+        if public_values.len() < Self::EXTRA_COMMITMENT_OFFSET {
+            return Err(error!(ErrorCode::PublicValuesTooShort));
+        }
+        Ok(Self { public_values })
+    }
+
+    /// The address of the blober the proof is for.
+    pub fn blober(&self) -> Pubkey {
+        Pubkey::new_from_array(self.array(Self::BLOBER_OFFSET))
+    }
+
+    /// The blober hash before the proven slots.
+    pub fn initial_hash(&self) -> [u8; HASH_BYTES] {
+        self.array(Self::INITIAL_HASH_OFFSET)
+    }
+
+    /// The blober hash after the proven slots.
+    pub fn final_hash(&self) -> [u8; HASH_BYTES] {
+        self.array(Self::FINAL_HASH_OFFSET)
+    }
+
+    /// The verifier-specific commitments following the base ones, or `None` if there are none.
+    pub fn extra_commitment(&self) -> Option<&'a [u8]> {
+        Some(&self.public_values[Self::EXTRA_COMMITMENT_OFFSET..]).filter(|extra| !extra.is_empty())
+    }
+
+    /// Reads a single field of possibly truncated public values, failing only if that field is
+    /// cut off.
+    pub(crate) fn field<const N: usize>(public_values: &[u8], offset: usize) -> Result<[u8; N]> {
+        public_values
+            .get(offset..offset + N)
+            .and_then(|field| field.try_into().ok())
+            .ok_or_else(|| error!(ErrorCode::PublicValuesTooShort))
+    }
+
+    fn array<const N: usize>(&self, offset: usize) -> [u8; N] {
+        let mut array = [0; N];
+        array.copy_from_slice(&self.public_values[offset..offset + N]);
+        array
+    }
+}
+
 #[account]
 #[derive(Debug, InitSpace, PartialEq, Eq, PartialOrd, Ord)]
 pub struct CheckpointConfig {
@@ -146,6 +213,84 @@ pub struct CheckpointConfig {
 mod tests {
     use super::*;
 
+    /// Public values committed by the data-correctness prover program.
+    const DATA_CORRECTNESS_PUBLIC_VALUES: [u8; 96] = [
+        3, 145, 232, 95, 237, 197, 86, 36, 133, 7, 130, 192, 44, 20, 165, 56, 142, 241, 131, 217,
+        169, 251, 153, 244, 24, 200, 141, 237, 87, 185, 20, 36, 227, 176, 196, 66, 152, 252, 28,
+        20, 154, 251, 244, 200, 153, 111, 185, 36, 39, 174, 65, 228, 100, 155, 147, 76, 164, 149,
+        153, 27, 120, 82, 184, 85, 235, 11, 55, 168, 251, 31, 209, 12, 182, 200, 154, 42, 5, 29,
+        196, 222, 85, 16, 54, 24, 5, 250, 103, 79, 41, 124, 74, 196, 185, 94, 22, 176,
+    ];
+
+    #[test]
+    fn public_values_match_committed_layout() {
+        let blober = Pubkey::new_unique();
+        let initial_hash = [1; HASH_BYTES];
+        let final_hash = [2; HASH_BYTES];
+        // Committed the same way as the prover programs do, with the pob-sla score at the end.
+        let base = [
+            bincode::serialize(&blober).unwrap(),
+            bincode::serialize(&initial_hash).unwrap(),
+            bincode::serialize(&final_hash).unwrap(),
+        ]
+        .concat();
+        let sla = [vec![0], bincode::serialize(&0.5f64).unwrap()].concat();
+
+        let parsed = CheckpointPublicValues::new(&base).unwrap();
+        assert_eq!(parsed.blober(), blober);
+        assert_eq!(parsed.initial_hash(), initial_hash);
+        assert_eq!(parsed.final_hash(), final_hash);
+        assert_eq!(parsed.extra_commitment(), None);
+
+        let with_sla = [base.as_slice(), &sla].concat();
+        let parsed = CheckpointPublicValues::new(&with_sla).unwrap();
+        assert_eq!(parsed.blober(), blober);
+        assert_eq!(parsed.final_hash(), final_hash);
+        assert_eq!(parsed.extra_commitment(), Some(sla.as_slice()));
+
+        assert!(CheckpointPublicValues::new(&base[..base.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn checkpoint_accessors_use_public_values_parser() {
+        let checkpoint = Checkpoint {
+            slot: 1,
+            proof: [0; GROTH16_PROOF_SIZE],
+            verification_key: String::new(),
+            public_values: DATA_CORRECTNESS_PUBLIC_VALUES.to_vec(),
+        };
+        let parsed = CheckpointPublicValues::new(&DATA_CORRECTNESS_PUBLIC_VALUES).unwrap();
+
+        assert_eq!(checkpoint.blober().unwrap(), parsed.blober());
+        assert_eq!(
+            checkpoint.blober().unwrap().to_bytes(),
+            DATA_CORRECTNESS_PUBLIC_VALUES[..PUBKEY_BYTES]
+        );
+        assert_eq!(checkpoint.initial_hash().unwrap(), parsed.initial_hash());
+        assert_eq!(checkpoint.final_hash().unwrap(), parsed.final_hash());
+        assert_eq!(
+            checkpoint.final_hash().unwrap(),
+            DATA_CORRECTNESS_PUBLIC_VALUES[CheckpointPublicValues::FINAL_HASH_OFFSET..]
+        );
+        assert_eq!(checkpoint.non_base_commitments(), None);
+    }
+
+    #[test]
+    fn checkpoint_accessors_only_need_their_own_field() {
+        let blober = Pubkey::new_unique();
+        let checkpoint = Checkpoint {
+            slot: 1,
+            proof: [0; GROTH16_PROOF_SIZE],
+            verification_key: String::new(),
+            public_values: [blober.to_bytes().as_slice(), &[1; HASH_BYTES]].concat(),
+        };
+
+        assert_eq!(checkpoint.blober().unwrap(), blober);
+        assert_eq!(checkpoint.initial_hash().unwrap(), [1; HASH_BYTES]);
+        assert!(checkpoint.final_hash().is_err());
+        assert!(checkpoint.parsed_public_values().is_err());
+    }
+
     #[test]
     fn test_checkpoint_serde() {
         let checkpoint = Checkpoint {
@@ -0,0 +1,12 @@
+use anchor_lang::{prelude::*, solana_program::hash::HASH_BYTES};
+
+/// Emitted when a blob is finalized and its hash is folded into the blober.
+#[event]
+#[derive(Debug, PartialEq, Eq)]
+pub struct BlobFinalized {
+    pub blober: Pubkey,
+    pub blob: Pubkey,
+    pub digest: [u8; HASH_BYTES],
+    pub size: u32,
+    pub slot: u64,
+}
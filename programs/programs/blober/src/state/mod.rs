@@ -2,3 +2,4 @@ pub mod bitmap;
 pub mod blob;
 pub mod blober;
 pub mod checkpoint;
+pub mod events;
@@ -1,20 +1,33 @@
 use anchor_lang::{
-    prelude::{AccountInfo, Pubkey},
-    solana_program::{self, clock::Clock, hash::HASH_BYTES, instruction::Instruction},
-    AccountDeserialize, InstructionData, ToAccountMetas,
+    prelude::{AccountInfo, AccountMeta, ProgramError, Pubkey},
+    solana_program::{
+        self, clock::Clock, hash::HASH_BYTES, instruction::Instruction, pubkey::PUBKEY_BYTES,
+    },
+    AccountDeserialize, AnchorDeserialize, Discriminator, InstructionData, ToAccountMetas,
 };
+use base64::{engine::general_purpose::STANDARD, Engine};
 use rand::{prelude::SliceRandom, thread_rng};
 use solana_program_test::*;
 use solana_sdk::{
+    instruction::InstructionError,
     signature::{Keypair, Signer},
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
 
 use crate::{
-    accounts, compute_blob_digest, find_blob_address, find_blober_address, hash_blob, id,
-    initial_hash, instruction, merge_hashes,
-    state::{blob::Blob, blober::Blober},
-    try_entry, CHUNK_SIZE,
+    accounts, chunk_crc, compute_blob_digest,
+    error::ErrorCode,
+    find_blob_address, find_blober_address, find_checkpoint_address,
+    find_checkpoint_config_address, find_checkpoint_signer_address, hash_blob, id, initial_hash,
+    instruction, is_valid_namespace, merge_hashes, predict_blober_hash,
+    state::{
+        blob::Blob,
+        blober::Blober,
+        checkpoint::{Checkpoint, CheckpointConfig, CheckpointPublicValues},
+        events::BlobFinalized,
+    },
+    try_entry, BLOB_SLOT_TOTAL_DELAY_LIMIT, CHECKPOINT_PDA_SIGNER_SEED, CHECKPOINT_SEED,
+    CHUNK_SIZE, GROTH16_PROOF_SIZE, MAX_NAMESPACE_LENGTH, SEED,
 };
 
 #[test]
@@ -147,6 +160,29 @@ async fn upload_blob(
     (blob, blob_digest)
 }
 
+/// Builds the `Initialize` instruction creating `blober` for `namespace`, trusting `payer`.
+fn initialize_instruction(
+    program_id: Pubkey,
+    blober: Pubkey,
+    payer: Pubkey,
+    namespace: &str,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: accounts::Initialize {
+            blober,
+            payer,
+            system_program: solana_program::system_program::id(),
+        }
+        .to_account_metas(None),
+        data: instruction::Initialize {
+            namespace: namespace.to_string(),
+            trusted: payer,
+        }
+        .data(),
+    }
+}
+
 #[tokio::test]
 async fn test_100k_blob() {
     solana_logger::setup();
@@ -205,132 +241,1068 @@ async fn test_100k_blob() {
 
     let blob = Blob::try_deserialize(&mut &blob.data[..]).expect("failed to deserialize blob");
 
-    println!("blob: {blob:?}");
-    assert_eq!(blob.blob_digest(), &blob_digest);
+    println!("blob: {blob:?}");
+    assert_eq!(blob.blob_digest(), &blob_digest);
+}
+
+#[tokio::test]
+async fn idle_blob_fails() {
+    let program_id = id();
+    let system_program = solana_program::system_program::id();
+
+    let program_test = ProgramTest::new("blob", program_id, processor!(test_entry));
+    let mut context = program_test.start_with_context().await;
+    let mut banks_client = context.banks_client.clone();
+    let payer = context.payer.insecure_clone();
+
+    let data_len = 100 * 1024;
+    let data: Vec<_> = (0u8..255).cycle().take(data_len).collect();
+
+    let chunks = data.chunks(CHUNK_SIZE as usize).collect::<Vec<_>>();
+    println!("num chunks: {} * {}", chunks.len(), chunks[0].len());
+
+    let blober = find_blober_address(program_id, payer.pubkey(), "test");
+    let blob = find_blob_address(program_id, payer.pubkey(), blober, 0, data_len);
+
+    // Create blober account.
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: accounts::Initialize {
+                    blober,
+                    payer: payer.pubkey(),
+                    system_program,
+                }
+                .to_account_metas(None),
+                data: instruction::Initialize {
+                    namespace: "test".to_string(),
+                    trusted: payer.pubkey(),
+                }
+                .data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+
+        process_transaction(&mut banks_client, transaction)
+            .await
+            .expect("failed to create blober account");
+    }
+
+    // Create blob
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: accounts::DeclareBlob {
+                    blob,
+                    blober,
+                    payer: payer.pubkey(),
+                    system_program,
+                }
+                .to_account_metas(None),
+                data: instruction::DeclareBlob {
+                    timestamp: 0,
+                    blob_size: data.len() as u32,
+                }
+                .data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+
+        process_transaction(&mut banks_client, transaction)
+            .await
+            .expect("failed to create blob");
+    }
+
+    // Randomly permute the chunks to simulate them arriving out of order.
+    let mut indexed_chunks: Vec<_> = chunks.iter().enumerate().collect();
+    indexed_chunks.shuffle(&mut thread_rng());
+
+    // This runs all chunks in sequence
+    for (chunk_index, chunk_data) in &indexed_chunks {
+        println!("chunk {chunk_index}");
+        let mut banks_client = banks_client.clone();
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: accounts::InsertChunk {
+                    blob,
+                    blober,
+                    payer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+                data: instruction::InsertChunk {
+                    idx: *chunk_index as u16,
+                    data: chunk_data.to_vec(),
+                }
+                .data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+        // Delay an arbitrary chunk.
+        if *chunk_index == 10 {
+            // Warp the validator to simulate time passing.
+            let current_slot = banks_client.get_sysvar::<Clock>().await.unwrap().slot;
+            let target_slot = current_slot + 2000 + 1;
+            println!("warping from slot {current_slot} to {target_slot}");
+            context.warp_to_slot(target_slot).unwrap();
+
+            // Inserting the chunk should fail.
+            process_transaction(&mut banks_client, transaction)
+                .await
+                .unwrap_err();
+            return;
+        } else {
+            process_transaction(&mut banks_client, transaction)
+                .await
+                .unwrap_or_else(|_| panic!("failed to upload chunk {chunk_index}"));
+        }
+    }
+}
+
+/// Creates a blober and declares an empty blob in it, returning the blober and blob addresses.
+async fn declare_abandoned_blob(context: &mut ProgramTestContext) -> (Pubkey, Pubkey) {
+    let program_id = id();
+    let system_program = solana_program::system_program::id();
+    let payer = context.payer.insecure_clone();
+
+    let blober = find_blober_address(program_id, payer.pubkey(), "test");
+    let blob = find_blob_address(program_id, payer.pubkey(), blober, 0, 1024);
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            initialize_instruction(program_id, blober, payer.pubkey(), "test"),
+            Instruction {
+                program_id,
+                accounts: accounts::DeclareBlob {
+                    blob,
+                    blober,
+                    payer: payer.pubkey(),
+                    system_program,
+                }
+                .to_account_metas(None),
+                data: instruction::DeclareBlob {
+                    timestamp: 0,
+                    blob_size: 1024,
+                }
+                .data(),
+            },
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        context.banks_client.get_latest_blockhash().await.unwrap(),
+    );
+    process_transaction(&mut context.banks_client, transaction)
+        .await
+        .expect("failed to declare blob");
+
+    (blober, blob)
+}
+
+/// Builds a `DiscardExpired` transaction paid for and signed by `signer` alone.
+fn discard_expired(
+    signer: &Keypair,
+    payer: Pubkey,
+    blober: Pubkey,
+    blob: Pubkey,
+    blockhash: solana_sdk::hash::Hash,
+) -> Transaction {
+    Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id: id(),
+            accounts: accounts::DiscardExpired {
+                blob,
+                blober,
+                payer,
+            }
+            .to_account_metas(None),
+            data: instruction::DiscardExpired {}.data(),
+        }],
+        Some(&signer.pubkey()),
+        &[signer],
+        blockhash,
+    )
+}
+
+#[tokio::test]
+async fn discard_expired_blob() {
+    let program_test = ProgramTest::new("blob", id(), processor!(test_entry));
+    let mut context = program_test.start_with_context().await;
+    let (blober, blob) = declare_abandoned_blob(&mut context).await;
+    let payer = context.payer.pubkey();
+
+    let current_slot = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .slot;
+    context
+        .warp_to_slot(current_slot + BLOB_SLOT_TOTAL_DELAY_LIMIT + 1)
+        .unwrap();
+
+    // Anyone can discard an expired blob, so fund an unrelated account to send the transaction.
+    let stranger = Keypair::new();
+    let transaction = Transaction::new_signed_with_payer(
+        &[solana_sdk::system_instruction::transfer(
+            &payer,
+            &stranger.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&payer),
+        &[&context.payer],
+        context.banks_client.get_latest_blockhash().await.unwrap(),
+    );
+    process_transaction(&mut context.banks_client, transaction)
+        .await
+        .expect("failed to fund stranger");
+
+    let blob_rent = context
+        .banks_client
+        .get_balance(blob)
+        .await
+        .expect("failed to get blob balance");
+    let payer_balance = context.banks_client.get_balance(payer).await.unwrap();
+
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    process_transaction(
+        &mut context.banks_client,
+        discard_expired(&stranger, payer, blober, blob, blockhash),
+    )
+    .await
+    .expect("failed to discard expired blob");
+
+    assert!(context
+        .banks_client
+        .get_account(blob)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        context.banks_client.get_balance(payer).await.unwrap(),
+        payer_balance + blob_rent
+    );
+}
+
+#[tokio::test]
+async fn discard_unexpired_blob_fails() {
+    let program_test = ProgramTest::new("blob", id(), processor!(test_entry));
+    let mut context = program_test.start_with_context().await;
+    let (blober, blob) = declare_abandoned_blob(&mut context).await;
+    let payer = context.payer.insecure_clone();
+
+    let current_slot = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .slot;
+    context
+        .warp_to_slot(current_slot + BLOB_SLOT_TOTAL_DELAY_LIMIT - 1)
+        .unwrap();
+
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let err = process_transaction(
+        &mut context.banks_client,
+        discard_expired(&payer, payer.pubkey(), blober, blob, blockhash),
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            BanksClientError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(code),
+            )) if code == u32::from(ErrorCode::BlobNotExpired)
+        ),
+        "Expected BlobNotExpired, got {err:?}"
+    );
+    assert!(context
+        .banks_client
+        .get_account(blob)
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn finalize_incomplete_blob_fails() {
+    let program_id = id();
+    let system_program = solana_program::system_program::id();
+
+    let program_test = ProgramTest::new("blob", program_id, processor!(test_entry));
+    let (mut banks_client, payer, _) = program_test.start().await;
+
+    // The declared size needs three chunks, the last one short.
+    let data: Vec<_> = (0u8..255)
+        .cycle()
+        .take(2 * CHUNK_SIZE as usize + 1)
+        .collect();
+    let blober = find_blober_address(program_id, payer.pubkey(), "test");
+    let blob = find_blob_address(program_id, payer.pubkey(), blober, 0, data.len());
+
+    let instructions = [
+        initialize_instruction(program_id, blober, payer.pubkey(), "test"),
+        Instruction {
+            program_id,
+            accounts: accounts::DeclareBlob {
+                blob,
+                blober,
+                payer: payer.pubkey(),
+                system_program,
+            }
+            .to_account_metas(None),
+            data: instruction::DeclareBlob {
+                timestamp: 0,
+                blob_size: data.len() as u32,
+            }
+            .data(),
+        },
+    ];
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &[&payer],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+    process_transaction(&mut banks_client, transaction)
+        .await
+        .expect("failed to declare blob");
+
+    // Only insert the first two chunks.
+    for (idx, chunk) in data.chunks(CHUNK_SIZE as usize).take(2).enumerate() {
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: accounts::InsertChunk {
+                    blob,
+                    blober,
+                    payer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+                data: instruction::InsertChunk {
+                    idx: idx as u16,
+                    data: chunk.to_vec(),
+                }
+                .data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+        process_transaction(&mut banks_client, transaction)
+            .await
+            .unwrap_or_else(|_| panic!("failed to insert chunk {idx}"));
+    }
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id,
+            accounts: accounts::FinalizeBlob {
+                blober,
+                blob,
+                payer: payer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: instruction::FinalizeBlob {}.data(),
+        }],
+        Some(&payer.pubkey()),
+        &[&payer],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+    let err = process_transaction(&mut banks_client, transaction)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            BanksClientError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(code),
+            )) if code == u32::from(ErrorCode::BlobNotComplete)
+        ),
+        "Expected BlobNotComplete, got {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn chunk_with_wrong_length_is_rejected() {
+    let program_id = id();
+    let system_program = solana_program::system_program::id();
+
+    let program_test = ProgramTest::new("blob", program_id, processor!(test_entry));
+    let (mut banks_client, payer, _) = program_test.start().await;
+
+    // The declared size needs three chunks, the last one a single byte.
+    let blob_size = 2 * CHUNK_SIZE as u32 + 1;
+    let blober = find_blober_address(program_id, payer.pubkey(), "test");
+    let blob = find_blob_address(program_id, payer.pubkey(), blober, 0, blob_size as usize);
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            initialize_instruction(program_id, blober, payer.pubkey(), "test"),
+            Instruction {
+                program_id,
+                accounts: accounts::DeclareBlob {
+                    blob,
+                    blober,
+                    payer: payer.pubkey(),
+                    system_program,
+                }
+                .to_account_metas(None),
+                data: instruction::DeclareBlob {
+                    timestamp: 0,
+                    blob_size,
+                }
+                .data(),
+            },
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+    process_transaction(&mut banks_client, transaction)
+        .await
+        .expect("failed to create blober and blob");
+
+    let insert = |idx: u16, len: usize, blockhash| {
+        Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: accounts::InsertChunk {
+                    blob,
+                    blober,
+                    payer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+                data: instruction::InsertChunk {
+                    idx,
+                    data: vec![idx as u8; len],
+                }
+                .data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            blockhash,
+        )
+    };
+
+    let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    // A short chunk that isn't the last one, and a last chunk longer than the remainder.
+    for (idx, len) in [(0, CHUNK_SIZE as usize - 1), (2, CHUNK_SIZE as usize)] {
+        let err = process_transaction(&mut banks_client, insert(idx, len, blockhash))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                BanksClientError::TransactionError(TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(code),
+                )) if code == u32::from(ErrorCode::ChunkSizeMismatch)
+            ),
+            "Expected ChunkSizeMismatch for chunk {idx}, got {err:?}"
+        );
+    }
+
+    // An empty chunk past the end of the blob matches its expected length of zero, so the index
+    // itself must be rejected.
+    let err = process_transaction(&mut banks_client, insert(3, 0, blockhash))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            BanksClientError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(code),
+            )) if code == u32::from(ErrorCode::ChunkIndexOutOfBounds)
+        ),
+        "Expected ChunkIndexOutOfBounds, got {err:?}"
+    );
+
+    for (idx, len) in [(0, CHUNK_SIZE as usize), (1, CHUNK_SIZE as usize), (2, 1)] {
+        process_transaction(&mut banks_client, insert(idx, len, blockhash))
+            .await
+            .unwrap_or_else(|_| panic!("chunk {idx} with the expected length should be accepted"));
+    }
+}
+
+#[tokio::test]
+async fn bad_chunk_crc_is_rejected() {
+    let program_id = id();
+    let system_program = solana_program::system_program::id();
+
+    let program_test = ProgramTest::new("blob", program_id, processor!(test_entry));
+    let (mut banks_client, payer, _) = program_test.start().await;
+
+    let data: Vec<_> = (0u8..255).cycle().take(CHUNK_SIZE as usize).collect();
+
+    let blober = find_blober_address(program_id, payer.pubkey(), "test");
+    let blob = find_blob_address(program_id, payer.pubkey(), blober, 0, data.len());
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            initialize_instruction(program_id, blober, payer.pubkey(), "test"),
+            Instruction {
+                program_id,
+                accounts: accounts::DeclareBlob {
+                    blob,
+                    blober,
+                    payer: payer.pubkey(),
+                    system_program,
+                }
+                .to_account_metas(None),
+                data: instruction::DeclareBlob {
+                    timestamp: 0,
+                    blob_size: data.len() as u32,
+                }
+                .data(),
+            },
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+    process_transaction(&mut banks_client, transaction)
+        .await
+        .expect("failed to create blober and blob");
+
+    let insert = |crc: u32, blockhash| {
+        Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: accounts::InsertChunk {
+                    blob,
+                    blober,
+                    payer: payer.pubkey(),
+                }
+                .to_account_metas(None),
+                data: instruction::InsertChunkWithCrc {
+                    idx: 0,
+                    data: data.clone(),
+                    crc,
+                }
+                .data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            blockhash,
+        )
+    };
+
+    let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let err = process_transaction(&mut banks_client, insert(chunk_crc(&data) ^ 1, blockhash))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            BanksClientError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(code),
+            )) if code == u32::from(ErrorCode::ChunkCrcMismatch)
+        ),
+        "Expected ChunkCrcMismatch, got {err:?}"
+    );
+
+    process_transaction(&mut banks_client, insert(chunk_crc(&data), blockhash))
+        .await
+        .expect("chunk with a matching CRC should be accepted");
+}
+
+#[tokio::test]
+async fn initialize_validates_namespace() {
+    let program_id = id();
+
+    let program_test = ProgramTest::new("blob", program_id, processor!(test_entry));
+    let (mut banks_client, payer, _) = program_test.start().await;
+
+    let initialize = |namespace: &str, blober: Pubkey, blockhash| {
+        Transaction::new_signed_with_payer(
+            &[initialize_instruction(
+                program_id,
+                blober,
+                payer.pubkey(),
+                namespace,
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            blockhash,
+        )
+    };
+    let assert_invalid_namespace = |err: BanksClientError| {
+        assert!(
+            matches!(
+                err,
+                BanksClientError::TransactionError(TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(code),
+                )) if code == u32::from(ErrorCode::InvalidNamespace)
+            ),
+            "Expected InvalidNamespace, got {err:?}"
+        );
+    };
+    let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+
+    let err = process_transaction(
+        &mut banks_client,
+        initialize(
+            "",
+            find_blober_address(program_id, payer.pubkey(), ""),
+            blockhash,
+        ),
+    )
+    .await
+    .unwrap_err();
+    assert_invalid_namespace(err);
+
+    let err = process_transaction(
+        &mut banks_client,
+        initialize(
+            "näme",
+            find_blober_address(program_id, payer.pubkey(), "näme"),
+            blockhash,
+        ),
+    )
+    .await
+    .unwrap_err();
+    assert_invalid_namespace(err);
+
+    // A namespace longer than a seed can't be used to derive the blober PDA at all, so it's
+    // rejected before the handler runs.
+    let too_long = "a".repeat(MAX_NAMESPACE_LENGTH as usize + 1);
+    process_transaction(
+        &mut banks_client,
+        initialize(&too_long, Pubkey::new_unique(), blockhash),
+    )
+    .await
+    .unwrap_err();
+
+    let longest = "a".repeat(MAX_NAMESPACE_LENGTH as usize);
+    let blober = find_blober_address(program_id, payer.pubkey(), &longest);
+    process_transaction(&mut banks_client, initialize(&longest, blober, blockhash))
+        .await
+        .expect("failed to initialize blober with a valid namespace");
+
+    let blober = banks_client.get_account(blober).await.unwrap().unwrap();
+    let blober =
+        Blober::try_deserialize(&mut &blober.data[..]).expect("failed to deserialize blober");
+    assert_eq!(blober.namespace, longest);
+}
+
+#[test]
+fn namespace_validation() {
+    assert!(is_valid_namespace("test"));
+    assert!(is_valid_namespace("my-app_v1.0"));
+    assert!(!is_valid_namespace(""));
+    assert!(!is_valid_namespace("with space"));
+    assert!(!is_valid_namespace("näme"));
+    assert!(!is_valid_namespace(
+        &"a".repeat(MAX_NAMESPACE_LENGTH as usize + 1)
+    ));
+}
+
+#[tokio::test]
+async fn set_trusted_requires_current_trusted_caller() {
+    let program_id = id();
+
+    let program_test = ProgramTest::new("blob", program_id, processor!(test_entry));
+    let (mut banks_client, payer, _) = program_test.start().await;
+
+    let blober = find_blober_address(program_id, payer.pubkey(), "test");
+    let transaction = Transaction::new_signed_with_payer(
+        &[initialize_instruction(
+            program_id,
+            blober,
+            payer.pubkey(),
+            "test",
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+    process_transaction(&mut banks_client, transaction)
+        .await
+        .expect("failed to create blober");
+
+    let intruder = Keypair::new();
+    let new_trusted = Keypair::new();
+    let set_trusted = |caller: &Keypair, new_trusted: Pubkey, blockhash| {
+        Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: accounts::SetTrusted {
+                    blober,
+                    caller: caller.pubkey(),
+                }
+                .to_account_metas(None),
+                data: instruction::SetTrusted { new_trusted }.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer, caller],
+            blockhash,
+        )
+    };
+    let assert_not_trusted = |err: BanksClientError| {
+        assert!(
+            matches!(
+                err,
+                BanksClientError::TransactionError(TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(code),
+                )) if code == u32::from(ErrorCode::NotTrustedCaller)
+            ),
+            "Expected NotTrustedCaller, got {err:?}"
+        );
+    };
+
+    let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let err = process_transaction(
+        &mut banks_client,
+        set_trusted(&intruder, intruder.pubkey(), blockhash),
+    )
+    .await
+    .unwrap_err();
+    assert_not_trusted(err);
+
+    process_transaction(
+        &mut banks_client,
+        set_trusted(&payer, new_trusted.pubkey(), blockhash),
+    )
+    .await
+    .expect("the trusted caller should be able to rotate");
+    let account = banks_client.get_account(blober).await.unwrap().unwrap();
+    let state = Blober::try_deserialize(&mut account.data.as_slice()).unwrap();
+    assert_eq!(state.caller, new_trusted.pubkey());
+    assert_eq!(state.hash, initial_hash());
+
+    // The previous trusted caller loses the right to rotate, the new one gains it.
+    let err = process_transaction(
+        &mut banks_client,
+        set_trusted(&payer, payer.pubkey(), blockhash),
+    )
+    .await
+    .unwrap_err();
+    assert_not_trusted(err);
+    process_transaction(
+        &mut banks_client,
+        set_trusted(&new_trusted, payer.pubkey(), blockhash),
+    )
+    .await
+    .expect("the new trusted caller should be able to rotate");
+}
+
+#[tokio::test]
+async fn finalize_blob_emits_event() {
+    let program_id = id();
+    let system_program = solana_program::system_program::id();
+
+    let program_test = ProgramTest::new("blober", program_id, processor!(test_entry));
+    let data: Vec<_> = (0u8..255).cycle().take(2 * CHUNK_SIZE as usize).collect();
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let blober = find_blober_address(program_id, payer.pubkey(), "test");
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[initialize_instruction(
+            program_id,
+            blober,
+            payer.pubkey(),
+            "test",
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+    process_transaction(&mut banks_client, transaction)
+        .await
+        .expect("failed to create blober account");
+
+    let (blob, blob_digest) = upload_blob(
+        program_id,
+        payer.insecure_clone(),
+        system_program,
+        &data,
+        &mut banks_client,
+        0,
+        blober,
+    )
+    .await;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id,
+            accounts: accounts::FinalizeBlob {
+                blober,
+                blob,
+                payer: payer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: instruction::FinalizeBlob {}.data(),
+        }],
+        Some(&payer.pubkey()),
+        &[&payer],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+    let tx = banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .unwrap();
+    tx.result.expect("failed to finalize blob");
+
+    let slot = banks_client.get_sysvar::<Clock>().await.unwrap().slot;
+    let events: Vec<_> = tx
+        .metadata
+        .expect("transaction metadata should be recorded")
+        .log_messages
+        .iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .map(|data| STANDARD.decode(data).expect("event data should be base64"))
+        .filter_map(|data| {
+            data.strip_prefix(BlobFinalized::DISCRIMINATOR)
+                .map(|mut event| BlobFinalized::deserialize(&mut event).unwrap())
+        })
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![BlobFinalized {
+            blober,
+            blob,
+            digest: blob_digest,
+            size: data.len() as u32,
+            slot,
+        }]
+    );
 }
 
 #[tokio::test]
-async fn idle_blob_fails() {
+async fn finalize_blobs_matches_sequential_finalization() {
     let program_id = id();
     let system_program = solana_program::system_program::id();
 
-    let program_test = ProgramTest::new("blob", program_id, processor!(test_entry));
-    let mut context = program_test.start_with_context().await;
-    let mut banks_client = context.banks_client.clone();
-    let payer = context.payer.insecure_clone();
-
-    let data_len = 100 * 1024;
-    let data: Vec<_> = (0u8..255).cycle().take(data_len).collect();
-
-    let chunks = data.chunks(CHUNK_SIZE as usize).collect::<Vec<_>>();
-    println!("num chunks: {} * {}", chunks.len(), chunks[0].len());
-
-    let blober = find_blober_address(program_id, payer.pubkey(), "test");
-    let blob = find_blob_address(program_id, payer.pubkey(), blober, 0, data_len);
+    let program_test = ProgramTest::new("blober", program_id, processor!(test_entry));
+    let sources: Vec<Vec<u8>> = vec![
+        (0u8..255).cycle().take(2 * 1024).collect(),
+        (10u8..100).cycle().take(3 * 1024).collect(),
+        (22u8..24).cycle().take(1024).collect(),
+    ];
+    let (mut banks_client, payer, _) = program_test.start().await;
 
-    // Create blober account.
-    {
+    let sequential = find_blober_address(program_id, payer.pubkey(), "sequential");
+    let batch = find_blober_address(program_id, payer.pubkey(), "batch");
+    let mut blobs = Vec::new();
+    for (blober, namespace) in [(sequential, "sequential"), (batch, "batch")] {
         let transaction = Transaction::new_signed_with_payer(
-            &[Instruction {
+            &[initialize_instruction(
                 program_id,
-                accounts: accounts::Initialize {
-                    blober,
-                    payer: payer.pubkey(),
-                    system_program,
-                }
-                .to_account_metas(None),
-                data: instruction::Initialize {
-                    namespace: "test".to_string(),
-                    trusted: payer.pubkey(),
-                }
-                .data(),
-            }],
+                blober,
+                payer.pubkey(),
+                namespace,
+            )],
             Some(&payer.pubkey()),
             &[&payer],
             banks_client.get_latest_blockhash().await.unwrap(),
         );
-
         process_transaction(&mut banks_client, transaction)
             .await
             .expect("failed to create blober account");
+
+        let mut uploaded = Vec::new();
+        for (timestamp, data) in sources.iter().enumerate() {
+            let (blob, digest) = upload_blob(
+                program_id,
+                payer.insecure_clone(),
+                system_program,
+                data,
+                &mut banks_client,
+                timestamp as u64,
+                blober,
+            )
+            .await;
+            uploaded.push((blob, digest, data.len()));
+        }
+        blobs.push(uploaded);
     }
+    let (sequential_blobs, batch_blobs) = (&blobs[0], &blobs[1]);
 
-    // Create blob
-    {
+    // Finalize the first blober's blobs one per transaction.
+    for (blob, _, _) in sequential_blobs {
         let transaction = Transaction::new_signed_with_payer(
             &[Instruction {
                 program_id,
-                accounts: accounts::DeclareBlob {
-                    blob,
-                    blober,
+                accounts: accounts::FinalizeBlob {
+                    blober: sequential,
+                    blob: *blob,
                     payer: payer.pubkey(),
-                    system_program,
                 }
                 .to_account_metas(None),
-                data: instruction::DeclareBlob {
-                    timestamp: 0,
-                    blob_size: data.len() as u32,
-                }
-                .data(),
+                data: instruction::FinalizeBlob {}.data(),
             }],
             Some(&payer.pubkey()),
             &[&payer],
             banks_client.get_latest_blockhash().await.unwrap(),
         );
-
         process_transaction(&mut banks_client, transaction)
             .await
-            .expect("failed to create blob");
+            .expect("failed to finalize blob");
     }
 
-    // Randomly permute the chunks to simulate them arriving out of order.
-    let mut indexed_chunks: Vec<_> = chunks.iter().enumerate().collect();
-    indexed_chunks.shuffle(&mut thread_rng());
+    let finalize_blobs = |blobs: &[Pubkey], blockhash| {
+        let mut accounts = accounts::FinalizeBlobs {
+            blober: batch,
+            payer: payer.pubkey(),
+        }
+        .to_account_metas(None);
+        accounts.extend(blobs.iter().map(|blob| AccountMeta::new(*blob, false)));
+        Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts,
+                data: instruction::FinalizeBlobs {}.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            blockhash,
+        )
+    };
+    let batch_keys: Vec<_> = batch_blobs.iter().map(|(blob, _, _)| *blob).collect();
+
+    // The blober itself must never be finalized as one of its blobs.
+    let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let err = process_transaction(
+        &mut banks_client,
+        finalize_blobs(&[batch_keys[0], batch], blockhash),
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            BanksClientError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(code),
+            )) if code == u32::from(ErrorCode::BlobNotInBlober)
+        ),
+        "Expected BlobNotInBlober, got {err:?}"
+    );
+
+    process_transaction(&mut banks_client, finalize_blobs(&batch_keys, blockhash))
+        .await
+        .expect("failed to finalize blobs");
+
+    for (blober, blobs) in [(sequential, sequential_blobs), (batch, batch_blobs)] {
+        let account = banks_client.get_account(blober).await.unwrap().unwrap();
+        let state =
+            Blober::try_deserialize(&mut &account.data[..]).expect("failed to deserialize blober");
+        let finalized: Vec<_> = blobs
+            .iter()
+            .map(|(blob, digest, size)| (*digest, *size as u32, *blob))
+            .collect();
+        assert_eq!(state.hash, predict_blober_hash(initial_hash(), &finalized));
+    }
+
+    for blob in batch_keys {
+        assert!(
+            banks_client.get_account(blob).await.unwrap().is_none(),
+            "Finalized blob {blob} should be closed"
+        );
+    }
+}
+
+#[tokio::test]
+async fn predict_blober_hash_matches_on_chain_hash() {
+    let program_id = id();
+    let system_program = solana_program::system_program::id();
+
+    let program_test = ProgramTest::new("blober", program_id, processor!(test_entry));
+    let (mut banks_client, payer, _) = program_test.start().await;
+    let blober = find_blober_address(program_id, payer.pubkey(), "test");
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[initialize_instruction(
+            program_id,
+            blober,
+            payer.pubkey(),
+            "test",
+        )],
+        Some(&payer.pubkey()),
+        &[&payer],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+    process_transaction(&mut banks_client, transaction)
+        .await
+        .expect("failed to create blober account");
+
+    assert_eq!(predict_blober_hash(initial_hash(), &[]), initial_hash());
+
+    let mut finalized = Vec::new();
+    let mut current = initial_hash();
+    for (timestamp, data) in [
+        (0u8..255).cycle().take(2 * 1024).collect::<Vec<_>>(),
+        (7u8..9).cycle().take(1024).collect(),
+    ]
+    .iter()
+    .enumerate()
+    {
+        let (blob, digest) = upload_blob(
+            program_id,
+            payer.insecure_clone(),
+            system_program,
+            data,
+            &mut banks_client,
+            timestamp as u64,
+            blober,
+        )
+        .await;
 
-    // This runs all chunks in sequence
-    for (chunk_index, chunk_data) in &indexed_chunks {
-        println!("chunk {chunk_index}");
-        let mut banks_client = banks_client.clone();
         let transaction = Transaction::new_signed_with_payer(
             &[Instruction {
                 program_id,
-                accounts: accounts::InsertChunk {
-                    blob,
+                accounts: accounts::FinalizeBlob {
                     blober,
+                    blob,
                     payer: payer.pubkey(),
                 }
                 .to_account_metas(None),
-                data: instruction::InsertChunk {
-                    idx: *chunk_index as u16,
-                    data: chunk_data.to_vec(),
-                }
-                .data(),
+                data: instruction::FinalizeBlob {}.data(),
             }],
             Some(&payer.pubkey()),
             &[&payer],
             banks_client.get_latest_blockhash().await.unwrap(),
         );
-        // Delay an arbitrary chunk.
-        if *chunk_index == 10 {
-            // Warp the validator to simulate time passing.
-            let current_slot = banks_client.get_sysvar::<Clock>().await.unwrap().slot;
-            let target_slot = current_slot + 2000 + 1;
-            println!("warping from slot {current_slot} to {target_slot}");
-            context.warp_to_slot(target_slot).unwrap();
-
-            // Inserting the chunk should fail.
-            process_transaction(&mut banks_client, transaction)
-                .await
-                .unwrap_err();
-            return;
-        } else {
-            process_transaction(&mut banks_client, transaction)
-                .await
-                .unwrap_or_else(|_| panic!("failed to upload chunk {chunk_index}"));
-        }
+        process_transaction(&mut banks_client, transaction)
+            .await
+            .expect("failed to finalize blob");
+
+        let account = banks_client.get_account(blober).await.unwrap().unwrap();
+        let state =
+            Blober::try_deserialize(&mut &account.data[..]).expect("failed to deserialize blober");
+        let blob = (digest, data.len() as u32, blob);
+
+        // Predicting from the previous on-chain hash and from scratch must agree with the program.
+        assert_eq!(state.hash, predict_blober_hash(current, &[blob]));
+        finalized.push(blob);
+        assert_eq!(state.hash, predict_blober_hash(initial_hash(), &finalized));
+        current = state.hash;
     }
 }
 
+#[test]
+fn chunk_crc_matches_crc32() {
+    // The standard CRC-32 (IEEE) check value.
+    assert_eq!(chunk_crc(b"123456789"), 0xCBF4_3926);
+    assert_eq!(chunk_crc(&[]), 0);
+}
+
 #[tokio::test]
 async fn hash_single_account() {
     let program_id = id();
@@ -929,3 +1901,299 @@ async fn hash_blober_itself() {
         .await
         .unwrap_err();
 }
+
+// Stands in for a verifier program: forwards a `create_checkpoint` instruction to the blober
+// program and signs it as the checkpoint PDA signer, without verifying the proof.
+fn mock_verifier_entry(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+    let instruction::CreateCheckpoint { blober, .. } = instruction::CreateCheckpoint::deserialize(
+        &mut &data[instruction::CreateCheckpoint::DISCRIMINATOR.len()..],
+    )
+    .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let (pda_signer, bump) = Pubkey::find_program_address(
+        &[
+            SEED,
+            CHECKPOINT_SEED,
+            CHECKPOINT_PDA_SIGNER_SEED,
+            blober.as_ref(),
+        ],
+        program_id,
+    );
+
+    let mut account_metas = accounts::CreateCheckpoint {
+        checkpoint: find_checkpoint_address(id(), blober),
+        checkpoint_config: find_checkpoint_config_address(id(), blober),
+        pda_signer,
+        payer: *accounts[3].key,
+        system_program: solana_program::system_program::id(),
+    }
+    .to_account_metas(None);
+    account_metas[2].is_signer = true;
+
+    solana_program::program::invoke_signed(
+        &Instruction {
+            program_id: id(),
+            accounts: account_metas,
+            data: data.to_vec(),
+        },
+        accounts,
+        &[&[
+            SEED,
+            CHECKPOINT_SEED,
+            CHECKPOINT_PDA_SIGNER_SEED,
+            blober.as_ref(),
+            &[bump],
+        ]],
+    )
+}
+
+/// Creates a blober owned by `payer` and makes `verifier` its checkpoint authority.
+async fn initialize_blober_with_verifier(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    verifier: Pubkey,
+) -> Pubkey {
+    let program_id = id();
+    let system_program = solana_program::system_program::id();
+    let blober = find_blober_address(program_id, payer.pubkey(), "test");
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            initialize_instruction(program_id, blober, payer.pubkey(), "test"),
+            Instruction {
+                program_id,
+                accounts: accounts::ConfigureCheckpoint {
+                    checkpoint: find_checkpoint_address(program_id, blober),
+                    checkpoint_config: find_checkpoint_config_address(program_id, blober),
+                    blober,
+                    payer: payer.pubkey(),
+                    system_program,
+                }
+                .to_account_metas(None),
+                data: instruction::ConfigureCheckpoint {
+                    authority: verifier,
+                }
+                .data(),
+            },
+        ],
+        Some(&payer.pubkey()),
+        &[payer],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+
+    process_transaction(banks_client, transaction)
+        .await
+        .expect("failed to configure checkpoint");
+
+    blober
+}
+
+/// Creates a checkpoint through the [`mock_verifier_entry`] deployed at `verifier`.
+async fn create_checkpoint(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    verifier: Pubkey,
+    checkpoint: instruction::CreateCheckpoint,
+) -> std::result::Result<(), BanksClientError> {
+    let program_id = id();
+    let blober = checkpoint.blober;
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id: verifier,
+            accounts: vec![
+                AccountMeta::new(find_checkpoint_address(program_id, blober), false),
+                AccountMeta::new_readonly(
+                    find_checkpoint_config_address(program_id, blober),
+                    false,
+                ),
+                AccountMeta::new(find_checkpoint_signer_address(verifier, blober), false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new_readonly(program_id, false),
+            ],
+            data: checkpoint.data(),
+        }],
+        Some(&payer.pubkey()),
+        &[payer],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+
+    process_transaction(banks_client, transaction).await
+}
+
+#[tokio::test]
+async fn create_checkpoint_through_authority() {
+    let program_id = id();
+    let verifier = Pubkey::new_unique();
+
+    let mut program_test = ProgramTest::new("blober", program_id, processor!(test_entry));
+    program_test.add_program("verifier", verifier, processor!(mock_verifier_entry));
+    let (mut banks_client, payer, _) = program_test.start().await;
+
+    let blober = initialize_blober_with_verifier(&mut banks_client, &payer, verifier).await;
+
+    let proof = [7; GROTH16_PROOF_SIZE];
+    let public_values = [blober.to_bytes(), [1; HASH_BYTES], [2; HASH_BYTES]].concat();
+    let slot = 42;
+    create_checkpoint(
+        &mut banks_client,
+        &payer,
+        verifier,
+        instruction::CreateCheckpoint {
+            blober,
+            proof,
+            public_values: public_values.clone(),
+            verification_key: format!("0x{}", "ab".repeat(HASH_BYTES)),
+            slot,
+        },
+    )
+    .await
+    .expect("failed to create checkpoint");
+
+    let checkpoint = banks_client
+        .get_account(find_checkpoint_address(program_id, blober))
+        .await
+        .unwrap()
+        .unwrap();
+    let checkpoint = Checkpoint::try_deserialize(&mut &checkpoint.data[..])
+        .expect("failed to deserialize checkpoint");
+    assert_eq!(checkpoint.slot, slot);
+    assert_eq!(checkpoint.proof, proof);
+    assert_eq!(checkpoint.public_values, public_values);
+    assert_eq!(checkpoint.blober().unwrap(), blober);
+}
+
+#[tokio::test]
+async fn create_checkpoint_rejects_truncated_public_values() {
+    let verifier = Pubkey::new_unique();
+
+    let mut program_test = ProgramTest::new("blober", id(), processor!(test_entry));
+    program_test.add_program("verifier", verifier, processor!(mock_verifier_entry));
+    let (mut banks_client, payer, _) = program_test.start().await;
+
+    let blober = initialize_blober_with_verifier(&mut banks_client, &payer, verifier).await;
+
+    // Too short for even the blober address, let alone the hashes.
+    for len in [
+        PUBKEY_BYTES - 1,
+        CheckpointPublicValues::EXTRA_COMMITMENT_OFFSET - 1,
+    ] {
+        let public_values = [blober.to_bytes(), [1; HASH_BYTES], [2; HASH_BYTES]].concat();
+        let err = create_checkpoint(
+            &mut banks_client,
+            &payer,
+            verifier,
+            instruction::CreateCheckpoint {
+                blober,
+                proof: [7; GROTH16_PROOF_SIZE],
+                public_values: public_values[..len].to_vec(),
+                verification_key: format!("0x{}", "ab".repeat(HASH_BYTES)),
+                slot: 42,
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                BanksClientError::TransactionError(TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(code),
+                )) if code == u32::from(ErrorCode::PublicValuesTooShort)
+            ),
+            "Expected PublicValuesTooShort for {len} bytes, got {err:?}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn configure_checkpoint_sets_authority() {
+    let program_id = id();
+    let system_program = solana_program::system_program::id();
+
+    let program_test = ProgramTest::new("blober", program_id, processor!(test_entry));
+    let (mut banks_client, payer, _) = program_test.start().await;
+
+    let blober = find_blober_address(program_id, payer.pubkey(), "test");
+    let checkpoint_config = find_checkpoint_config_address(program_id, blober);
+
+    // Create blober account.
+    {
+        let transaction = Transaction::new_signed_with_payer(
+            &[initialize_instruction(
+                program_id,
+                blober,
+                payer.pubkey(),
+                "test",
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            banks_client.get_latest_blockhash().await.unwrap(),
+        );
+
+        process_transaction(&mut banks_client, transaction)
+            .await
+            .expect("failed to create blober account");
+    }
+
+    let configure = |authority: Pubkey, blockhash| {
+        Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id,
+                accounts: accounts::ConfigureCheckpoint {
+                    checkpoint: find_checkpoint_address(program_id, blober),
+                    checkpoint_config,
+                    blober,
+                    payer: payer.pubkey(),
+                    system_program,
+                }
+                .to_account_metas(None),
+                data: instruction::ConfigureCheckpoint { authority }.data(),
+            }],
+            Some(&payer.pubkey()),
+            &[&payer],
+            blockhash,
+        )
+    };
+
+    // The payer makes itself the authority first, so it's allowed to hand it over afterwards.
+    let verifier = Pubkey::new_unique();
+    for authority in [payer.pubkey(), verifier] {
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        process_transaction(&mut banks_client, configure(authority, blockhash))
+            .await
+            .expect("failed to configure checkpoint");
+
+        let config = banks_client
+            .get_account(checkpoint_config)
+            .await
+            .unwrap()
+            .unwrap();
+        let config = CheckpointConfig::try_deserialize(&mut &config.data[..])
+            .expect("failed to deserialize checkpoint config");
+        assert_eq!(config.authority, authority);
+        assert_eq!(config.blober, blober);
+    }
+
+    // The payer is no longer the authority, so it can't change it again.
+    let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let err = process_transaction(
+        &mut banks_client,
+        configure(Pubkey::new_unique(), blockhash),
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            BanksClientError::TransactionError(TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(code),
+            )) if code == u32::from(ErrorCode::Unauthorized)
+        ),
+        "Expected Unauthorized, got {err:?}"
+    );
+}
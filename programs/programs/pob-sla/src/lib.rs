@@ -30,8 +30,7 @@ pub mod data_anchor_pob_sla_verifier {
             .non_base_commitments()
             .ok_or_else(|| error!(PobSlaError::NoSlaCommitmentsFound))?;
 
-        let sla_score: f64 = bincode::deserialize(sla_bytes)
-            .map_err(|_| error!(PobSlaError::InvalidSlaScoreFormat))?;
+        let sla_score = deserialize_sla_score(sla_bytes)?;
 
         require_gte!(sla_score, 0.0, PobSlaError::InvalidScore);
 
@@ -106,6 +105,43 @@ impl<'info> From<&mut Verify<'info>>
     }
 }
 
+/// The precision of the SLA score committed by the PoB SLA prover program. The score is committed
+/// as this tag followed by the score itself, so deployments that don't need `f64` precision can
+/// halve the size of the commitment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum SlaScorePrecision {
+    #[default]
+    F64 = 0,
+    F32 = 1,
+}
+
+impl TryFrom<u8> for SlaScorePrecision {
+    type Error = Error;
+
+    fn try_from(tag: u8) -> Result<Self> {
+        match tag {
+            0 => Ok(SlaScorePrecision::F64),
+            1 => Ok(SlaScorePrecision::F32),
+            _ => Err(error!(PobSlaError::UnknownSlaScorePrecision)),
+        }
+    }
+}
+
+/// Deserializes an SLA score committed as a [`SlaScorePrecision`] tag followed by the score in
+/// that precision.
+pub fn deserialize_sla_score(bytes: &[u8]) -> Result<f64> {
+    let (&tag, score_bytes) = bytes
+        .split_first()
+        .ok_or_else(|| error!(PobSlaError::NoSlaCommitmentsFound))?;
+
+    match SlaScorePrecision::try_from(tag)? {
+        SlaScorePrecision::F64 => bincode::deserialize::<f64>(score_bytes),
+        SlaScorePrecision::F32 => bincode::deserialize::<f32>(score_bytes).map(f64::from),
+    }
+    .map_err(|_| error!(PobSlaError::InvalidSlaScoreFormat))
+}
+
 #[error_code]
 pub enum PobSlaError {
     #[msg("No SLA commitments found in public values")]
@@ -114,4 +150,57 @@ pub enum PobSlaError {
     InvalidSlaScoreFormat,
     #[msg("Invalid SLA score, must be greater than or equal to 0")]
     InvalidScore,
+    #[msg("Unknown SLA score precision tag")]
+    UnknownSlaScorePrecision,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mirrors how the prover program commits the score: the tag followed by the score, both
+    /// serialized with bincode.
+    fn commit(precision: SlaScorePrecision, score: f64) -> Vec<u8> {
+        let score = match precision {
+            SlaScorePrecision::F64 => bincode::serialize(&score),
+            SlaScorePrecision::F32 => bincode::serialize(&(score as f32)),
+        };
+        [
+            bincode::serialize(&(precision as u8)).unwrap(),
+            score.unwrap(),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn f64_score_round_trips() {
+        let bytes = commit(SlaScorePrecision::F64, 0.987654321);
+        assert_eq!(bytes.len(), 1 + 8);
+        assert_eq!(deserialize_sla_score(&bytes).unwrap(), 0.987654321f64);
+    }
+
+    #[test]
+    fn f32_score_round_trips() {
+        let bytes = commit(SlaScorePrecision::F32, 0.75);
+        assert_eq!(bytes.len(), 1 + 4);
+        assert_eq!(deserialize_sla_score(&bytes).unwrap(), 0.75);
+    }
+
+    #[test]
+    fn unknown_precision_is_rejected() {
+        let mut bytes = commit(SlaScorePrecision::F32, 0.75);
+        bytes[0] = 2;
+        let err = deserialize_sla_score(&bytes).unwrap_err();
+        assert!(
+            matches!(&err, Error::AnchorError(e) if e.error_name == "UnknownSlaScorePrecision"),
+            "Expected UnknownSlaScorePrecision, got {err:?}"
+        );
+    }
+
+    #[test]
+    fn mismatched_precision_is_rejected() {
+        let mut bytes = commit(SlaScorePrecision::F32, 0.75);
+        bytes[0] = SlaScorePrecision::F64 as u8;
+        assert!(deserialize_sla_score(&bytes).is_err());
+    }
 }